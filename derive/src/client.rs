@@ -0,0 +1,184 @@
+use proc_macro2::Span;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use syn::AttributeArgs;
+use syn::Error;
+use syn::Ident;
+use syn::ItemTrait;
+use syn::Meta;
+use syn::NestedMeta;
+use syn::Result;
+use syn::TraitItem;
+use syn::Type;
+
+use crate::internal::attr;
+use crate::internal::case;
+use crate::internal::Export;
+use crate::internal::Kind;
+
+pub fn expand(args: AttributeArgs, mut input: ItemTrait) -> Result<TokenStream> {
+  let mut name: Option<String> = None;
+
+  for arg in args.iter() {
+    match arg {
+      NestedMeta::Meta(meta @ Meta::NameValue(_)) if meta.path().is_ident("name") => {
+        name = Some(attr::string(meta)?.value());
+      }
+      _ => return Err(Error::new_spanned(arg, "unexpected client attribute")),
+    }
+  }
+
+  if !input.generics.params.is_empty() {
+    return Err(Error::new_spanned(&input.generics, "client traits can't be generic"));
+  }
+
+  let mut exports: Vec<Export> = Vec::new();
+
+  for item in input.items.iter_mut() {
+    if let TraitItem::Method(ref mut method) = item {
+      if let Some(export) = Export::from_ast(&mut method.attrs, &mut method.sig)? {
+        exports.push(export);
+      }
+    }
+  }
+
+  if exports.is_empty() {
+    return Err(Error::new(
+      Span::call_site(),
+      "expected at least one #[func] or #[view] method",
+    ));
+  }
+
+  let vis: _ = &input.vis;
+  let ident: Ident = match name {
+    Some(name) => Ident::new(&name, Span::call_site()),
+    None => format_ident!("{}Client", label(&input.ident)),
+  };
+
+  let doc: String = format!("A typed client for calling [`{}`] contracts.", input.ident);
+  let methods: _ = exports.iter().map(expand_method);
+  let descriptions: _ = exports.iter().map(Export::description);
+  let describe_doc: String = format!("Adds the funcs and views of [`{}`] to `schema`.", input.ident);
+
+  Ok(quote! {
+    #input
+
+    #[doc = #doc]
+    #[derive(Clone)]
+    #vis struct #ident {
+      contract: ::scarab::export::ScHname,
+    }
+
+    impl #ident {
+      /// Creates a new client for the contract `contract`.
+      pub const fn new(contract: ::scarab::export::ScHname) -> Self {
+        Self { contract }
+      }
+
+      /// Returns the hash name of the called contract.
+      pub fn contract(&self) -> ::scarab::export::ScHname {
+        self.contract.clone()
+      }
+
+      #[doc = #describe_doc]
+      pub fn describe(schema: &mut ::scarab::export::Schema) {
+        #(#descriptions;)*
+      }
+
+      #(#methods)*
+    }
+  })
+}
+
+/// Returns the client name of a trait: `IERC20` becomes `ERC20`.
+fn label(ident: &Ident) -> String {
+  let ident: String = ident.to_string();
+  let mut chars: _ = ident.chars();
+
+  match (chars.next(), chars.next()) {
+    (Some('I'), Some(next)) if next.is_uppercase() => ident[1..].to_string(),
+    _ => ident,
+  }
+}
+
+fn expand_method(export: &Export) -> TokenStream {
+  let ident: Ident = format_ident!("{}", case::snake(&export.ident.to_string()));
+  let name: &str = &export.name;
+
+  let params: _ = export.params.iter().map(|param| {
+    let ident: &Ident = &param.ident;
+    let ty: &Type = &param.ty;
+
+    match param.by_ref {
+      true => quote!(#ident: &#ty),
+      false => quote!(#ident: #ty),
+    }
+  });
+
+  let setters: _ = export.params.iter().map(|param| {
+    let ident: &Ident = &param.ident;
+    let key: &str = &param.key;
+    let ty: &Type = &param.ty;
+
+    match param.by_ref {
+      true => quote!(<#ty as ::scarab::export::ParamValue>::write_param(#ident, &__PARAMS, #key)),
+      false => quote!(<#ty as ::scarab::export::ParamValue>::write_param(&#ident, &__PARAMS, #key)),
+    }
+  });
+
+  let (context, transfer, call): (TokenStream, TokenStream, TokenStream) = match export.kind {
+    Kind::Func => (
+      quote!(::scarab::export::ScFuncContext),
+      quote!(transfer: ::scarab::export::ScTransfers),
+      quote!(ctx.call(
+        self.contract.clone(),
+        ::scarab::export::ScHname::new(#name),
+        ::core::option::Option::Some(__PARAMS),
+        ::core::option::Option::Some(transfer),
+      )),
+    ),
+    Kind::View => (
+      quote!(::scarab::export::ScViewContext),
+      quote!(),
+      quote!(ctx.call(
+        self.contract.clone(),
+        ::scarab::export::ScHname::new(#name),
+        ::core::option::Option::Some(__PARAMS),
+      )),
+    ),
+  };
+
+  let (output, body): (TokenStream, TokenStream) = match export.output {
+    Some(ref ty) => {
+      let result: &str = &export.result;
+      let message: String = format!("missing required results: {}", result);
+
+      (
+        quote!(-> #ty),
+        quote! {
+          let __RESULTS: ::scarab::export::ScImmutableMap = #call;
+
+          match <#ty as ::scarab::export::ResultValue>::read_result(&__RESULTS, #result) {
+            ::core::option::Option::Some(__value) => __value,
+            ::core::option::Option::None => ::scarab::panic::fail(#message),
+          }
+        },
+      )
+    }
+    None => (quote!(), quote!(#call;)),
+  };
+
+  let doc: String = format!("Calls `{}` on the contract.", name);
+
+  quote! {
+    #[doc = #doc]
+    pub fn #ident(&self, ctx: &#context, #(#params,)* #transfer) #output {
+      let __PARAMS: ::scarab::export::ScMutableMap = ::scarab::export::ScMutableMap::new();
+
+      #(#setters;)*
+
+      #body
+    }
+  }
+}
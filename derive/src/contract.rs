@@ -0,0 +1,230 @@
+use proc_macro2::Span;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use syn::AttributeArgs;
+use syn::Error;
+use syn::Ident;
+use syn::ImplItem;
+use syn::ItemImpl;
+use syn::Meta;
+use syn::NestedMeta;
+use syn::Path;
+use syn::Result;
+use syn::Type;
+
+use crate::internal::Export;
+use crate::internal::Kind;
+use crate::internal::Param;
+
+/// The arguments of `#[scarab::contract(...)]`.
+#[derive(Default)]
+struct Args {
+  /// `events(A, B)` - the event types added to the schema.
+  events: Vec<Path>,
+  /// `bridges(A__Bridge, B__Bridge)` - the bridges registered and described
+  /// along with the methods of the contract.
+  bridges: Vec<Path>,
+  /// `no_on_load` - skips generating `on_load`.
+  no_on_load: bool,
+}
+
+pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> Result<TokenStream> {
+  let args: Args = parse_args(&args)?;
+
+  if !input.generics.params.is_empty() {
+    return Err(Error::new_spanned(
+      &input.generics,
+      "contract impl blocks can't be generic",
+    ));
+  }
+
+  let mut exports: Vec<Export> = Vec::new();
+
+  for item in input.items.iter_mut() {
+    if let ImplItem::Method(ref mut method) = item {
+      if let Some(export) = Export::from_ast(&mut method.attrs, &mut method.sig)? {
+        exports.push(export);
+      }
+    }
+  }
+
+  if exports.is_empty() {
+    return Err(Error::new(
+      Span::call_site(),
+      "expected at least one #[func] or #[view] method",
+    ));
+  }
+
+  let self_ty: &Type = &input.self_ty;
+  let trait_: Option<&Path> = input.trait_.as_ref().map(|(_, path, _)| path);
+  let label: String = label(self_ty);
+
+  let wrappers: _ = exports.iter().map(|export| expand_wrapper(export, trait_, &label));
+  let registrations: _ = exports.iter().map(expand_registration);
+  let descriptions: _ = exports.iter().map(Export::description);
+  let events: &[Path] = &args.events;
+  let bridges: &[Path] = &args.bridges;
+
+  let on_load: TokenStream = match args.no_on_load {
+    true => quote!(),
+    false => quote! {
+      // Contracts are compiled natively for testing; only wasm modules are loaded by the host
+      #[cfg(target_arch = "wasm32")]
+      #[no_mangle]
+      fn on_load() {
+        <#self_ty as ::scarab::export::Export>::export();
+      }
+    },
+  };
+
+  Ok(quote! {
+    #input
+
+    const _: () = {
+      impl #self_ty {
+        #(#wrappers)*
+      }
+
+      impl ::scarab::export::Export for #self_ty {
+        fn register(exports: &::scarab::export::ScExports) {
+          <Self as ::scarab::export::Export>::register_with(exports);
+        }
+
+        fn register_with(registry: &dyn ::scarab::export::Registry) {
+          #(#registrations;)*
+          #(<Self as #bridges>::register(registry);)*
+        }
+
+        fn describe(schema: &mut ::scarab::export::Schema) {
+          #(#descriptions;)*
+          #(<Self as #bridges>::describe(schema);)*
+          #(schema.add_event(<#events as ::scarab::export::Event>::schema());)*
+        }
+      }
+
+      #on_load
+    };
+  })
+}
+
+/// Parses `#[contract(events(A, B), bridges(C__Bridge), no_on_load)]`.
+fn parse_args(args: &[NestedMeta]) -> Result<Args> {
+  let mut output: Args = Args::default();
+
+  for arg in args {
+    let list: _ = match arg {
+      NestedMeta::Meta(Meta::Path(path)) if path.is_ident("no_on_load") => {
+        output.no_on_load = true;
+        continue;
+      }
+      NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("events") => list,
+      NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("bridges") => list,
+      _ => return Err(Error::new_spanned(arg, "unexpected contract attribute")),
+    };
+
+    let (paths, expected): (&mut Vec<Path>, &str) = match list.path.is_ident("events") {
+      true => (&mut output.events, "expected event type"),
+      false => (&mut output.bridges, "expected bridge trait"),
+    };
+
+    for nested in list.nested.iter() {
+      match nested {
+        NestedMeta::Meta(Meta::Path(path)) => paths.push(path.clone()),
+        _ => return Err(Error::new_spanned(nested, expected)),
+      }
+    }
+  }
+
+  Ok(output)
+}
+
+/// Returns the name of the hidden function that bridges `export` to the host.
+fn wrapper(export: &Export) -> Ident {
+  match export.kind {
+    Kind::Func => format_ident!("__func_{}", export.ident),
+    Kind::View => format_ident!("__view_{}", export.ident),
+  }
+}
+
+fn label(ty: &Type) -> String {
+  match ty {
+    Type::Path(path) => path
+      .path
+      .segments
+      .last()
+      .map(|segment| segment.ident.to_string())
+      .unwrap_or_default(),
+    _ => String::from("Contract"),
+  }
+}
+
+fn expand_wrapper(export: &Export, trait_: Option<&Path>, label: &str) -> TokenStream {
+  let ident: &Ident = &export.ident;
+  let wrapper: Ident = wrapper(export);
+  let enter: String = format!("{}.{} [>]", label, export.name);
+  let leave: String = format!("{}.{} [<]", label, export.name);
+
+  let context: TokenStream = match export.kind {
+    Kind::Func => quote!(::scarab::export::ScFuncContext),
+    Kind::View => quote!(::scarab::export::ScViewContext),
+  };
+
+  let method: TokenStream = match trait_ {
+    Some(trait_) => quote!(<Self as #trait_>::#ident),
+    None => quote!(Self::#ident),
+  };
+
+  let params: _ = export.params.iter().map(expand_param);
+
+  let args: _ = export.params.iter().map(|param| {
+    let ident: &Ident = &param.ident;
+
+    match param.by_ref {
+      true => quote!(&#ident),
+      false => quote!(#ident),
+    }
+  });
+
+  let call: TokenStream = match export.output {
+    Some(_) => {
+      let result: &str = &export.result;
+      quote!(::scarab::export::ContextExt::result(__CTX, #result, #method(__CTX, #(#args),*)))
+    }
+    None => quote!(#method(__CTX, #(#args),*)),
+  };
+
+  quote! {
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    pub fn #wrapper(__CTX: &#context) {
+      ::scarab::trace!(#enter);
+
+      #(#params)*
+
+      #call;
+
+      ::scarab::trace!(#leave);
+    }
+  }
+}
+
+fn expand_param(param: &Param) -> TokenStream {
+  let ident: &Ident = &param.ident;
+  let key: &str = &param.key;
+  let ty: &Type = &param.ty;
+
+  quote! {
+    let #ident: #ty = ::scarab::export::ContextExt::get_required_param_value(__CTX, #key);
+  }
+}
+
+fn expand_registration(export: &Export) -> TokenStream {
+  let name: &str = &export.name;
+  let wrapper: Ident = wrapper(export);
+
+  match export.kind {
+    Kind::Func => quote!(registry.add_func(#name, Self::#wrapper)),
+    Kind::View => quote!(registry.add_view(#name, Self::#wrapper)),
+  }
+}
@@ -1,68 +1,229 @@
 use proc_macro2::Span;
 use proc_macro2::TokenStream;
 use quote::quote;
+use syn::parse_quote;
 use syn::DeriveInput;
 use syn::Error;
+use syn::Generics;
 use syn::Ident;
+use syn::Member;
+use syn::Path;
 use syn::Result;
 
 use crate::internal::Container;
 use crate::internal::Data;
+use crate::internal::Derive;
 use crate::internal::Field;
 use crate::internal::Style;
+use crate::internal::Variant;
+
+/// Selects between the panicking and fallible decoding traits.
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+  Decode,
+  TryDecode,
+}
 
 pub fn derive(input: DeriveInput) -> Result<TokenStream> {
   let container: Container = Container::from_ast(&input)?;
 
-  // TODO: Check Attributes
-  // TODO: Support Generics
+  container.data.check_versions(container.version)?;
 
   let ident: &Ident = container.ident;
   let dummy: Ident = Ident::new(&format!("_IMPL_DECODE_FOR_{}", ident), Span::call_site());
-  let tokens: TokenStream = expand_container(&container)?;
 
-  Ok(quote! {
-    #[allow(non_upper_case_globals)]
-    const #dummy: () = {
-      impl ::scarab::export::Decode for #ident {
-        fn decode(__DECODER: &mut ::scarab::export::BytesDecoder) -> Self {
+  let decode: TokenStream = {
+    let trait_: Path = parse_quote!(::scarab::export::Decode);
+    let generics: Generics = container.generics(Derive::Decode, &trait_);
+    let (impl_generics, ty_generics, where_clause): _ = generics.split_for_impl();
+    let tokens: TokenStream = expand_container(&container, Mode::Decode)?;
+
+    quote! {
+      impl #impl_generics #trait_ for #ident #ty_generics #where_clause {
+        fn decode(__DECODER: &mut ::scarab::export::Decoder<'_>) -> Self {
           #tokens
         }
       }
+    }
+  };
+
+  let try_decode: TokenStream = {
+    let trait_: Path = parse_quote!(::scarab::export::TryDecode);
+    let generics: Generics = container.generics(Derive::Decode, &trait_);
+    let (impl_generics, ty_generics, where_clause): _ = generics.split_for_impl();
+    let tokens: TokenStream = expand_container(&container, Mode::TryDecode)?;
+
+    quote! {
+      impl #impl_generics #trait_ for #ident #ty_generics #where_clause {
+        fn try_decode(
+          __DECODER: &mut ::scarab::export::Decoder<'_>,
+        ) -> ::core::result::Result<Self, ::scarab::export::DecodeError> {
+          #tokens
+        }
+      }
+    }
+  };
+
+  Ok(quote! {
+    #[allow(non_upper_case_globals)]
+    const #dummy: () = {
+      #decode
+      #try_decode
     };
   })
 }
 
-fn expand_container(container: &Container) -> Result<TokenStream> {
-  match container.data {
-    Data::Struct(Style::Struct, ref fields) => Ok(expand_map(fields)),
-    Data::Struct(Style::Tuple, ref fields) => Ok(expand_seq(fields)),
-    Data::Struct(Style::Unit, _) => Err(Error::new(Span::call_site(), "Can't derive Encode for unit structs")),
-    Data::Enum(_) => Err(Error::new(Span::call_site(), "Can't derive Encode for enums")),
+fn expand_container(container: &Container, mode: Mode) -> Result<TokenStream> {
+  let tokens: TokenStream = match container.data {
+    Data::Struct(Style::Struct, ref fields) => expand_value(expand_map(quote!(Self), fields, mode), mode),
+    Data::Struct(Style::Tuple, ref fields) => expand_value(expand_seq(quote!(Self), fields, mode), mode),
+    Data::Struct(Style::Unit, _) => return Err(Error::new(Span::call_site(), "Can't derive Decode for unit structs")),
+    Data::Enum(ref variants) => expand_enum(container.ident, variants, mode),
+  };
+
+  match container.version {
+    Some(version) => Ok(expand_record(container.ident, tokens, version, mode)),
+    None => Ok(tokens),
+  }
+}
+
+/// Reads the version and contents of a versioned record; `tokens` decode the
+/// value from the contents, which must be consumed entirely.
+fn expand_record(ident: &Ident, tokens: TokenStream, version: i64, mode: Mode) -> TokenStream {
+  let expected: String = ident.to_string();
+
+  match mode {
+    Mode::Decode => quote! {
+      #[allow(unused_variables)]
+      let __VERSION: i64 = __DECODER.version(#expected, #version);
+      let mut __RECORD: ::scarab::export::Decoder<'_> = __DECODER.record(#expected);
+      let __DECODER: &mut ::scarab::export::Decoder<'_> = &mut __RECORD;
+      let __VALUE: Self = { #tokens };
+
+      __DECODER.finish(#expected);
+      __VALUE
+    },
+    Mode::TryDecode => quote! {
+      #[allow(unused_variables)]
+      let __VERSION: i64 = __DECODER.try_version(#expected, #version)?;
+      let mut __RECORD: ::scarab::export::Decoder<'_> = __DECODER.try_record(#expected)?;
+      let __DECODER: &mut ::scarab::export::Decoder<'_> = &mut __RECORD;
+      let __VALUE: Self = { #tokens }?;
+
+      __DECODER.try_finish(#expected)?;
+      ::core::result::Result::Ok(__VALUE)
+    },
+  }
+}
+
+fn expand_enum(ident: &Ident, variants: &[Variant], mode: Mode) -> TokenStream {
+  let arms: _ = variants.iter().map(|variant| {
+    let name: &Ident = variant.ident;
+    let tag: i64 = variant.tag;
+
+    let value: TokenStream = match variant.style {
+      Style::Struct => expand_map(quote!(Self::#name), &variant.fields, mode),
+      Style::Tuple => expand_seq(quote!(Self::#name), &variant.fields, mode),
+      Style::Unit => quote!(Self::#name),
+    };
+
+    let value: TokenStream = expand_value(value, mode);
+
+    quote!(#tag => #value,)
+  });
+
+  match mode {
+    Mode::Decode => {
+      let message: String = format!("invalid tag for `{}`: {{}}", ident);
+
+      quote! {
+        match __DECODER.int64() {
+          #(#arms)*
+          __TAG => ::core::panic!(#message, __TAG),
+        }
+      }
+    }
+    Mode::TryDecode => {
+      let expected: String = ident.to_string();
+
+      quote! {{
+        let __OFFSET: usize = __DECODER.offset();
+
+        match __DECODER.try_int64()? {
+          #(#arms)*
+          __TAG => ::core::result::Result::Err(::scarab::export::DecodeError::new(
+            __OFFSET,
+            #expected,
+            ::scarab::export::DecodeErrorKind::Tag(__TAG),
+          )),
+        }
+      }}
+    }
+  }
+}
+
+/// Wraps a decoded value in `Ok` when generating the fallible decoder.
+fn expand_value(value: TokenStream, mode: Mode) -> TokenStream {
+  match mode {
+    Mode::Decode => value,
+    Mode::TryDecode => quote!(::core::result::Result::Ok(#value)),
   }
 }
 
-fn expand_map(fields: &[Field]) -> TokenStream {
-  let stmts: _ = fields
-    .iter()
-    .map(|field| &field.member)
-    .map(|field| quote!(#field: ::scarab::export::Decode::decode(__DECODER)));
+fn expand_map(path: TokenStream, fields: &[Field], mode: Mode) -> TokenStream {
+  let stmts: _ = fields.iter().map(|field| {
+    let member: &Member = &field.member;
+    let value: TokenStream = expand_field(field, mode);
+    quote!(#member: #value)
+  });
 
   quote! {
-    Self {
+    #path {
       #(#stmts,)*
     }
   }
 }
 
-fn expand_seq(fields: &[Field]) -> TokenStream {
-  let stmts: _ = fields
-    .iter()
-    .map(|_| quote!(::scarab::export::Decode::decode(__DECODER)));
+fn expand_seq(path: TokenStream, fields: &[Field], mode: Mode) -> TokenStream {
+  let stmts: _ = fields.iter().map(|field| expand_field(field, mode));
 
   quote! {
-    Self(
+    #path(
       #(#stmts,)*
     )
   }
 }
+
+fn expand_field(field: &Field, mode: Mode) -> TokenStream {
+  let fallback: TokenStream = field.fallback();
+
+  if field.skip {
+    return fallback;
+  }
+
+  let decode: TokenStream = match (mode, &field.with) {
+    (Mode::Decode, Some(with)) => quote!(#with::decode(__DECODER)),
+    (Mode::Decode, None) => quote!(::scarab::export::Decode::decode(__DECODER)),
+    (Mode::TryDecode, Some(with)) => quote!(#with::try_decode(__DECODER)?),
+    (Mode::TryDecode, None) => quote!(::scarab::export::TryDecode::try_decode(__DECODER)?),
+  };
+
+  match field.since {
+    // Fields added after the version of the record are absent
+    Some(since) => quote! {
+      if __VERSION < #since {
+        #fallback
+      } else {
+        #decode
+      }
+    },
+    None if field.is_optional() => quote! {
+      if __DECODER.is_empty() {
+        #fallback
+      } else {
+        #decode
+      }
+    },
+    None => decode,
+  }
+}
@@ -1,30 +1,39 @@
 use proc_macro2::Span;
 use proc_macro2::TokenStream;
 use quote::quote;
+use quote::ToTokens;
+use syn::parse_quote;
 use syn::DeriveInput;
 use syn::Error;
+use syn::Generics;
 use syn::Ident;
+use syn::Member;
+use syn::Path;
 use syn::Result;
 
 use crate::internal::Container;
 use crate::internal::Data;
+use crate::internal::Derive;
 use crate::internal::Field;
 use crate::internal::Style;
+use crate::internal::Variant;
 
 pub fn derive(input: DeriveInput) -> Result<TokenStream> {
   let container: Container = Container::from_ast(&input)?;
 
-  // TODO: Check Attributes
-  // TODO: Support Generics
+  container.data.check_versions(container.version)?;
 
   let ident: &Ident = container.ident;
+  let trait_: Path = parse_quote!(::scarab::export::Encode);
+  let generics: Generics = container.generics(Derive::Encode, &trait_);
+  let (impl_generics, ty_generics, where_clause): _ = generics.split_for_impl();
   let dummy: Ident = Ident::new(&format!("_IMPL_ENCODE_FOR_{}", ident), Span::call_site());
   let tokens: TokenStream = expand_container(&container)?;
 
   Ok(quote! {
     #[allow(non_upper_case_globals)]
     const #dummy: () = {
-      impl ::scarab::export::Encode for #ident {
+      impl #impl_generics #trait_ for #ident #ty_generics #where_clause {
         fn encode(&self, __ENCODER: &mut ::scarab::export::BytesEncoder) {
           #tokens
         }
@@ -34,21 +43,99 @@ pub fn derive(input: DeriveInput) -> Result<TokenStream> {
 }
 
 fn expand_container(container: &Container) -> Result<TokenStream> {
-  match container.data {
-    Data::Struct(Style::Struct, ref fields) => Ok(expand_fields(fields)),
-    Data::Struct(Style::Tuple, ref fields) => Ok(expand_fields(fields)),
-    Data::Struct(Style::Unit, _) => Err(Error::new(Span::call_site(), "Can't derive Encode for unit structs")),
-    Data::Enum(_) => Err(Error::new(Span::call_site(), "Can't derive Encode for enums")),
+  let tokens: TokenStream = match container.data {
+    Data::Struct(Style::Struct, ref fields) => expand_fields(fields),
+    Data::Struct(Style::Tuple, ref fields) => expand_fields(fields),
+    Data::Struct(Style::Unit, _) => return Err(Error::new(Span::call_site(), "Can't derive Encode for unit structs")),
+    Data::Enum(ref variants) => expand_enum(variants),
+  };
+
+  match container.version {
+    Some(version) => Ok(expand_record(tokens, version)),
+    None => Ok(tokens),
+  }
+}
+
+/// Writes the version of a versioned record followed by its length-prefixed
+/// contents.
+fn expand_record(tokens: TokenStream, version: i64) -> TokenStream {
+  quote! {
+    let mut __RECORD: ::scarab::export::BytesEncoder = ::scarab::export::BytesEncoder::new();
+
+    {
+      let __ENCODER: &mut ::scarab::export::BytesEncoder = &mut __RECORD;
+      #tokens
+    }
+
+    __ENCODER.int64(#version);
+    __ENCODER.bytes(&__RECORD.data());
   }
 }
 
 fn expand_fields(fields: &[Field]) -> TokenStream {
-  let stmts: _ = fields
-    .iter()
-    .map(|field| &field.member)
-    .map(|field| quote!(::scarab::export::Encode::encode(&self.#field, __ENCODER)));
+  let stmts: _ = fields.iter().filter(|field| !field.skip).map(|field| {
+    let member: &Member = &field.member;
+    expand_field(field, quote!(&self.#member))
+  });
 
   quote! {
     #(#stmts;)*
   }
 }
+
+fn expand_field(field: &Field, value: TokenStream) -> TokenStream {
+  match field.with {
+    Some(ref with) => quote!(#with::encode(#value, __ENCODER)),
+    None => quote!(::scarab::export::Encode::encode(#value, __ENCODER)),
+  }
+}
+
+fn expand_enum(variants: &[Variant]) -> TokenStream {
+  if variants.is_empty() {
+    return quote!(match *self {});
+  }
+
+  let arms: _ = variants.iter().map(expand_variant);
+
+  quote! {
+    match self {
+      #(#arms)*
+    }
+  }
+}
+
+fn expand_variant(variant: &Variant) -> TokenStream {
+  let ident: &Ident = variant.ident;
+  let tag: i64 = variant.tag;
+
+  // Skipped fields are matched with a wildcard to avoid unused bindings
+  let names: Vec<TokenStream> = variant
+    .fields
+    .iter()
+    .map(|field| match field.skip {
+      true => quote!(_),
+      false => field.binding().into_token_stream(),
+    })
+    .collect();
+
+  let stmts: _ = variant.fields.iter().filter(|field| !field.skip).map(|field| {
+    let binding: Ident = field.binding();
+    expand_field(field, quote!(#binding))
+  });
+
+  let pattern: TokenStream = match variant.style {
+    Style::Struct => {
+      let members: _ = variant.fields.iter().map(|field| &field.member);
+      quote!(Self::#ident { #(#members: #names),* })
+    }
+    Style::Tuple => quote!(Self::#ident(#(#names),*)),
+    Style::Unit => quote!(Self::#ident),
+  };
+
+  quote! {
+    #pattern => {
+      __ENCODER.int64(#tag);
+      #(#stmts;)*
+    }
+  }
+}
@@ -0,0 +1,77 @@
+use syn::punctuated::Punctuated;
+use syn::Attribute;
+use syn::Error;
+use syn::Lit;
+use syn::LitStr;
+use syn::Meta;
+use syn::NestedMeta;
+use syn::Result;
+use syn::Token;
+use syn::WherePredicate;
+
+const SCARAB: &str = "scarab";
+
+/// Returns every nested meta item found in `#[scarab(...)]` attributes.
+pub fn parse(attrs: &[Attribute]) -> Result<Vec<Meta>> {
+  let mut output: Vec<Meta> = Vec::new();
+
+  for attr in attrs.iter().filter(|attr| attr.path.is_ident(SCARAB)) {
+    match attr.parse_meta()? {
+      Meta::List(list) => {
+        for nested in list.nested {
+          match nested {
+            NestedMeta::Meta(meta) => output.push(meta),
+            NestedMeta::Lit(lit) => return Err(Error::new_spanned(lit, "unexpected literal in scarab attribute")),
+          }
+        }
+      }
+      meta => return Err(Error::new_spanned(meta, "expected #[scarab(...)]")),
+    }
+  }
+
+  Ok(output)
+}
+
+/// Removes all `#[scarab(...)]` attributes.
+pub fn strip(attrs: &mut Vec<Attribute>) {
+  attrs.retain(|attr| !attr.path.is_ident(SCARAB));
+}
+
+/// Returns the name of the given meta item.
+pub fn name(meta: &Meta) -> String {
+  meta.path().get_ident().map(ToString::to_string).unwrap_or_default()
+}
+
+/// Parses the value of a `name = 123` meta item as an integer.
+pub fn int(meta: &Meta) -> Result<i64> {
+  match meta {
+    Meta::NameValue(value) => match value.lit {
+      Lit::Int(ref lit) => lit.base10_parse(),
+      ref lit => Err(Error::new_spanned(lit, "expected integer literal")),
+    },
+    _ => Err(Error::new_spanned(meta, format!("expected `{} = ...`", name(meta)))),
+  }
+}
+
+/// Parses the value of a `name = "..."` meta item as a string literal.
+pub fn string(meta: &Meta) -> Result<&LitStr> {
+  match meta {
+    Meta::NameValue(value) => match value.lit {
+      Lit::Str(ref lit) => Ok(lit),
+      ref lit => Err(Error::new_spanned(lit, "expected string literal")),
+    },
+    _ => Err(Error::new_spanned(meta, format!("expected `{} = \"...\"`", name(meta)))),
+  }
+}
+
+/// Parses a string literal as a comma-separated list of where predicates.
+pub fn predicates(lit: &LitStr) -> Result<Vec<WherePredicate>> {
+  lit
+    .parse_with(Punctuated::<WherePredicate, Token![,]>::parse_terminated)
+    .map(|predicates| predicates.into_iter().collect())
+}
+
+/// Returns an error for an attribute that is not supported in this position.
+pub fn unknown(meta: &Meta) -> Error {
+  Error::new_spanned(meta, format!("unknown scarab attribute `{}`", name(meta)))
+}
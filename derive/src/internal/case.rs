@@ -0,0 +1,47 @@
+/// Converts a `snake_case` identifier to `camelCase`.
+///
+/// Identifiers that are already `camelCase` are returned unchanged.
+pub fn camel(ident: &str) -> String {
+  let mut output: String = String::with_capacity(ident.len());
+  let mut upper: bool = false;
+
+  for char in ident.trim_start_matches("r#").trim_matches('_').chars() {
+    if char == '_' {
+      upper = !output.is_empty();
+    } else if upper {
+      output.extend(char.to_uppercase());
+      upper = false;
+    } else {
+      output.push(char);
+    }
+  }
+
+  output
+}
+
+/// Converts a `camelCase` identifier to `snake_case`.
+///
+/// Identifiers that are already `snake_case` are returned unchanged.
+pub fn snake(ident: &str) -> String {
+  let chars: Vec<char> = ident.trim_start_matches("r#").chars().collect();
+  let mut output: String = String::with_capacity(chars.len() + 4);
+
+  for (index, char) in chars.iter().enumerate() {
+    if char.is_uppercase() && index > 0 {
+      let prev: char = chars[index - 1];
+      let next: Option<&char> = chars.get(index + 1);
+
+      // Break before `Of` in `balanceOf` and `Id` in `URIId`
+      if prev.is_lowercase()
+        || prev.is_numeric()
+        || (prev.is_uppercase() && matches!(next, Some(c) if c.is_lowercase()))
+      {
+        output.push('_');
+      }
+    }
+
+    output.extend(char.to_lowercase());
+  }
+
+  output
+}
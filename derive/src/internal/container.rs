@@ -1,25 +1,190 @@
+use syn::parse_quote;
 use syn::DeriveInput;
+use syn::Error;
+use syn::GenericArgument;
+use syn::GenericParam;
 use syn::Generics;
 use syn::Ident;
+use syn::Meta;
+use syn::NestedMeta;
+use syn::Path;
+use syn::PathArguments;
 use syn::Result;
+use syn::ReturnType;
+use syn::Type;
+use syn::WherePredicate;
 
+use crate::internal::attr;
 use crate::internal::Data;
+use crate::internal::Field;
+
+/// The trait being derived; selects which custom bounds apply.
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "extra-traits", derive(Debug))]
+pub enum Derive {
+  Encode,
+  Decode,
+}
 
 #[cfg_attr(feature = "extra-traits", derive(Debug))]
 pub struct Container<'a> {
   pub ident: &'a Ident,
   pub data: Data<'a>,
   pub generics: &'a Generics,
+  pub encode_bound: Option<Vec<WherePredicate>>,
+  pub decode_bound: Option<Vec<WherePredicate>>,
+  /// `#[scarab(validate = "function")]` - checks the value of the container.
+  pub validate: Option<Path>,
+  /// `#[scarab(version = N)]` - the current version of a versioned record.
+  pub version: Option<i64>,
 }
 
 impl<'a> Container<'a> {
   pub fn from_ast(input: &'a DeriveInput) -> Result<Self> {
-    // TODO: Handle Container Attributes
+    let mut encode_bound: Option<Vec<WherePredicate>> = None;
+    let mut decode_bound: Option<Vec<WherePredicate>> = None;
+    let mut validate: Option<Path> = None;
+    let mut version: Option<i64> = None;
+
+    for meta in attr::parse(&input.attrs)? {
+      match (attr::name(&meta).as_str(), &meta) {
+        // #[scarab(bound = "T: Trait")]
+        ("bound", Meta::NameValue(_)) => {
+          let predicates: Vec<WherePredicate> = attr::predicates(attr::string(&meta)?)?;
+          encode_bound = Some(predicates.clone());
+          decode_bound = Some(predicates);
+        }
+        // #[scarab(bound(encode = "T: Trait", decode = "T: Trait"))]
+        ("bound", Meta::List(list)) => {
+          for nested in list.nested.iter() {
+            let meta: &Meta = match nested {
+              NestedMeta::Meta(meta) => meta,
+              NestedMeta::Lit(lit) => return Err(Error::new_spanned(lit, "expected `encode` or `decode`")),
+            };
+
+            match attr::name(meta).as_str() {
+              "encode" => encode_bound = Some(attr::predicates(attr::string(meta)?)?),
+              "decode" => decode_bound = Some(attr::predicates(attr::string(meta)?)?),
+              _ => return Err(attr::unknown(meta)),
+            }
+          }
+        }
+        // #[scarab(validate = "function")]
+        ("validate", Meta::NameValue(_)) => validate = Some(attr::string(&meta)?.parse()?),
+        // #[scarab(version = N)]
+        ("version", Meta::NameValue(_)) => match attr::int(&meta)? {
+          value if value >= 0 => version = Some(value),
+          _ => return Err(Error::new_spanned(meta, "`version` must not be negative")),
+        },
+        _ => return Err(attr::unknown(&meta)),
+      }
+    }
 
     Ok(Self {
       data: Data::from_ast(&input.data)?,
       ident: &input.ident,
       generics: &input.generics,
+      encode_bound,
+      decode_bound,
+      validate,
+      version,
     })
   }
+
+  /// Returns the container generics extended with the bounds required to
+  /// derive `trait_`.
+  ///
+  /// Type parameters used by encoded fields are bounded by `trait_`, and when
+  /// decoding, those used by fields falling back to `Default::default()` are
+  /// bounded by `Default`. Custom bounds specified with
+  /// `#[scarab(bound = "...")]` replace the inferred bounds.
+  pub fn generics(&self, derive: Derive, trait_: &Path) -> Generics {
+    let mut generics: Generics = self.generics.clone();
+
+    let custom: &Option<Vec<WherePredicate>> = match derive {
+      Derive::Encode => &self.encode_bound,
+      Derive::Decode => &self.decode_bound,
+    };
+
+    let predicates: Vec<WherePredicate> = match custom {
+      Some(predicates) => predicates.clone(),
+      None => self.infer_bounds(derive, trait_),
+    };
+
+    generics.make_where_clause().predicates.extend(predicates);
+    generics
+  }
+
+  fn infer_bounds(&self, derive: Derive, trait_: &Path) -> Vec<WherePredicate> {
+    let fields: Vec<&Field<'_>> = self.data.fields();
+    let mut predicates: Vec<WherePredicate> = Vec::new();
+
+    let params: _ = self.generics.params.iter().filter_map(|param| match param {
+      GenericParam::Type(param) => Some(&param.ident),
+      _ => None,
+    });
+
+    for ident in params {
+      if fields.iter().any(|field| !field.skip && references(field.ty, ident)) {
+        predicates.push(parse_quote!(#ident: #trait_));
+      }
+
+      if derive == Derive::Decode
+        && fields
+          .iter()
+          .any(|field| field.is_trait_default() && references(field.ty, ident))
+      {
+        predicates.push(parse_quote!(#ident: ::core::default::Default));
+      }
+    }
+
+    predicates
+  }
+}
+
+/// Returns `true` if `ty` refers to the type parameter `param`.
+///
+/// `PhantomData<T>` does not require any bounds on `T` and is ignored; types
+/// that can't be inspected are assumed to refer to `param`.
+fn references(ty: &Type, param: &Ident) -> bool {
+  match ty {
+    Type::Array(ty) => references(&ty.elem, param),
+    Type::Group(ty) => references(&ty.elem, param),
+    Type::Paren(ty) => references(&ty.elem, param),
+    Type::Ptr(ty) => references(&ty.elem, param),
+    Type::Reference(ty) => references(&ty.elem, param),
+    Type::Slice(ty) => references(&ty.elem, param),
+    Type::Tuple(ty) => ty.elems.iter().any(|elem| references(elem, param)),
+    Type::Never(_) => false,
+    Type::Path(ty) => {
+      if let Some(ref qself) = ty.qself {
+        if references(&qself.ty, param) {
+          return true;
+        }
+      }
+
+      if ty.path.leading_colon.is_none() && matches!(ty.path.segments.first(), Some(segment) if segment.ident == *param)
+      {
+        return true;
+      }
+
+      ty.path
+        .segments
+        .iter()
+        .filter(|segment| segment.ident != "PhantomData")
+        .any(|segment| match segment.arguments {
+          PathArguments::None => false,
+          PathArguments::AngleBracketed(ref arguments) => arguments.args.iter().any(|argument| match argument {
+            GenericArgument::Type(ty) => references(ty, param),
+            GenericArgument::Binding(binding) => references(&binding.ty, param),
+            _ => false,
+          }),
+          PathArguments::Parenthesized(ref arguments) => {
+            arguments.inputs.iter().any(|input| references(input, param))
+              || matches!(arguments.output, ReturnType::Type(_, ref output) if references(output, param))
+          }
+        })
+    }
+    _ => true,
+  }
 }
@@ -23,31 +23,110 @@ impl<'a> Data<'a> {
   pub fn from_ast(data: &'a syn::Data) -> Result<Self> {
     match data {
       syn::Data::Struct(DataStruct { ref fields, .. }) => {
-        let (style, fields): (Style, Vec<Field<'a>>) = Self::expand_struct(fields);
+        let (style, fields): (Style, Vec<Field<'a>>) = Self::expand_struct(fields)?;
         Ok(Self::Struct(style, fields))
       }
-      syn::Data::Enum(DataEnum { ref variants, .. }) => Ok(Self::Enum(Self::expand_enum(variants))),
+      syn::Data::Enum(DataEnum { ref variants, .. }) => Self::expand_enum(variants).map(Self::Enum),
       syn::Data::Union(_) => Err(Error::new(Span::call_site(), "Cannot derive Encode for unions")),
     }
   }
 
-  pub fn expand_struct(fields: &Fields) -> (Style, Vec<Field<'_>>) {
+  pub fn expand_struct(fields: &Fields) -> Result<(Style, Vec<Field<'_>>)> {
     match fields {
-      Fields::Named(FieldsNamed { ref named, .. }) => (Style::Struct, Self::expand_fields(named)),
-      Fields::Unnamed(FieldsUnnamed { ref unnamed, .. }) => (Style::Tuple, Self::expand_fields(unnamed)),
-      Fields::Unit => (Style::Unit, Vec::new()),
+      Fields::Named(FieldsNamed { ref named, .. }) => Ok((Style::Struct, Self::expand_fields(named)?)),
+      Fields::Unnamed(FieldsUnnamed { ref unnamed, .. }) => Ok((Style::Tuple, Self::expand_fields(unnamed)?)),
+      Fields::Unit => Ok((Style::Unit, Vec::new())),
     }
   }
 
-  pub fn expand_enum(variants: &Punctuated<syn::Variant, Token![,]>) -> Vec<Variant<'_>> {
-    variants.iter().map(|variant| Variant::from_ast(variant)).collect()
+  pub fn expand_enum(variants: &Punctuated<syn::Variant, Token![,]>) -> Result<Vec<Variant<'_>>> {
+    let mut output: Vec<Variant<'_>> = Vec::with_capacity(variants.len());
+    let mut next: Option<i64> = Some(0);
+
+    for variant in variants {
+      let this: Variant<'_> = Variant::from_ast(variant, next)?;
+
+      if let Some(other) = output.iter().find(|other| other.tag == this.tag) {
+        return Err(Error::new_spanned(
+          variant,
+          format!("duplicate tag `{}` (already used by `{}`)", this.tag, other.ident),
+        ));
+      }
+
+      next = this.tag.checked_add(1);
+      output.push(this);
+    }
+
+    Ok(output)
   }
 
-  pub fn expand_fields(fields: &Punctuated<syn::Field, Token![,]>) -> Vec<Field<'_>> {
+  pub fn expand_fields(fields: &Punctuated<syn::Field, Token![,]>) -> Result<Vec<Field<'_>>> {
     fields
       .iter()
       .enumerate()
       .map(|(index, field)| Field::from_ast(field, index))
       .collect()
   }
+
+  /// Returns the fields of the struct or of every variant of the enum.
+  pub fn fields(&self) -> Vec<&Field<'a>> {
+    match self {
+      Self::Struct(_, fields) => fields.iter().collect(),
+      Self::Enum(variants) => variants.iter().flat_map(|variant| variant.fields.iter()).collect(),
+    }
+  }
+
+  /// Checks that the optional fields of every record can be detected when
+  /// decoding records of the given container `version`.
+  pub fn check_versions(&self, version: Option<i64>) -> Result<()> {
+    match self {
+      Self::Struct(_, fields) => Self::check_fields(fields, version),
+      Self::Enum(variants) => variants
+        .iter()
+        .try_for_each(|variant| Self::check_fields(&variant.fields, version)),
+    }
+  }
+
+  fn check_fields(fields: &[Field<'_>], version: Option<i64>) -> Result<()> {
+    let mut optional: bool = false;
+    let mut since: i64 = i64::MIN;
+
+    // Absent fields can only be detected at the end of a record so optional
+    // fields must be trailing and ordered by the version they were added in.
+    // Unversioned containers detect them at the end of the input instead.
+    for field in fields.iter().filter(|field| !field.skip) {
+      if field.since.is_some() && version.is_none() {
+        return Err(Error::new_spanned(
+          field.original,
+          "`since` fields require `#[scarab(version = N)]` on the container",
+        ));
+      }
+
+      if optional && !field.is_optional() {
+        return Err(Error::new_spanned(
+          field.original,
+          "fields following a `default` field must also be `default`",
+        ));
+      }
+
+      if let Some(value) = field.since {
+        if value < since {
+          return Err(Error::new_spanned(field.original, "`since` versions must not decrease"));
+        }
+
+        if matches!(version, Some(version) if value > version) {
+          return Err(Error::new_spanned(
+            field.original,
+            "`since` must not exceed the version of the container",
+          ));
+        }
+
+        since = value;
+      }
+
+      optional |= field.is_optional();
+    }
+
+    Ok(())
+  }
 }
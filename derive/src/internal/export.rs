@@ -0,0 +1,207 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Attribute;
+use syn::Error;
+use syn::FnArg;
+use syn::Ident;
+use syn::Meta;
+use syn::NestedMeta;
+use syn::Pat;
+use syn::Result;
+use syn::ReturnType;
+use syn::Signature;
+use syn::Type;
+
+use crate::internal::attr;
+use crate::internal::case;
+
+const FUNC: &str = "func";
+const VIEW: &str = "view";
+
+/// The kind of entry point a method is exported as.
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "extra-traits", derive(Debug))]
+pub enum Kind {
+  Func,
+  View,
+}
+
+/// A method exported with `#[func]` or `#[view]`.
+#[cfg_attr(feature = "extra-traits", derive(Debug))]
+pub struct Export {
+  pub kind: Kind,
+  pub ident: Ident,
+  /// `#[func(name = "...")]` - the name the method is exported as.
+  pub name: String,
+  /// `#[func(result = "...")]` - the result key of the return value.
+  pub result: String,
+  pub params: Vec<Param>,
+  pub output: Option<Type>,
+}
+
+/// A typed parameter of an exported method.
+#[cfg_attr(feature = "extra-traits", derive(Debug))]
+pub struct Param {
+  pub ident: Ident,
+  /// `#[scarab(key = "...")]` - the key the parameter is read from.
+  pub key: String,
+  /// The parameter type, without a leading reference.
+  pub ty: Type,
+  /// Whether the method takes the parameter by reference.
+  pub by_ref: bool,
+}
+
+impl Export {
+  /// Parses an exported method, removing the attributes handled by the
+  /// contract and client macros.
+  ///
+  /// Returns `None` if the method is not exported.
+  pub fn from_ast(attrs: &mut Vec<Attribute>, sig: &mut Signature) -> Result<Option<Self>> {
+    let (kind, attr): (Kind, Attribute) = match Self::take(attrs)? {
+      Some(export) => export,
+      None => return Ok(None),
+    };
+
+    let ident: Ident = sig.ident.clone();
+    let mut name: String = case::camel(&ident.to_string());
+    let mut result: Option<String> = None;
+
+    if let Meta::List(list) = attr.parse_meta()? {
+      for nested in list.nested.iter() {
+        let meta: &Meta = match nested {
+          NestedMeta::Meta(meta) => meta,
+          NestedMeta::Lit(lit) => return Err(Error::new_spanned(lit, "expected `name` or `result`")),
+        };
+
+        match attr::name(meta).as_str() {
+          "name" => name = attr::string(meta)?.value(),
+          "result" => result = Some(attr::string(meta)?.value()),
+          _ => {
+            return Err(Error::new_spanned(
+              meta,
+              format!("unknown export attribute `{}`", attr::name(meta)),
+            ))
+          }
+        }
+      }
+    }
+
+    let mut inputs: _ = sig.inputs.iter_mut();
+
+    match inputs.next() {
+      Some(FnArg::Typed(_)) => {}
+      Some(FnArg::Receiver(receiver)) => {
+        return Err(Error::new_spanned(receiver, "exported methods can't take `self`"));
+      }
+      None => {
+        return Err(Error::new_spanned(
+          &sig.ident,
+          "exported methods must take a context argument",
+        ));
+      }
+    }
+
+    let params: Vec<Param> = inputs
+      .map(|input| match input {
+        FnArg::Typed(input) => Param::from_ast(input),
+        FnArg::Receiver(receiver) => Err(Error::new_spanned(receiver, "unexpected `self`")),
+      })
+      .collect::<Result<_>>()?;
+
+    let output: Option<Type> = match sig.output {
+      ReturnType::Default => None,
+      ReturnType::Type(_, ref ty) => Some((**ty).clone()),
+    };
+
+    Ok(Some(Self {
+      kind,
+      ident,
+      result: result.unwrap_or_else(|| name.clone()),
+      name,
+      params,
+      output,
+    }))
+  }
+
+  /// Returns a statement adding the method to a `schema` in scope.
+  pub fn description(&self) -> TokenStream {
+    let name: &str = &self.name;
+
+    let params: _ = self.params.iter().map(|param| {
+      let key: &str = &param.key;
+      let ty: &Type = &param.ty;
+
+      quote!(.param_field(<#ty as ::scarab::export::ParamValue>::schema(#key)))
+    });
+
+    let result: TokenStream = match self.output {
+      Some(ref ty) => {
+        let key: &str = &self.result;
+        quote!(.result_field(<#ty as ::scarab::export::ResultValue>::schema(#key)))
+      }
+      None => quote!(),
+    };
+
+    let schema: TokenStream = quote!(::scarab::export::FuncSchema::new(#name) #(#params)* #result);
+
+    match self.kind {
+      Kind::Func => quote!(schema.add_func(#schema)),
+      Kind::View => quote!(schema.add_view(#schema)),
+    }
+  }
+
+  /// Removes and returns the `#[func]` or `#[view]` attribute.
+  fn take(attrs: &mut Vec<Attribute>) -> Result<Option<(Kind, Attribute)>> {
+    let mut output: Option<(Kind, Attribute)> = None;
+    let mut index: usize = 0;
+
+    while index < attrs.len() {
+      let kind: Kind = if attrs[index].path.is_ident(FUNC) {
+        Kind::Func
+      } else if attrs[index].path.is_ident(VIEW) {
+        Kind::View
+      } else {
+        index += 1;
+        continue;
+      };
+
+      let attr: Attribute = attrs.remove(index);
+
+      if output.is_some() {
+        return Err(Error::new_spanned(attr, "duplicate export attribute"));
+      }
+
+      output = Some((kind, attr));
+    }
+
+    Ok(output)
+  }
+}
+
+impl Param {
+  fn from_ast(input: &mut syn::PatType) -> Result<Self> {
+    let ident: Ident = match *input.pat {
+      Pat::Ident(ref pat) => pat.ident.clone(),
+      ref pat => return Err(Error::new_spanned(pat, "exported parameters must be identifiers")),
+    };
+
+    let mut key: String = case::camel(&ident.to_string());
+
+    for meta in attr::parse(&input.attrs)? {
+      match attr::name(&meta).as_str() {
+        "key" => key = attr::string(&meta)?.value(),
+        _ => return Err(attr::unknown(&meta)),
+      }
+    }
+
+    // Parameter attributes are not allowed once the macro has expanded
+    attr::strip(&mut input.attrs);
+
+    let (ty, by_ref): (Type, bool) = match *input.ty {
+      Type::Reference(ref ty) if ty.mutability.is_none() => ((*ty.elem).clone(), true),
+      ref ty => (ty.clone(), false),
+    };
+
+    Ok(Self { ident, key, ty, by_ref })
+  }
+}
@@ -1,17 +1,51 @@
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use syn::Error;
+use syn::GenericArgument;
 use syn::Ident;
 use syn::Member;
+use syn::Meta;
+use syn::Path;
+use syn::PathArguments;
+use syn::Result;
+use syn::Type;
+
+use crate::internal::attr;
+use crate::internal::case;
+
+/// The value used when a field is skipped or absent from the input.
+#[cfg_attr(feature = "extra-traits", derive(Debug))]
+pub enum Fallback {
+  /// Use `Default::default()`.
+  Trait,
+  /// Call the given function.
+  Path(Path),
+}
 
 #[cfg_attr(feature = "extra-traits", derive(Debug))]
 pub struct Field<'a> {
+  pub original: &'a syn::Field,
   pub index: usize,
   pub ident: Option<&'a Ident>,
   pub member: Member,
+  pub ty: &'a Type,
+  /// `#[scarab(skip)]` - the field is never encoded.
+  pub skip: bool,
+  /// `#[scarab(default)]` - the field may be absent at the end of the input.
+  pub default: Option<Fallback>,
+  /// `#[scarab(with = "module")]` - the field is encoded by `module`.
+  pub with: Option<Path>,
+  /// `#[scarab(since = N)]` - the version the field was introduced in.
+  pub since: Option<i64>,
+  /// `#[scarab(key = "...")]` - the map key the field is stored under.
+  pub key: Option<String>,
+  /// `#[scarab(validate = "function")]` - checks the value of the field.
+  pub validate: Option<Path>,
 }
 
 impl<'a> Field<'a> {
-  pub fn from_ast(field: &'a syn::Field, index: usize) -> Self {
-    // TODO: Handle Field Attributes
-
+  pub fn from_ast(field: &'a syn::Field, index: usize) -> Result<Self> {
     let ident: Option<&Ident> = field.ident.as_ref();
 
     let member: Member = match ident {
@@ -19,6 +53,105 @@ impl<'a> Field<'a> {
       None => Member::Unnamed(index.into()),
     };
 
-    Self { index, ident, member }
+    let mut this: Self = Self {
+      original: field,
+      index,
+      ident,
+      member,
+      ty: &field.ty,
+      skip: false,
+      default: None,
+      with: None,
+      since: None,
+      key: None,
+      validate: None,
+    };
+
+    for meta in attr::parse(&field.attrs)? {
+      match (attr::name(&meta).as_str(), &meta) {
+        ("skip", Meta::Path(_)) => this.skip = true,
+        ("default", Meta::Path(_)) => this.default = Some(Fallback::Trait),
+        ("default", Meta::NameValue(_)) => this.default = Some(Fallback::Path(attr::string(&meta)?.parse()?)),
+        ("with", Meta::NameValue(_)) => this.with = Some(attr::string(&meta)?.parse()?),
+        ("since", Meta::NameValue(_)) => this.since = Some(attr::int(&meta)?),
+        ("key", Meta::NameValue(_)) => this.key = Some(attr::string(&meta)?.value()),
+        ("validate", Meta::NameValue(_)) => this.validate = Some(attr::string(&meta)?.parse()?),
+        _ => return Err(attr::unknown(&meta)),
+      }
+    }
+
+    if this.skip && this.with.is_some() {
+      return Err(Error::new_spanned(field, "`skip` cannot be combined with `with`"));
+    }
+
+    // Fields introduced in a later version are absent from older records.
+    if this.since.is_some() && this.default.is_none() {
+      this.default = Some(Fallback::Trait);
+    }
+
+    Ok(this)
+  }
+
+  /// Returns an identifier used to bind this field in patterns.
+  pub fn binding(&self) -> Ident {
+    format_ident!("__field{}", self.index)
+  }
+
+  /// Returns `true` if the field may be missing from the encoded input.
+  pub fn is_optional(&self) -> bool {
+    self.default.is_some()
+  }
+
+  /// Returns `true` if a skipped or absent field is set to
+  /// `Default::default()`.
+  pub fn is_trait_default(&self) -> bool {
+    match self.default {
+      Some(Fallback::Trait) => true,
+      Some(Fallback::Path(_)) => false,
+      None => self.skip,
+    }
+  }
+
+  /// Returns an expression producing the value of a skipped or absent field.
+  pub fn fallback(&self) -> TokenStream {
+    match self.default {
+      Some(Fallback::Path(ref path)) => quote!(#path()),
+      Some(Fallback::Trait) | None => quote!(::core::default::Default::default()),
+    }
+  }
+
+  /// Returns the map key of the field; named fields default to the camelCase
+  /// field name.
+  pub fn key(&self) -> Result<String> {
+    match (&self.key, self.ident) {
+      (Some(key), _) => Ok(key.clone()),
+      (None, Some(ident)) => Ok(case::camel(&ident.to_string())),
+      (None, None) => Err(Error::new_spanned(
+        self.original,
+        "tuple fields require `#[scarab(key = \"...\")]`",
+      )),
+    }
+  }
+
+  /// Returns the inner type if the field is an `Option<T>`.
+  pub fn option(&self) -> Option<&'a Type> {
+    let path: &Path = match self.ty {
+      Type::Path(ty) if ty.qself.is_none() => &ty.path,
+      _ => return None,
+    };
+
+    let segment: &syn::PathSegment = path.segments.last()?;
+
+    if segment.ident != "Option" {
+      return None;
+    }
+
+    match segment.arguments {
+      PathArguments::AngleBracketed(ref arguments) if arguments.args.len() == 1 => match arguments.args[0] {
+        GenericArgument::Type(ref ty) => Some(ty),
+        _ => None,
+      },
+      _ => None,
+    }
   }
 }
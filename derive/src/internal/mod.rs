@@ -1,11 +1,18 @@
+pub mod attr;
+pub mod case;
 mod container;
 mod data;
+mod export;
 mod field;
 mod style;
 mod variant;
 
 pub use self::container::Container;
+pub use self::container::Derive;
 pub use self::data::Data;
+pub use self::export::Export;
+pub use self::export::Kind;
+pub use self::export::Param;
 pub use self::field::Field;
 pub use self::style::Style;
 pub use self::variant::Variant;
@@ -1,5 +1,14 @@
+use core::convert::TryInto;
+use syn::Error;
+use syn::Expr;
+use syn::ExprLit;
+use syn::ExprUnary;
 use syn::Ident;
+use syn::Lit;
+use syn::Result;
+use syn::UnOp;
 
+use crate::internal::attr;
 use crate::internal::Data;
 use crate::internal::Field;
 use crate::internal::Style;
@@ -9,15 +18,55 @@ pub struct Variant<'a> {
   pub ident: &'a Ident,
   pub style: Style,
   pub fields: Vec<Field<'a>>,
+  pub tag: i64,
 }
 
 impl<'a> Variant<'a> {
-  pub fn from_ast(variant: &'a syn::Variant) -> Self {
-    // TODO: Handle Variant Attributes
-
+  /// Parses a variant; `next` is the tag assigned when none is specified, or
+  /// `None` if the tag of the previous variant was `i64::MAX`.
+  pub fn from_ast(variant: &'a syn::Variant, next: Option<i64>) -> Result<Self> {
     let ident: &Ident = &variant.ident;
-    let (style, fields): _ = Data::expand_struct(&variant.fields);
+    let (style, fields): _ = Data::expand_struct(&variant.fields)?;
+
+    let mut tag: Option<i64> = match variant.discriminant {
+      Some((_, ref expr)) => Some(Self::discriminant(expr)?),
+      None => next,
+    };
+
+    for meta in attr::parse(&variant.attrs)? {
+      match attr::name(&meta).as_str() {
+        "tag" => tag = Some(attr::int(&meta)?),
+        _ => return Err(attr::unknown(&meta)),
+      }
+    }
+
+    let tag: i64 = tag.ok_or_else(|| Error::new_spanned(ident, "enum discriminant overflowed"))?;
+
+    Ok(Self {
+      ident,
+      style,
+      fields,
+      tag,
+    })
+  }
+
+  fn discriminant(expr: &Expr) -> Result<i64> {
+    // Literals are parsed as `i128` so `-9223372036854775808` doesn't overflow
+    Self::literal(expr)?
+      .try_into()
+      .map_err(|_| Error::new_spanned(expr, "discriminant out of range for `i64`"))
+  }
 
-    Self { ident, style, fields }
+  fn literal(expr: &Expr) -> Result<i128> {
+    match expr {
+      Expr::Lit(ExprLit { lit: Lit::Int(lit), .. }) => lit.base10_parse(),
+      Expr::Unary(ExprUnary {
+        op: UnOp::Neg(_), expr, ..
+      }) => Self::literal(expr).map(|value| -value),
+      _ => Err(Error::new_spanned(
+        expr,
+        "unsupported discriminant: use an integer literal or #[scarab(tag = N)]",
+      )),
+    }
   }
 }
@@ -1,12 +1,20 @@
 extern crate proc_macro;
 
+mod client;
+mod contract;
 mod decode;
 mod encode;
 mod internal;
+mod params;
+mod results;
+mod state;
 
 use proc_macro::TokenStream;
 use syn::parse_macro_input;
+use syn::AttributeArgs;
 use syn::DeriveInput;
+use syn::ItemImpl;
+use syn::ItemTrait;
 
 #[proc_macro_derive(Encode, attributes(scarab))]
 pub fn derive_encode(input: TokenStream) -> TokenStream {
@@ -21,3 +29,44 @@ pub fn derive_decode(input: TokenStream) -> TokenStream {
     .unwrap_or_else(|error| error.to_compile_error())
     .into()
 }
+
+#[proc_macro_derive(Params, attributes(scarab))]
+pub fn derive_params(input: TokenStream) -> TokenStream {
+  params::derive(parse_macro_input!(input as DeriveInput))
+    .unwrap_or_else(|error| error.to_compile_error())
+    .into()
+}
+
+#[proc_macro_derive(Results, attributes(scarab))]
+pub fn derive_results(input: TokenStream) -> TokenStream {
+  results::derive(parse_macro_input!(input as DeriveInput))
+    .unwrap_or_else(|error| error.to_compile_error())
+    .into()
+}
+
+#[proc_macro_derive(State, attributes(scarab))]
+pub fn derive_state(input: TokenStream) -> TokenStream {
+  state::derive(parse_macro_input!(input as DeriveInput))
+    .unwrap_or_else(|error| error.to_compile_error())
+    .into()
+}
+
+#[proc_macro_attribute]
+pub fn contract(args: TokenStream, input: TokenStream) -> TokenStream {
+  contract::expand(
+    parse_macro_input!(args as AttributeArgs),
+    parse_macro_input!(input as ItemImpl),
+  )
+  .unwrap_or_else(|error| error.to_compile_error())
+  .into()
+}
+
+#[proc_macro_attribute]
+pub fn client(args: TokenStream, input: TokenStream) -> TokenStream {
+  client::expand(
+    parse_macro_input!(args as AttributeArgs),
+    parse_macro_input!(input as ItemTrait),
+  )
+  .unwrap_or_else(|error| error.to_compile_error())
+  .into()
+}
@@ -0,0 +1,182 @@
+use proc_macro2::Span;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::DeriveInput;
+use syn::Error;
+use syn::Ident;
+use syn::Path;
+use syn::Result;
+use syn::Type;
+
+use crate::internal::Container;
+use crate::internal::Data;
+use crate::internal::Field;
+use crate::internal::Style;
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+  let container: Container = Container::from_ast(&input)?;
+
+  let ident: &Ident = container.ident;
+  let (impl_generics, ty_generics, where_clause): _ = container.generics.split_for_impl();
+  let dummy: Ident = Ident::new(&format!("_IMPL_PARAMS_FOR_{}", ident), Span::call_site());
+  let tokens: TokenStream = expand_container(&container)?;
+
+  Ok(quote! {
+    #[allow(non_upper_case_globals)]
+    const #dummy: () = {
+      impl #impl_generics ::scarab::export::Params for #ident #ty_generics #where_clause {
+        #[allow(unused_mut)]
+        fn from_params(
+          __PARAMS: &::scarab::export::ScImmutableMap,
+        ) -> ::core::result::Result<Self, ::scarab::export::ParamsError> {
+          #tokens
+        }
+      }
+    };
+  })
+}
+
+fn expand_container(container: &Container) -> Result<TokenStream> {
+  let fields: &[Field] = match container.data {
+    Data::Struct(Style::Struct, ref fields) | Data::Struct(Style::Tuple, ref fields) => fields,
+    Data::Struct(Style::Unit, _) => &[],
+    Data::Enum(_) => return Err(Error::new(Span::call_site(), "Can't derive Params for enums")),
+  };
+
+  let stmts: Vec<TokenStream> = fields.iter().map(expand_field).collect::<Result<_>>()?;
+
+  // Required fields are only unwrapped once every field has been read
+  let required: Vec<Ident> = fields
+    .iter()
+    .filter(|field| is_required(field))
+    .map(Field::binding)
+    .collect();
+
+  let value: TokenStream = expand_value(container, fields);
+
+  let validate: TokenStream = match container.validate {
+    Some(ref path) => quote! {
+      if let ::core::result::Result::Err(error) = #path(&__VALUE) {
+        return ::core::result::Result::Err(::scarab::export::ParamsError::custom(error.to_string()));
+      }
+    },
+    None => quote!(),
+  };
+
+  Ok(quote! {
+    let mut __ERROR: ::scarab::export::ParamsError = ::scarab::export::ParamsError::new();
+
+    #(#stmts)*
+
+    match (#(#required,)*) {
+      (#(::core::option::Option::Some(#required),)*) if __ERROR.is_empty() => {
+        let __VALUE: Self = #value;
+        #validate
+        ::core::result::Result::Ok(__VALUE)
+      }
+      _ => ::core::result::Result::Err(__ERROR),
+    }
+  })
+}
+
+fn expand_value(container: &Container, fields: &[Field]) -> TokenStream {
+  let members: _ = fields.iter().map(|field| &field.member);
+  let bindings: _ = fields.iter().map(Field::binding);
+
+  match container.data {
+    Data::Struct(Style::Unit, _) => quote!(Self),
+    _ => quote!(Self { #(#members: #bindings),* }),
+  }
+}
+
+fn expand_field(field: &Field) -> Result<TokenStream> {
+  let binding: Ident = field.binding();
+  let ty: &Type = field.ty;
+
+  if field.skip {
+    let fallback: TokenStream = field.fallback();
+    return Ok(quote!(let #binding: #ty = #fallback;));
+  }
+
+  let key: String = field.key()?;
+  let validate: TokenStream = expand_validate(&key, field.validate.as_ref());
+
+  // Values that can't be represented by the field type are invalid and read
+  // as absent
+  let read: _ = |ty: &Type| {
+    quote! {
+      match <#ty as ::scarab::export::ParamValue>::read_param(__PARAMS, #key) {
+        ::core::result::Result::Ok(__value) => __value,
+        ::core::result::Result::Err(error) => {
+          __ERROR.add_invalid(#key, error);
+          ::core::option::Option::None
+        }
+      }
+    }
+  };
+
+  if let Some(inner) = field.option() {
+    let read: TokenStream = read(inner);
+
+    return Ok(quote! {
+      let #binding: #ty = #read;
+
+      if let ::core::option::Option::Some(ref __value) = #binding {
+        #validate
+      }
+    });
+  }
+
+  if field.is_optional() {
+    let read: TokenStream = read(ty);
+    let fallback: TokenStream = field.fallback();
+
+    return Ok(quote! {
+      let #binding: #ty = match #read {
+        ::core::option::Option::Some(__value) => __value,
+        ::core::option::Option::None => #fallback,
+      };
+
+      {
+        let __value: &#ty = &#binding;
+        #validate
+      }
+    });
+  }
+
+  Ok(quote! {
+    let #binding: ::core::option::Option<#ty> = match <#ty as ::scarab::export::ParamValue>::read_param(__PARAMS, #key) {
+      ::core::result::Result::Ok(::core::option::Option::Some(__value)) => {
+        {
+          let __value: &#ty = &__value;
+          #validate
+        }
+
+        ::core::option::Option::Some(__value)
+      }
+      ::core::result::Result::Ok(::core::option::Option::None) => {
+        __ERROR.add_missing(#key);
+        ::core::option::Option::None
+      }
+      ::core::result::Result::Err(error) => {
+        __ERROR.add_invalid(#key, error);
+        ::core::option::Option::None
+      }
+    };
+  })
+}
+
+fn expand_validate(key: &str, validate: Option<&Path>) -> TokenStream {
+  match validate {
+    Some(path) => quote! {
+      if let ::core::result::Result::Err(error) = #path(__value) {
+        __ERROR.add_invalid(#key, error.to_string());
+      }
+    },
+    None => quote!(),
+  }
+}
+
+fn is_required(field: &Field) -> bool {
+  !field.skip && !field.is_optional() && field.option().is_none()
+}
@@ -0,0 +1,140 @@
+use proc_macro2::Span;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::DeriveInput;
+use syn::Error;
+use syn::Ident;
+use syn::Member;
+use syn::Result;
+use syn::Type;
+
+use crate::internal::Container;
+use crate::internal::Data;
+use crate::internal::Field;
+use crate::internal::Style;
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+  let container: Container = Container::from_ast(&input)?;
+
+  let ident: &Ident = container.ident;
+  let (impl_generics, ty_generics, where_clause): _ = container.generics.split_for_impl();
+  let dummy: Ident = Ident::new(&format!("_IMPL_RESULTS_FOR_{}", ident), Span::call_site());
+
+  let fields: &[Field] = match container.data {
+    Data::Struct(Style::Struct, ref fields) | Data::Struct(Style::Tuple, ref fields) => fields,
+    Data::Struct(Style::Unit, _) => &[],
+    Data::Enum(_) => return Err(Error::new(Span::call_site(), "Can't derive Results for enums")),
+  };
+
+  let write: TokenStream = expand_write(fields)?;
+  let read: TokenStream = expand_read(&container, fields)?;
+
+  Ok(quote! {
+    #[allow(non_upper_case_globals)]
+    const #dummy: () = {
+      impl #impl_generics ::scarab::export::Results for #ident #ty_generics #where_clause {
+        fn write_results(&self, __RESULTS: &::scarab::export::ScMutableMap) {
+          #write
+        }
+
+        #[allow(unused_mut)]
+        fn from_results(__RESULTS: &::scarab::export::ScImmutableMap) -> Self {
+          #read
+        }
+      }
+    };
+  })
+}
+
+fn expand_write(fields: &[Field]) -> Result<TokenStream> {
+  let stmts: Vec<TokenStream> = fields
+    .iter()
+    .filter(|field| !field.skip)
+    .map(|field| {
+      let key: String = field.key()?;
+      let member: &Member = &field.member;
+
+      Ok(match field.option() {
+        Some(_) => quote! {
+          if let ::core::option::Option::Some(ref __value) = self.#member {
+            ::scarab::export::ResultValue::write_result(__value, __RESULTS, #key);
+          }
+        },
+        None => quote! {
+          ::scarab::export::ResultValue::write_result(&self.#member, __RESULTS, #key);
+        },
+      })
+    })
+    .collect::<Result<_>>()?;
+
+  Ok(quote!(#(#stmts)*))
+}
+
+fn expand_read(container: &Container, fields: &[Field]) -> Result<TokenStream> {
+  let stmts: Vec<TokenStream> = fields.iter().map(expand_field).collect::<Result<_>>()?;
+
+  let required: Vec<Ident> = fields
+    .iter()
+    .filter(|field| !field.skip && !field.is_optional() && field.option().is_none())
+    .map(Field::binding)
+    .collect();
+
+  let members: _ = fields.iter().map(|field| &field.member);
+  let bindings: _ = fields.iter().map(Field::binding);
+
+  let value: TokenStream = match container.data {
+    Data::Struct(Style::Unit, _) => quote!(Self),
+    _ => quote!(Self { #(#members: #bindings),* }),
+  };
+
+  Ok(quote! {
+    let mut __MISSING: ::std::vec::Vec<&'static str> = ::std::vec::Vec::new();
+
+    #(#stmts)*
+
+    match (#(#required,)*) {
+      (#(::core::option::Option::Some(#required),)*) => #value,
+      _ => ::scarab::panic::fail(&::std::format!("missing required results: {}", __MISSING.join(", "))),
+    }
+  })
+}
+
+fn expand_field(field: &Field) -> Result<TokenStream> {
+  let binding: Ident = field.binding();
+  let ty: &Type = field.ty;
+
+  if field.skip {
+    let fallback: TokenStream = field.fallback();
+    return Ok(quote!(let #binding: #ty = #fallback;));
+  }
+
+  let key: String = field.key()?;
+
+  let read: _ = |ty: &Type| quote!(<#ty as ::scarab::export::ResultValue>::read_result(__RESULTS, #key));
+
+  if let Some(inner) = field.option() {
+    let read: TokenStream = read(inner);
+    return Ok(quote!(let #binding: #ty = #read;));
+  }
+
+  let read: TokenStream = read(ty);
+
+  if field.is_optional() {
+    let fallback: TokenStream = field.fallback();
+
+    return Ok(quote! {
+      let #binding: #ty = match #read {
+        ::core::option::Option::Some(__value) => __value,
+        ::core::option::Option::None => #fallback,
+      };
+    });
+  }
+
+  Ok(quote! {
+    let #binding: ::core::option::Option<#ty> = #read;
+
+    if #binding.is_none() {
+      __MISSING.push(#key);
+    }
+  })
+}
@@ -0,0 +1,154 @@
+use proc_macro2::Span;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use syn::Attribute;
+use syn::DeriveInput;
+use syn::Error;
+use syn::Ident;
+use syn::Result;
+use syn::Type;
+
+use crate::internal::Container;
+use crate::internal::Data;
+use crate::internal::Field;
+use crate::internal::Style;
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+  let container: Container = Container::from_ast(&input)?;
+
+  if !container.generics.params.is_empty() {
+    return Err(Error::new_spanned(
+      container.generics,
+      "Can't derive State for generic structs",
+    ));
+  }
+
+  let fields: &[Field] = match container.data {
+    Data::Struct(Style::Struct, ref fields) => fields,
+    Data::Struct(Style::Unit, _) => &[],
+    Data::Struct(Style::Tuple, _) => return Err(Error::new(Span::call_site(), "Can't derive State for tuple structs")),
+    Data::Enum(_) => return Err(Error::new(Span::call_site(), "Can't derive State for enums")),
+  };
+
+  let ident: &Ident = container.ident;
+  let vis: _ = &input.vis;
+  let immutable: Ident = format_ident!("Immutable{}", ident);
+  let mutable: Ident = format_ident!("Mutable{}", ident);
+  let dummy: Ident = Ident::new(&format!("_IMPL_STATE_FOR_{}", ident), Span::call_site());
+
+  let immutable_map: TokenStream = quote!(::scarab::export::ScImmutableMap);
+  let mutable_map: TokenStream = quote!(::scarab::export::ScMutableMap);
+
+  let immutable_doc: String = format!("A read-only accessor for [`{}`] in contract state.", ident);
+  let mutable_doc: String = format!("A mutable accessor for [`{}`] in contract state.", ident);
+
+  let uses: TokenStream = expand_uses(ident, fields);
+
+  let fields: Vec<&Field> = fields.iter().filter(|field| !field.skip).collect();
+  let immutable_fns: Vec<TokenStream> = fields
+    .iter()
+    .map(|field| expand_field(field, &immutable_map))
+    .collect::<Result<_>>()?;
+  let mutable_fns: Vec<TokenStream> = fields
+    .iter()
+    .map(|field| expand_field(field, &mutable_map))
+    .collect::<Result<_>>()?;
+
+  let impl_immutable: TokenStream = expand_impls(ident, &immutable, &immutable_map);
+  let impl_mutable: TokenStream = expand_impls(ident, &mutable, &mutable_map);
+
+  Ok(quote! {
+    #[doc = #immutable_doc]
+    #[derive(Clone, Copy)]
+    #vis struct #immutable {
+      obj_id: i32,
+    }
+
+    #[doc = #mutable_doc]
+    #[derive(Clone, Copy)]
+    #vis struct #mutable {
+      obj_id: i32,
+    }
+
+    impl #immutable {
+      #(#immutable_fns)*
+    }
+
+    impl #mutable {
+      /// Returns a read-only accessor for the same state.
+      pub fn immutable(&self) -> #immutable {
+        #immutable { obj_id: self.obj_id }
+      }
+
+      #(#mutable_fns)*
+    }
+
+    #[allow(non_upper_case_globals)]
+    const #dummy: () = {
+      #impl_immutable
+      #impl_mutable
+      #uses
+    };
+  })
+}
+
+fn expand_impls(ident: &Ident, proxy: &Ident, map: &TokenStream) -> TokenStream {
+  quote! {
+    impl ::scarab::export::StateField<#map> for #ident {
+      type Proxy = #proxy;
+
+      fn open_field(obj_id: i32, key: &str) -> Self::Proxy {
+        <Self as ::scarab::export::State<#map>>::open_state(::scarab::export::open_map(obj_id, key))
+      }
+    }
+
+    impl ::scarab::export::State<#map> for #ident {
+      fn open_state(obj_id: i32) -> Self::Proxy {
+        #proxy { obj_id }
+      }
+    }
+  }
+}
+
+// Fields are only accessed through the proxies, so mark them as used
+fn expand_uses(ident: &Ident, fields: &[Field]) -> TokenStream {
+  if fields.is_empty() {
+    return TokenStream::new();
+  }
+
+  let idents: Vec<&Ident> = fields.iter().filter_map(|field| field.ident).collect();
+
+  quote! {
+    #[allow(dead_code)]
+    fn __fields(__value: &#ident) {
+      let #ident { #(#idents,)* } = __value;
+      let _ = (#(#idents,)*);
+    }
+  }
+}
+
+fn expand_field(field: &Field, map: &TokenStream) -> Result<TokenStream> {
+  let ident: &Ident = match field.ident {
+    Some(ident) => ident,
+    None => return Err(Error::new_spanned(field.original, "expected named field")),
+  };
+
+  let key: String = field.key()?;
+  let ty: &Type = field.ty;
+
+  // Field docs are carried over to the accessor
+  let docs: _ = field
+    .original
+    .attrs
+    .iter()
+    .filter(|attr| attr.path.is_ident("doc"))
+    .collect::<Vec<&Attribute>>();
+
+  Ok(quote! {
+    #(#docs)*
+    pub fn #ident(&self) -> <#ty as ::scarab::export::StateField<#map>>::Proxy {
+      <#ty as ::scarab::export::StateField<#map>>::open_field(self.obj_id, #key)
+    }
+  })
+}
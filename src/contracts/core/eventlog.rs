@@ -15,6 +15,10 @@ use crate::consts::*;
 use crate::contracts::core::Contract;
 use crate::traits::core::Array;
 use crate::traits::extension::MapExt;
+use crate::traits::utility::Event;
+use crate::traits::utility::TryDecode;
+use crate::types::EventError;
+use crate::types::EventRecord;
 use crate::types::ScBytes;
 
 /// A simple wrapper around the core [eventlog][SPEC] contract.
@@ -51,6 +55,31 @@ impl EventLog {
       .to_vec()
   }
 
+  /// Returns the events of type `E` matching the `filter` conditions.
+  ///
+  /// Records of other events are skipped; the `count` of the filter applies
+  /// to all records.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if a record of type `E` could not be decoded.
+  pub fn search_events<E>(&self, ctx: &ScViewContext, filter: EventFilter) -> Result<Vec<E>, EventError>
+  where
+    E: Event + TryDecode,
+  {
+    let mut output: Vec<E> = Vec::new();
+
+    for record in self.search(ctx, filter) {
+      let record: EventRecord = EventRecord::from_bytes(&record)?;
+
+      if record.is::<E>() {
+        output.push(record.decode()?);
+      }
+    }
+
+    Ok(output)
+  }
+
   fn params(&self) -> ScMutableMap {
     map!(CORE_EVENTLOG_PARAM_CONTRACT_HNAME => &self.0)
   }
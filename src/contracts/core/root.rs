@@ -34,6 +34,8 @@ use crate::traits::extension::ColorExt;
 use crate::traits::extension::MapExt;
 use crate::traits::math::ToInteger;
 use crate::traits::utility::Decode as _;
+use crate::traits::utility::TryDecode as _;
+use crate::types::DecodeError;
 use crate::Decode;
 use crate::Encode;
 
@@ -151,6 +153,19 @@ impl<'a> ContractAPI<'a> {
     ContractRecord::from_bytes(&data)
   }
 
+  /// Returns the on-chain record of the smart contract.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the record could not be decoded.
+  pub fn try_record(&self, ctx: &ScViewContext) -> core::result::Result<ContractRecord, DecodeError> {
+    let data: Vec<u8> = ctx
+      .call(CORE_ROOT, CORE_ROOT_VIEW_FIND_CONTRACT, self.params().into())
+      .get_value(CORE_ROOT_PARAM_DATA);
+
+    ContractRecord::try_from_bytes(&data)
+  }
+
   /// Sets the fee values for the smart contract.
   ///
   /// Note: panics if `fee` is empty.
@@ -251,7 +266,7 @@ impl Fee {
     }
 
     if let Some(value) = self.validator.map(i64::from) {
-      params.set(CORE_ROOT_PARAM_OWNER_FEE, &value);
+      params.set(CORE_ROOT_PARAM_VALIDATOR_FEE, &value);
     }
   }
 }
@@ -270,6 +285,24 @@ pub struct ContractRecord {
 }
 
 impl ContractRecord {
+  #[cfg(feature = "testing")]
+  pub(crate) fn new(program_hash: ScHash, description: String, name: String, creator: ScAgentId) -> Self {
+    Self {
+      program_hash,
+      description,
+      name,
+      owner_fee: 0,
+      validator_fee: 0,
+      creator,
+    }
+  }
+
+  #[cfg(feature = "testing")]
+  pub(crate) fn set_fees(&mut self, owner_fee: Option<i64>, validator_fee: Option<i64>) {
+    self.owner_fee = owner_fee.unwrap_or(self.owner_fee);
+    self.validator_fee = validator_fee.unwrap_or(self.validator_fee);
+  }
+
   pub const fn program_hash(&self) -> &ScHash {
     &self.program_hash
   }
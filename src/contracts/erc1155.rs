@@ -0,0 +1,305 @@
+#![allow(non_camel_case_types, non_snake_case)]
+
+use wasmlib::ScAgentId;
+use wasmlib::ScFuncContext;
+use wasmlib::ScViewContext;
+
+use crate::panic::fail;
+use crate::traits::extension::ContextExt;
+use crate::traits::utility::Event;
+use crate::traits::utility::Registry;
+use crate::types::EventSchema;
+use crate::types::ScTag;
+use crate::types::Schema;
+use crate::Decode;
+use crate::Encode;
+
+pub use crate::types::U256;
+
+mod private {
+  pub struct Private;
+}
+
+// =============================================================================
+// ERC-1155 Interface
+// =============================================================================
+
+#[scarab::client]
+pub trait IERC1155 {
+  /// Emitted when `value` tokens of `id` are transferred by `operator`.
+  fn TransferSingle(operator: ScAgentId, from: ScAgentId, to: ScAgentId, id: U256, value: U256) -> TransferSingle {
+    TransferSingle::new(operator, from, to, id, value)
+  }
+
+  /// Emitted when `values` tokens of `ids` are transferred by `operator`.
+  fn TransferBatch(
+    operator: ScAgentId,
+    from: ScAgentId,
+    to: ScAgentId,
+    ids: Vec<U256>,
+    values: Vec<U256>,
+  ) -> TransferBatch {
+    TransferBatch::new(operator, from, to, ids, values)
+  }
+
+  /// Emitted when an operator is enabled or disabled for an owner.
+  fn ApprovalForAll(owner: ScAgentId, operator: ScAgentId, approved: bool) -> ApprovalForAll {
+    ApprovalForAll::new(owner, operator, approved)
+  }
+
+  /// Returns the amount of tokens of `id` owned by `owner`.
+  #[view(result = "balance")]
+  fn balanceOf(ctx: &ScViewContext, owner: &ScAgentId, id: &U256) -> U256;
+
+  /// Returns the amount of tokens of each of `ids` owned by the owner at the same index of `owners`.
+  #[view(result = "balances")]
+  fn balanceOfBatch(ctx: &ScViewContext, owners: &[ScAgentId], ids: &[U256]) -> Vec<U256>;
+
+  /// Returns `true` if `operator` is allowed to manage all tokens of `owner`.
+  #[view(result = "approved")]
+  fn isApprovedForAll(ctx: &ScViewContext, owner: &ScAgentId, operator: &ScAgentId) -> bool;
+
+  /// Enables or disables `operator` to manage all tokens of the caller, and MUST fire the ApprovalForAll event.
+  #[func]
+  fn setApprovalForAll(ctx: &ScFuncContext, operator: &ScAgentId, approved: bool);
+
+  /// Transfers `value` tokens of `id` from `from` to `to`, and MUST fire the TransferSingle event.
+  #[func]
+  fn safeTransferFrom(ctx: &ScFuncContext, from: &ScAgentId, to: &ScAgentId, id: &U256, value: &U256);
+
+  /// Transfers `values` tokens of `ids` from `from` to `to`, and MUST fire the TransferBatch event.
+  #[func]
+  fn safeBatchTransferFrom(ctx: &ScFuncContext, from: &ScAgentId, to: &ScAgentId, ids: &[U256], values: &[U256]);
+}
+
+// =============================================================================
+// ERC-1155 Events
+// =============================================================================
+
+/// Emitted when `value` tokens of `id` are transferred by `operator`.
+#[derive(Encode, Decode)]
+pub struct TransferSingle {
+  pub operator: ScAgentId,
+  pub from: ScAgentId,
+  pub to: ScAgentId,
+  pub id: U256,
+  pub value: U256,
+}
+
+impl TransferSingle {
+  pub const fn new(operator: ScAgentId, from: ScAgentId, to: ScAgentId, id: U256, value: U256) -> TransferSingle {
+    Self {
+      operator,
+      from,
+      to,
+      id,
+      value,
+    }
+  }
+}
+
+impl Event for TransferSingle {
+  const NAME: &'static str = "TransferSingle";
+
+  fn schema() -> EventSchema {
+    EventSchema::new(Self::NAME)
+      .field("operator", ScTag::AgentId)
+      .field("from", ScTag::AgentId)
+      .field("to", ScTag::AgentId)
+      .field("id", ScTag::Bytes)
+      .field("value", ScTag::Bytes)
+  }
+}
+
+/// Emitted when `values` tokens of `ids` are transferred by `operator`.
+#[derive(Encode, Decode)]
+pub struct TransferBatch {
+  pub operator: ScAgentId,
+  pub from: ScAgentId,
+  pub to: ScAgentId,
+  pub ids: Vec<U256>,
+  pub values: Vec<U256>,
+}
+
+impl TransferBatch {
+  pub const fn new(
+    operator: ScAgentId,
+    from: ScAgentId,
+    to: ScAgentId,
+    ids: Vec<U256>,
+    values: Vec<U256>,
+  ) -> TransferBatch {
+    Self {
+      operator,
+      from,
+      to,
+      ids,
+      values,
+    }
+  }
+}
+
+impl Event for TransferBatch {
+  const NAME: &'static str = "TransferBatch";
+
+  fn schema() -> EventSchema {
+    EventSchema::new(Self::NAME)
+      .field("operator", ScTag::AgentId)
+      .field("from", ScTag::AgentId)
+      .field("to", ScTag::AgentId)
+      .field("ids", ScTag::Bytes)
+      .field("values", ScTag::Bytes)
+  }
+}
+
+/// Emitted when an operator is enabled or disabled for an owner.
+#[derive(Encode, Decode)]
+pub struct ApprovalForAll {
+  pub owner: ScAgentId,
+  pub operator: ScAgentId,
+  pub approved: bool,
+}
+
+impl ApprovalForAll {
+  pub const fn new(owner: ScAgentId, operator: ScAgentId, approved: bool) -> ApprovalForAll {
+    Self {
+      owner,
+      operator,
+      approved,
+    }
+  }
+}
+
+impl Event for ApprovalForAll {
+  const NAME: &'static str = "ApprovalForAll";
+
+  fn schema() -> EventSchema {
+    EventSchema::new(Self::NAME)
+      .field("owner", ScTag::AgentId)
+      .field("operator", ScTag::AgentId)
+      .field("approved", ScTag::Int64)
+  }
+}
+
+// =============================================================================
+// Bridge to IOTA Smart Contract API
+// =============================================================================
+
+/// Exports an [IERC1155] implementation.
+///
+/// Batch params (`owners`, `ids` and `values`) are passed as native arrays.
+/// Like scalar params they are required, and they fail the request if their
+/// lengths differ.
+pub trait IERC1155__Bridge: IERC1155 {
+  fn register(exports: &dyn Registry) {
+    Self::export(exports, private::Private);
+  }
+
+  /// Adds the ERC-1155 funcs, views and events to `schema`.
+  fn describe(schema: &mut Schema) {
+    ERC1155Client::describe(schema);
+
+    schema.add_event(TransferSingle::schema());
+    schema.add_event(TransferBatch::schema());
+    schema.add_event(ApprovalForAll::schema());
+  }
+
+  #[doc(hidden)]
+  fn export(exports: &dyn Registry, _: private::Private);
+
+  #[doc(hidden)]
+  fn view_balanceOf(ctx: &ScViewContext) {
+    trace!("ERC1155.balanceOf [>]");
+
+    let owner: ScAgentId = ctx.get_required_param("owner");
+    let id: U256 = ctx.get_required_param("id");
+
+    ctx.result("balance", Self::balanceOf(ctx, &owner, &id));
+
+    trace!("ERC1155.balanceOf [<]");
+  }
+
+  #[doc(hidden)]
+  fn view_balanceOfBatch(ctx: &ScViewContext) {
+    trace!("ERC1155.balanceOfBatch [>]");
+
+    let owners: Vec<ScAgentId> = ctx.get_required_param_value("owners");
+    let ids: Vec<U256> = ctx.get_required_param_value("ids");
+
+    if owners.len() != ids.len() {
+      fail("ERC1155: owners and ids length mismatch");
+    }
+
+    ctx.result("balances", Self::balanceOfBatch(ctx, &owners, &ids));
+
+    trace!("ERC1155.balanceOfBatch [<]");
+  }
+
+  #[doc(hidden)]
+  fn view_isApprovedForAll(ctx: &ScViewContext) {
+    trace!("ERC1155.isApprovedForAll [>]");
+
+    let owner: ScAgentId = ctx.get_required_param("owner");
+    let operator: ScAgentId = ctx.get_required_param("operator");
+
+    ctx.result("approved", Self::isApprovedForAll(ctx, &owner, &operator));
+
+    trace!("ERC1155.isApprovedForAll [<]");
+  }
+
+  #[doc(hidden)]
+  fn func_setApprovalForAll(ctx: &ScFuncContext) {
+    trace!("ERC1155.setApprovalForAll [>]");
+
+    let operator: ScAgentId = ctx.get_required_param("operator");
+
+    let approved: bool = ctx.get_required_param_value("approved");
+
+    Self::setApprovalForAll(ctx, &operator, approved);
+
+    trace!("ERC1155.setApprovalForAll [<]");
+  }
+
+  #[doc(hidden)]
+  fn func_safeTransferFrom(ctx: &ScFuncContext) {
+    trace!("ERC1155.safeTransferFrom [>]");
+
+    let from: ScAgentId = ctx.get_required_param("from");
+    let to: ScAgentId = ctx.get_required_param("to");
+    let id: U256 = ctx.get_required_param("id");
+    let value: U256 = ctx.get_required_param("value");
+
+    Self::safeTransferFrom(ctx, &from, &to, &id, &value);
+
+    trace!("ERC1155.safeTransferFrom [<]");
+  }
+
+  #[doc(hidden)]
+  fn func_safeBatchTransferFrom(ctx: &ScFuncContext) {
+    trace!("ERC1155.safeBatchTransferFrom [>]");
+
+    let from: ScAgentId = ctx.get_required_param("from");
+    let to: ScAgentId = ctx.get_required_param("to");
+    let ids: Vec<U256> = ctx.get_required_param_value("ids");
+    let values: Vec<U256> = ctx.get_required_param_value("values");
+
+    if ids.len() != values.len() {
+      fail("ERC1155: ids and values length mismatch");
+    }
+
+    Self::safeBatchTransferFrom(ctx, &from, &to, &ids, &values);
+
+    trace!("ERC1155.safeBatchTransferFrom [<]");
+  }
+}
+
+impl<T: IERC1155> IERC1155__Bridge for T {
+  fn export(exports: &dyn Registry, _: private::Private) {
+    exports.add_view("balanceOf", T::view_balanceOf);
+    exports.add_view("balanceOfBatch", T::view_balanceOfBatch);
+    exports.add_view("isApprovedForAll", T::view_isApprovedForAll);
+    exports.add_func("setApprovalForAll", T::func_setApprovalForAll);
+    exports.add_func("safeTransferFrom", T::func_safeTransferFrom);
+    exports.add_func("safeBatchTransferFrom", T::func_safeBatchTransferFrom);
+  }
+}
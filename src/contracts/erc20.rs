@@ -1,15 +1,28 @@
 #![allow(non_camel_case_types, non_snake_case)]
 
+use core::marker::PhantomData;
 use wasmlib::ScAgentId;
-use wasmlib::ScExports;
 use wasmlib::ScFuncContext;
+use wasmlib::ScImmutableMap;
+use wasmlib::ScMutableAgentId;
+use wasmlib::ScMutableMap;
 use wasmlib::ScViewContext;
 
+use crate::panic::fail;
 use crate::traits::extension::ContextExt;
+use crate::traits::extension::MapExt;
+use crate::traits::math::try_transfer_between;
+use crate::traits::math::SafeMath;
+use crate::traits::utility::Event;
+use crate::traits::utility::Registry;
+use crate::types::EventSchema;
+use crate::types::ScMutableU256;
+use crate::types::ScTag;
+use crate::types::Schema;
 use crate::Decode;
 use crate::Encode;
 
-pub type U256 = i64;
+pub use crate::types::U256;
 
 mod private {
   pub struct Private;
@@ -19,6 +32,7 @@ mod private {
 // ERC-20 Interface
 // =============================================================================
 
+#[scarab::client]
 pub trait IERC20 {
   /// Emitted when the allowance of a spender changes.
   fn Approval(owner: ScAgentId, spender: ScAgentId, value: U256) -> Approval {
@@ -31,30 +45,39 @@ pub trait IERC20 {
   }
 
   /// Returns the name of the token.
+  #[view]
   fn name(ctx: &ScViewContext) -> String;
 
   /// Returns the symbol of the token.
+  #[view]
   fn symbol(ctx: &ScViewContext) -> String;
 
   /// Returns the number of decimals the token uses.
+  #[view]
   fn decimals(ctx: &ScViewContext) -> u8;
 
   /// Returns the total token supply.
+  #[view]
   fn totalSupply(ctx: &ScViewContext) -> U256;
 
   /// Returns the account balance of another account with address `owner`.
+  #[view(result = "balance")]
   fn balanceOf(ctx: &ScViewContext, owner: &ScAgentId) -> U256;
 
   /// Returns the amount which `spender` is still allowed to withdraw from `owner`.
+  #[view(result = "remaining")]
   fn allowance(ctx: &ScViewContext, owner: &ScAgentId, spender: &ScAgentId) -> U256;
 
   /// Allows `spender` to withdraw from your account multiple times, up to the `value` amount.
+  #[func(result = "success")]
   fn approve(ctx: &ScFuncContext, spender: &ScAgentId, value: &U256) -> bool;
 
   /// Transfers `value` amount of tokens to address `to`, and MUST fire the Transfer event.
+  #[func(result = "success")]
   fn transfer(ctx: &ScFuncContext, to: &ScAgentId, value: &U256) -> bool;
 
   /// Transfers `value` amount of tokens from address `from` to address `to`, and MUST fire the Transfer event.
+  #[func(result = "success")]
   fn transferFrom(ctx: &ScFuncContext, from: &ScAgentId, to: &ScAgentId, value: &U256) -> bool;
 }
 
@@ -76,6 +99,17 @@ impl Approval {
   }
 }
 
+impl Event for Approval {
+  const NAME: &'static str = "Approval";
+
+  fn schema() -> EventSchema {
+    EventSchema::new(Self::NAME)
+      .field("owner", ScTag::AgentId)
+      .field("spender", ScTag::AgentId)
+      .field("value", ScTag::Bytes)
+  }
+}
+
 /// Emitted when tokens are transferred, including zero value transfers.
 #[derive(Encode, Decode)]
 pub struct Transfer {
@@ -90,17 +124,36 @@ impl Transfer {
   }
 }
 
+impl Event for Transfer {
+  const NAME: &'static str = "Transfer";
+
+  fn schema() -> EventSchema {
+    EventSchema::new(Self::NAME)
+      .field("from", ScTag::AgentId)
+      .field("to", ScTag::AgentId)
+      .field("value", ScTag::Bytes)
+  }
+}
+
 // =============================================================================
 // Bridge to IOTA Smart Contract API
 // =============================================================================
 
 pub trait IERC20__Bridge: IERC20 {
-  fn register(exports: &ScExports) {
+  fn register(exports: &dyn Registry) {
     Self::export(exports, private::Private);
   }
 
+  /// Adds the ERC-20 funcs, views and events to `schema`.
+  fn describe(schema: &mut Schema) {
+    ERC20Client::describe(schema);
+
+    schema.add_event(Approval::schema());
+    schema.add_event(Transfer::schema());
+  }
+
   #[doc(hidden)]
-  fn export(exports: &ScExports, _: private::Private);
+  fn export(exports: &dyn Registry, _: private::Private);
 
   #[doc(hidden)]
   fn view_name(ctx: &ScViewContext) {
@@ -124,7 +177,7 @@ pub trait IERC20__Bridge: IERC20 {
   fn view_decimals(ctx: &ScViewContext) {
     trace!("ERC20.decimals [>]");
 
-    ctx.result("decimals", Self::decimals(ctx) as i64);
+    ctx.result("decimals", Self::decimals(ctx));
 
     trace!("ERC20.decimals [<]");
   }
@@ -168,7 +221,7 @@ pub trait IERC20__Bridge: IERC20 {
     let spender: ScAgentId = ctx.get_required_param("spender");
     let value: U256 = ctx.get_required_param("value");
 
-    ctx.result("success", Self::approve(ctx, &spender, &value) as i64);
+    ctx.result("success", Self::approve(ctx, &spender, &value));
 
     trace!("ERC20.approve [<]");
   }
@@ -180,7 +233,7 @@ pub trait IERC20__Bridge: IERC20 {
     let to: ScAgentId = ctx.get_required_param("to");
     let value: U256 = ctx.get_required_param("value");
 
-    ctx.result("success", Self::transfer(ctx, &to, &value) as i64);
+    ctx.result("success", Self::transfer(ctx, &to, &value));
 
     trace!("ERC20.transfer [<]");
   }
@@ -193,14 +246,14 @@ pub trait IERC20__Bridge: IERC20 {
     let to: ScAgentId = ctx.get_required_param("to");
     let value: U256 = ctx.get_required_param("value");
 
-    ctx.result("success", Self::transferFrom(ctx, &from, &to, &value) as i64);
+    ctx.result("success", Self::transferFrom(ctx, &from, &to, &value));
 
     trace!("ERC20.transferFrom [<]");
   }
 }
 
 impl<T: IERC20> IERC20__Bridge for T {
-  fn export(exports: &ScExports, _: private::Private) {
+  fn export(exports: &dyn Registry, _: private::Private) {
     exports.add_view("name", T::view_name);
     exports.add_view("symbol", T::view_symbol);
     exports.add_view("decimals", T::view_decimals);
@@ -212,3 +265,540 @@ impl<T: IERC20> IERC20__Bridge for T {
     exports.add_func("transferFrom", T::func_transferFrom);
   }
 }
+
+// =============================================================================
+// ERC-20 Extensions
+// =============================================================================
+
+/// An [IERC20] extension for creating tokens.
+///
+/// Minted tokens are announced with a [Transfer] event from the zero agent.
+#[scarab::client]
+pub trait IERC20Mintable: IERC20 {
+  /// Creates `value` tokens and assigns them to `to`, increasing the total supply.
+  #[func]
+  fn mint(ctx: &ScFuncContext, to: &ScAgentId, value: &U256);
+}
+
+/// An [IERC20] extension for destroying tokens.
+///
+/// Burned tokens are announced with a [Transfer] event to the zero agent.
+#[scarab::client]
+pub trait IERC20Burnable: IERC20 {
+  /// Destroys `value` tokens of the caller, reducing the total supply.
+  #[func]
+  fn burn(ctx: &ScFuncContext, value: &U256);
+
+  /// Destroys `value` tokens of `from`, deducting from the allowance of the caller.
+  #[func]
+  fn burnFrom(ctx: &ScFuncContext, from: &ScAgentId, value: &U256);
+}
+
+/// An [IERC20] extension for stopping all token movements.
+#[scarab::client]
+pub trait IERC20Pausable: IERC20 {
+  /// Emitted when the token is paused by `account`.
+  fn Paused(account: ScAgentId) -> Paused {
+    Paused::new(account)
+  }
+
+  /// Emitted when the token is unpaused by `account`.
+  fn Unpaused(account: ScAgentId) -> Unpaused {
+    Unpaused::new(account)
+  }
+
+  /// Returns `true` if the token is paused.
+  #[view]
+  fn paused(ctx: &ScViewContext) -> bool;
+
+  /// Stops transfers, minting and burning, and MUST fire the Paused event.
+  #[func]
+  fn pause(ctx: &ScFuncContext);
+
+  /// Resumes transfers, minting and burning, and MUST fire the Unpaused event.
+  #[func]
+  fn unpause(ctx: &ScFuncContext);
+}
+
+/// An [IERC20] extension that limits the total supply.
+///
+/// This extension emits no events.
+#[scarab::client]
+pub trait IERC20Capped: IERC20 {
+  /// Returns the maximum total supply of the token.
+  #[view]
+  fn cap(ctx: &ScViewContext) -> U256;
+}
+
+/// Emitted when the token is paused.
+#[derive(Encode, Decode)]
+pub struct Paused {
+  pub account: ScAgentId,
+}
+
+impl Paused {
+  pub const fn new(account: ScAgentId) -> Paused {
+    Self { account }
+  }
+}
+
+impl Event for Paused {
+  const NAME: &'static str = "Paused";
+
+  fn schema() -> EventSchema {
+    EventSchema::new(Self::NAME).field("account", ScTag::AgentId)
+  }
+}
+
+/// Emitted when the token is unpaused.
+#[derive(Encode, Decode)]
+pub struct Unpaused {
+  pub account: ScAgentId,
+}
+
+impl Unpaused {
+  pub const fn new(account: ScAgentId) -> Unpaused {
+    Self { account }
+  }
+}
+
+impl Event for Unpaused {
+  const NAME: &'static str = "Unpaused";
+
+  fn schema() -> EventSchema {
+    EventSchema::new(Self::NAME).field("account", ScTag::AgentId)
+  }
+}
+
+pub trait IERC20Mintable__Bridge: IERC20Mintable {
+  fn register(exports: &dyn Registry) {
+    Self::export(exports, private::Private);
+  }
+
+  /// Adds the ERC-20 mintable funcs to `schema`.
+  fn describe(schema: &mut Schema) {
+    ERC20MintableClient::describe(schema);
+  }
+
+  #[doc(hidden)]
+  fn export(exports: &dyn Registry, _: private::Private);
+
+  #[doc(hidden)]
+  fn func_mint(ctx: &ScFuncContext) {
+    trace!("ERC20.mint [>]");
+
+    let to: ScAgentId = ctx.get_required_param("to");
+    let value: U256 = ctx.get_required_param("value");
+
+    Self::mint(ctx, &to, &value);
+
+    trace!("ERC20.mint [<]");
+  }
+}
+
+impl<T: IERC20Mintable> IERC20Mintable__Bridge for T {
+  fn export(exports: &dyn Registry, _: private::Private) {
+    exports.add_func("mint", T::func_mint);
+  }
+}
+
+pub trait IERC20Burnable__Bridge: IERC20Burnable {
+  fn register(exports: &dyn Registry) {
+    Self::export(exports, private::Private);
+  }
+
+  /// Adds the ERC-20 burnable funcs to `schema`.
+  fn describe(schema: &mut Schema) {
+    ERC20BurnableClient::describe(schema);
+  }
+
+  #[doc(hidden)]
+  fn export(exports: &dyn Registry, _: private::Private);
+
+  #[doc(hidden)]
+  fn func_burn(ctx: &ScFuncContext) {
+    trace!("ERC20.burn [>]");
+
+    let value: U256 = ctx.get_required_param("value");
+
+    Self::burn(ctx, &value);
+
+    trace!("ERC20.burn [<]");
+  }
+
+  #[doc(hidden)]
+  fn func_burnFrom(ctx: &ScFuncContext) {
+    trace!("ERC20.burnFrom [>]");
+
+    let from: ScAgentId = ctx.get_required_param("from");
+    let value: U256 = ctx.get_required_param("value");
+
+    Self::burnFrom(ctx, &from, &value);
+
+    trace!("ERC20.burnFrom [<]");
+  }
+}
+
+impl<T: IERC20Burnable> IERC20Burnable__Bridge for T {
+  fn export(exports: &dyn Registry, _: private::Private) {
+    exports.add_func("burn", T::func_burn);
+    exports.add_func("burnFrom", T::func_burnFrom);
+  }
+}
+
+pub trait IERC20Pausable__Bridge: IERC20Pausable {
+  fn register(exports: &dyn Registry) {
+    Self::export(exports, private::Private);
+  }
+
+  /// Adds the ERC-20 pausable funcs, views and events to `schema`.
+  fn describe(schema: &mut Schema) {
+    ERC20PausableClient::describe(schema);
+
+    schema.add_event(Paused::schema());
+    schema.add_event(Unpaused::schema());
+  }
+
+  #[doc(hidden)]
+  fn export(exports: &dyn Registry, _: private::Private);
+
+  #[doc(hidden)]
+  fn view_paused(ctx: &ScViewContext) {
+    trace!("ERC20.paused [>]");
+
+    ctx.result("paused", Self::paused(ctx));
+
+    trace!("ERC20.paused [<]");
+  }
+
+  #[doc(hidden)]
+  fn func_pause(ctx: &ScFuncContext) {
+    trace!("ERC20.pause [>]");
+
+    Self::pause(ctx);
+
+    trace!("ERC20.pause [<]");
+  }
+
+  #[doc(hidden)]
+  fn func_unpause(ctx: &ScFuncContext) {
+    trace!("ERC20.unpause [>]");
+
+    Self::unpause(ctx);
+
+    trace!("ERC20.unpause [<]");
+  }
+}
+
+impl<T: IERC20Pausable> IERC20Pausable__Bridge for T {
+  fn export(exports: &dyn Registry, _: private::Private) {
+    exports.add_view("paused", T::view_paused);
+    exports.add_func("pause", T::func_pause);
+    exports.add_func("unpause", T::func_unpause);
+  }
+}
+
+pub trait IERC20Capped__Bridge: IERC20Capped {
+  fn register(exports: &dyn Registry) {
+    Self::export(exports, private::Private);
+  }
+
+  /// Adds the ERC-20 capped views to `schema`.
+  fn describe(schema: &mut Schema) {
+    ERC20CappedClient::describe(schema);
+  }
+
+  #[doc(hidden)]
+  fn export(exports: &dyn Registry, _: private::Private);
+
+  #[doc(hidden)]
+  fn view_cap(ctx: &ScViewContext) {
+    trace!("ERC20.cap [>]");
+
+    ctx.result("cap", Self::cap(ctx));
+
+    trace!("ERC20.cap [<]");
+  }
+}
+
+impl<T: IERC20Capped> IERC20Capped__Bridge for T {
+  fn export(exports: &dyn Registry, _: private::Private) {
+    exports.add_view("cap", T::view_cap);
+  }
+}
+
+// =============================================================================
+// Standard ERC-20 Implementation
+// =============================================================================
+
+const KEY_OWNER: &str = "owner";
+const KEY_TOTAL_SUPPLY: &str = "totalSupply";
+const KEY_BALANCES: &str = "balances";
+const KEY_ALLOWANCES: &str = "allowances";
+const KEY_PAUSED: &str = "paused";
+
+/// The metadata of a [StandardERC20] token.
+pub trait ERC20Config {
+  /// The name of the token.
+  const NAME: &'static str;
+
+  /// The symbol of the token.
+  const SYMBOL: &'static str;
+
+  /// The number of decimals the token uses.
+  const DECIMALS: u8 = 18;
+
+  /// The maximum total supply of the token, if any.
+  const CAP: Option<U256> = None;
+}
+
+/// A ready-to-use [IERC20] implementation backed by contract state.
+///
+/// Balances are stored in the `balances` state map and allowances in the
+/// nested `allowances` map, keyed by owner and then spender. Transfers that
+/// exceed a balance or allowance fail the request. An allowance of
+/// [`U256::MAX`] is never decreased by `transferFrom`.
+///
+/// The contract is initialized with `init`, which assigns the `supply` param
+/// to the `owner` param. Both are required, since `init` is called by the root
+/// contract rather than by the deployer.
+///
+/// All ERC-20 extensions are implemented; only the owner can mint, pause and
+/// unpause. Since each bridge registers its own funcs, a token only supports
+/// the extensions it registers:
+///
+/// ```ignore
+/// StandardERC20::<MyToken>::register_with_init(&exports);
+/// <StandardERC20<MyToken> as IERC20Mintable__Bridge>::register(&exports);
+/// ```
+pub struct StandardERC20<C> {
+  marker: PhantomData<C>,
+}
+
+impl<C: ERC20Config> StandardERC20<C> {
+  /// Registers `init` and the ERC-20 funcs and views with `exports`.
+  pub fn register_with_init(exports: &dyn Registry) {
+    exports.add_func("init", Self::func_init);
+    <Self as IERC20__Bridge>::register(exports);
+  }
+
+  /// Assigns the initial `supply` to `owner`.
+  ///
+  /// Fails the request if the token was already initialized.
+  pub fn init(ctx: &ScFuncContext, owner: &ScAgentId, supply: &U256) {
+    let state: ScMutableMap = ctx.state();
+
+    if state.get::<_, ScMutableAgentId>(KEY_OWNER).exists() {
+      fail("ERC20: already initialized");
+    }
+
+    if *supply > Self::cap(ctx.view()) {
+      fail("ERC20: cap exceeded");
+    }
+
+    state.set(KEY_OWNER, owner);
+    state.set(KEY_TOTAL_SUPPLY, supply);
+    state.get_map(KEY_BALANCES).set(owner, supply);
+
+    Self::Transfer(zero_agent(), owner.clone(), *supply).emit(ctx);
+  }
+
+  /// Returns the owner assigned by `init`.
+  pub fn owner(ctx: &ScViewContext) -> ScAgentId {
+    ctx.state().get_value(KEY_OWNER)
+  }
+
+  #[doc(hidden)]
+  pub fn func_init(ctx: &ScFuncContext) {
+    trace!("ERC20.init [>]");
+
+    let owner: ScAgentId = ctx.get_required_param("owner");
+    let supply: U256 = ctx.get_required_param("supply");
+
+    Self::init(ctx, &owner, &supply);
+
+    trace!("ERC20.init [<]");
+  }
+
+  fn move_balance(ctx: &ScFuncContext, from: &ScAgentId, to: &ScAgentId, value: &U256) {
+    Self::require_unpaused(ctx);
+
+    let balances: ScMutableMap = ctx.state().get_map(KEY_BALANCES);
+    let source: ScMutableU256 = balances.get(from);
+    let target: ScMutableU256 = balances.get(to);
+
+    if let Err(error) = try_transfer_between(&source, &target, *value) {
+      fail(&format!("ERC20: {}", error));
+    }
+
+    Self::Transfer(from.clone(), to.clone(), *value).emit(ctx);
+  }
+
+  fn burn_balance(ctx: &ScFuncContext, from: &ScAgentId, value: &U256) {
+    Self::require_unpaused(ctx);
+
+    let balance: ScMutableU256 = ctx.state().get_map(KEY_BALANCES).get(from);
+
+    if balance.value() < *value {
+      fail("ERC20: insufficient balance");
+    }
+
+    balance.set_value(balance.value() - *value);
+
+    let supply: ScMutableU256 = ctx.state().get(KEY_TOTAL_SUPPLY);
+
+    supply.set_value(supply.value() - *value);
+
+    Self::Transfer(from.clone(), zero_agent(), *value).emit(ctx);
+  }
+
+  fn spend_allowance(ctx: &ScFuncContext, owner: &ScAgentId, value: &U256) {
+    let allowances: ScMutableMap = ctx.state().get_map(KEY_ALLOWANCES);
+    let allowance: ScMutableU256 = allowances.get_map(owner).get(&ctx.caller());
+    let remaining: U256 = allowance.value();
+
+    if remaining < *value {
+      fail("ERC20: insufficient allowance");
+    }
+
+    if remaining != U256::MAX {
+      allowance.set_value(remaining - *value);
+    }
+  }
+
+  fn require_owner(ctx: &ScFuncContext) {
+    if ctx.caller() != Self::owner(ctx.view()) {
+      fail("ERC20: caller is not the owner");
+    }
+  }
+
+  fn require_unpaused(ctx: &ScFuncContext) {
+    if Self::paused(ctx.view()) {
+      fail("ERC20: paused");
+    }
+  }
+
+  fn set_paused(ctx: &ScFuncContext, paused: bool) {
+    Self::require_owner(ctx);
+
+    if Self::paused(ctx.view()) == paused {
+      fail(if paused { "ERC20: paused" } else { "ERC20: not paused" });
+    }
+
+    ctx.state().set(KEY_PAUSED, &i64::from(paused));
+  }
+}
+
+impl<C: ERC20Config> IERC20 for StandardERC20<C> {
+  fn name(_: &ScViewContext) -> String {
+    C::NAME.to_string()
+  }
+
+  fn symbol(_: &ScViewContext) -> String {
+    C::SYMBOL.to_string()
+  }
+
+  fn decimals(_: &ScViewContext) -> u8 {
+    C::DECIMALS
+  }
+
+  fn totalSupply(ctx: &ScViewContext) -> U256 {
+    ctx.state().get_value(KEY_TOTAL_SUPPLY)
+  }
+
+  fn balanceOf(ctx: &ScViewContext, owner: &ScAgentId) -> U256 {
+    ctx.state().get_map(KEY_BALANCES).get_value(owner)
+  }
+
+  fn allowance(ctx: &ScViewContext, owner: &ScAgentId, spender: &ScAgentId) -> U256 {
+    let allowances: ScImmutableMap = ctx.state().get_map(KEY_ALLOWANCES);
+
+    allowances.get_map(owner).get_value(spender)
+  }
+
+  fn approve(ctx: &ScFuncContext, spender: &ScAgentId, value: &U256) -> bool {
+    let owner: ScAgentId = ctx.caller();
+    let allowances: ScMutableMap = ctx.state().get_map(KEY_ALLOWANCES);
+
+    allowances.get_map(&owner).set(spender, value);
+
+    Self::Approval(owner, spender.clone(), *value).emit(ctx);
+
+    true
+  }
+
+  fn transfer(ctx: &ScFuncContext, to: &ScAgentId, value: &U256) -> bool {
+    Self::move_balance(ctx, &ctx.caller(), to, value);
+
+    true
+  }
+
+  fn transferFrom(ctx: &ScFuncContext, from: &ScAgentId, to: &ScAgentId, value: &U256) -> bool {
+    Self::spend_allowance(ctx, from, value);
+    Self::move_balance(ctx, from, to, value);
+
+    true
+  }
+}
+
+impl<C: ERC20Config> IERC20Mintable for StandardERC20<C> {
+  fn mint(ctx: &ScFuncContext, to: &ScAgentId, value: &U256) {
+    Self::require_owner(ctx);
+    Self::require_unpaused(ctx);
+
+    let supply: ScMutableU256 = ctx.state().get(KEY_TOTAL_SUPPLY);
+
+    if let Err(error) = supply.checked_add_value(*value) {
+      fail(&format!("ERC20: {}", error));
+    }
+
+    if supply.value() > Self::cap(ctx.view()) {
+      fail("ERC20: cap exceeded");
+    }
+
+    let balance: ScMutableU256 = ctx.state().get_map(KEY_BALANCES).get(to);
+
+    // The balance can't overflow since it is bounded by the total supply
+    balance.set_value(balance.value() + *value);
+
+    Self::Transfer(zero_agent(), to.clone(), *value).emit(ctx);
+  }
+}
+
+impl<C: ERC20Config> IERC20Burnable for StandardERC20<C> {
+  fn burn(ctx: &ScFuncContext, value: &U256) {
+    Self::burn_balance(ctx, &ctx.caller(), value);
+  }
+
+  fn burnFrom(ctx: &ScFuncContext, from: &ScAgentId, value: &U256) {
+    Self::spend_allowance(ctx, from, value);
+    Self::burn_balance(ctx, from, value);
+  }
+}
+
+impl<C: ERC20Config> IERC20Pausable for StandardERC20<C> {
+  fn paused(ctx: &ScViewContext) -> bool {
+    ctx.state().get_value::<_, i64>(KEY_PAUSED) != 0
+  }
+
+  fn pause(ctx: &ScFuncContext) {
+    Self::set_paused(ctx, true);
+    Self::Paused(ctx.caller()).emit(ctx);
+  }
+
+  fn unpause(ctx: &ScFuncContext) {
+    Self::set_paused(ctx, false);
+    Self::Unpaused(ctx.caller()).emit(ctx);
+  }
+}
+
+impl<C: ERC20Config> IERC20Capped for StandardERC20<C> {
+  fn cap(_: &ScViewContext) -> U256 {
+    C::CAP.unwrap_or(U256::MAX)
+  }
+}
+
+// The agent used as the sender of minted and the receiver of burned tokens
+pub(crate) fn zero_agent() -> ScAgentId {
+  ScAgentId::from_bytes(&[0; 37])
+}
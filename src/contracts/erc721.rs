@@ -0,0 +1,447 @@
+#![allow(non_camel_case_types, non_snake_case)]
+
+use wasmlib::ScAgentId;
+use wasmlib::ScFuncContext;
+use wasmlib::ScImmutableMap;
+use wasmlib::ScMutableMap;
+use wasmlib::ScViewContext;
+
+use crate::contracts::erc20::zero_agent;
+use crate::panic::fail;
+use crate::storage::StorageMap;
+use crate::traits::extension::ContextExt;
+use crate::traits::extension::MapExt;
+use crate::traits::utility::Event;
+use crate::traits::utility::Registry;
+use crate::types::EventSchema;
+use crate::types::ScTag;
+use crate::types::Schema;
+use crate::Decode;
+use crate::Encode;
+
+pub use crate::types::U256;
+
+mod private {
+  pub struct Private;
+}
+
+// =============================================================================
+// ERC-721 Interface
+// =============================================================================
+
+#[scarab::client]
+pub trait IERC721 {
+  /// Emitted when ownership of a token changes.
+  fn Transfer(from: ScAgentId, to: ScAgentId, tokenId: U256) -> Transfer {
+    Transfer::new(from, to, tokenId)
+  }
+
+  /// Emitted when the approved agent of a token changes.
+  fn Approval(owner: ScAgentId, approved: ScAgentId, tokenId: U256) -> Approval {
+    Approval::new(owner, approved, tokenId)
+  }
+
+  /// Emitted when an operator is enabled or disabled for an owner.
+  fn ApprovalForAll(owner: ScAgentId, operator: ScAgentId, approved: bool) -> ApprovalForAll {
+    ApprovalForAll::new(owner, operator, approved)
+  }
+
+  /// Returns the number of tokens owned by `owner`.
+  #[view(result = "balance")]
+  fn balanceOf(ctx: &ScViewContext, owner: &ScAgentId) -> U256;
+
+  /// Returns the owner of the token `tokenId`.
+  #[view(result = "owner")]
+  fn ownerOf(ctx: &ScViewContext, tokenId: &U256) -> ScAgentId;
+
+  /// Returns the agent approved for the token `tokenId`.
+  #[view(result = "approved")]
+  fn getApproved(ctx: &ScViewContext, tokenId: &U256) -> ScAgentId;
+
+  /// Returns `true` if `operator` is allowed to manage all tokens of `owner`.
+  #[view(result = "approved")]
+  fn isApprovedForAll(ctx: &ScViewContext, owner: &ScAgentId, operator: &ScAgentId) -> bool;
+
+  /// Allows `approved` to transfer the token `tokenId`, and MUST fire the Approval event.
+  #[func]
+  fn approve(ctx: &ScFuncContext, approved: &ScAgentId, tokenId: &U256);
+
+  /// Enables or disables `operator` to manage all tokens of the caller, and MUST fire the ApprovalForAll event.
+  #[func]
+  fn setApprovalForAll(ctx: &ScFuncContext, operator: &ScAgentId, approved: bool);
+
+  /// Transfers the token `tokenId` from `from` to `to`, and MUST fire the Transfer event.
+  #[func]
+  fn transferFrom(ctx: &ScFuncContext, from: &ScAgentId, to: &ScAgentId, tokenId: &U256);
+}
+
+// =============================================================================
+// ERC-721 Events
+// =============================================================================
+
+/// Emitted when ownership of a token changes.
+#[derive(Encode, Decode)]
+pub struct Transfer {
+  pub from: ScAgentId,
+  pub to: ScAgentId,
+  pub token_id: U256,
+}
+
+impl Transfer {
+  pub const fn new(from: ScAgentId, to: ScAgentId, token_id: U256) -> Transfer {
+    Self { from, to, token_id }
+  }
+}
+
+impl Event for Transfer {
+  const NAME: &'static str = "Transfer";
+
+  fn schema() -> EventSchema {
+    EventSchema::new(Self::NAME)
+      .field("from", ScTag::AgentId)
+      .field("to", ScTag::AgentId)
+      .field("tokenId", ScTag::Bytes)
+  }
+}
+
+/// Emitted when the approved agent of a token changes.
+#[derive(Encode, Decode)]
+pub struct Approval {
+  pub owner: ScAgentId,
+  pub approved: ScAgentId,
+  pub token_id: U256,
+}
+
+impl Approval {
+  pub const fn new(owner: ScAgentId, approved: ScAgentId, token_id: U256) -> Approval {
+    Self {
+      owner,
+      approved,
+      token_id,
+    }
+  }
+}
+
+impl Event for Approval {
+  const NAME: &'static str = "Approval";
+
+  fn schema() -> EventSchema {
+    EventSchema::new(Self::NAME)
+      .field("owner", ScTag::AgentId)
+      .field("approved", ScTag::AgentId)
+      .field("tokenId", ScTag::Bytes)
+  }
+}
+
+/// Emitted when an operator is enabled or disabled for an owner.
+#[derive(Encode, Decode)]
+pub struct ApprovalForAll {
+  pub owner: ScAgentId,
+  pub operator: ScAgentId,
+  pub approved: bool,
+}
+
+impl ApprovalForAll {
+  pub const fn new(owner: ScAgentId, operator: ScAgentId, approved: bool) -> ApprovalForAll {
+    Self {
+      owner,
+      operator,
+      approved,
+    }
+  }
+}
+
+impl Event for ApprovalForAll {
+  const NAME: &'static str = "ApprovalForAll";
+
+  fn schema() -> EventSchema {
+    EventSchema::new(Self::NAME)
+      .field("owner", ScTag::AgentId)
+      .field("operator", ScTag::AgentId)
+      .field("approved", ScTag::Int64)
+  }
+}
+
+// =============================================================================
+// Bridge to IOTA Smart Contract API
+// =============================================================================
+
+pub trait IERC721__Bridge: IERC721 {
+  fn register(exports: &dyn Registry) {
+    Self::export(exports, private::Private);
+  }
+
+  /// Adds the ERC-721 funcs, views and events to `schema`.
+  fn describe(schema: &mut Schema) {
+    ERC721Client::describe(schema);
+
+    schema.add_event(Transfer::schema());
+    schema.add_event(Approval::schema());
+    schema.add_event(ApprovalForAll::schema());
+  }
+
+  #[doc(hidden)]
+  fn export(exports: &dyn Registry, _: private::Private);
+
+  #[doc(hidden)]
+  fn view_balanceOf(ctx: &ScViewContext) {
+    trace!("ERC721.balanceOf [>]");
+
+    let owner: ScAgentId = ctx.get_required_param("owner");
+
+    ctx.result("balance", Self::balanceOf(ctx, &owner));
+
+    trace!("ERC721.balanceOf [<]");
+  }
+
+  #[doc(hidden)]
+  fn view_ownerOf(ctx: &ScViewContext) {
+    trace!("ERC721.ownerOf [>]");
+
+    let token_id: U256 = ctx.get_required_param("tokenId");
+
+    ctx.result("owner", Self::ownerOf(ctx, &token_id));
+
+    trace!("ERC721.ownerOf [<]");
+  }
+
+  #[doc(hidden)]
+  fn view_getApproved(ctx: &ScViewContext) {
+    trace!("ERC721.getApproved [>]");
+
+    let token_id: U256 = ctx.get_required_param("tokenId");
+
+    ctx.result("approved", Self::getApproved(ctx, &token_id));
+
+    trace!("ERC721.getApproved [<]");
+  }
+
+  #[doc(hidden)]
+  fn view_isApprovedForAll(ctx: &ScViewContext) {
+    trace!("ERC721.isApprovedForAll [>]");
+
+    let owner: ScAgentId = ctx.get_required_param("owner");
+    let operator: ScAgentId = ctx.get_required_param("operator");
+
+    ctx.result("approved", Self::isApprovedForAll(ctx, &owner, &operator));
+
+    trace!("ERC721.isApprovedForAll [<]");
+  }
+
+  #[doc(hidden)]
+  fn func_approve(ctx: &ScFuncContext) {
+    trace!("ERC721.approve [>]");
+
+    let approved: ScAgentId = ctx.get_required_param("approved");
+    let token_id: U256 = ctx.get_required_param("tokenId");
+
+    Self::approve(ctx, &approved, &token_id);
+
+    trace!("ERC721.approve [<]");
+  }
+
+  #[doc(hidden)]
+  fn func_setApprovalForAll(ctx: &ScFuncContext) {
+    trace!("ERC721.setApprovalForAll [>]");
+
+    let operator: ScAgentId = ctx.get_required_param("operator");
+
+    let approved: bool = ctx.get_required_param_value("approved");
+
+    Self::setApprovalForAll(ctx, &operator, approved);
+
+    trace!("ERC721.setApprovalForAll [<]");
+  }
+
+  #[doc(hidden)]
+  fn func_transferFrom(ctx: &ScFuncContext) {
+    trace!("ERC721.transferFrom [>]");
+
+    let from: ScAgentId = ctx.get_required_param("from");
+    let to: ScAgentId = ctx.get_required_param("to");
+    let token_id: U256 = ctx.get_required_param("tokenId");
+
+    Self::transferFrom(ctx, &from, &to, &token_id);
+
+    trace!("ERC721.transferFrom [<]");
+  }
+}
+
+impl<T: IERC721> IERC721__Bridge for T {
+  fn export(exports: &dyn Registry, _: private::Private) {
+    exports.add_view("balanceOf", T::view_balanceOf);
+    exports.add_view("ownerOf", T::view_ownerOf);
+    exports.add_view("getApproved", T::view_getApproved);
+    exports.add_view("isApprovedForAll", T::view_isApprovedForAll);
+    exports.add_func("approve", T::func_approve);
+    exports.add_func("setApprovalForAll", T::func_setApprovalForAll);
+    exports.add_func("transferFrom", T::func_transferFrom);
+  }
+}
+
+// =============================================================================
+// Standard ERC-721 Implementation
+// =============================================================================
+
+const KEY_OWNERS: &str = "owners";
+const KEY_BALANCES: &str = "balances";
+const KEY_APPROVALS: &str = "approvals";
+const KEY_OPERATORS: &str = "operators";
+
+/// A ready-to-use [IERC721] implementation backed by contract state.
+///
+/// Owners and approvals are stored in the `owners` and `approvals` storage
+/// maps keyed by token id, balances in the `balances` storage map and
+/// operators in the nested `operators` state map, keyed by owner and then
+/// operator.
+///
+/// Tokens are created and destroyed by the contract with [`mint`][Self::mint]
+/// and [`burn`][Self::burn].
+pub struct StandardERC721;
+
+impl StandardERC721 {
+  /// Creates the token `token_id` and assigns it to `to`.
+  ///
+  /// Fails the request if the token already exists or `to` is the zero agent.
+  pub fn mint(ctx: &ScFuncContext, to: &ScAgentId, token_id: &U256) {
+    if *to == zero_agent() {
+      fail("ERC721: mint to the zero agent");
+    }
+
+    let owners: StorageMap<U256, ScAgentId> = ctx.get_state_map(KEY_OWNERS);
+
+    if owners.contains_key(token_id) {
+      fail("ERC721: token already minted");
+    }
+
+    owners.insert(token_id, to.clone());
+
+    Self::add_balance(ctx, to, true);
+    Self::Transfer(zero_agent(), to.clone(), *token_id).emit(ctx);
+  }
+
+  /// Destroys the token `token_id`.
+  ///
+  /// Fails the request if the token does not exist.
+  pub fn burn(ctx: &ScFuncContext, token_id: &U256) {
+    let owners: StorageMap<U256, ScAgentId> = ctx.get_state_map(KEY_OWNERS);
+    let approvals: StorageMap<U256, ScAgentId> = ctx.get_state_map(KEY_APPROVALS);
+
+    let owner: ScAgentId = match owners.remove(token_id) {
+      Some(owner) => owner,
+      None => fail("ERC721: invalid token id"),
+    };
+
+    approvals.remove(token_id);
+
+    Self::add_balance(ctx, &owner, false);
+    Self::Transfer(owner, zero_agent(), *token_id).emit(ctx);
+  }
+
+  // Increments or decrements the number of tokens owned by `owner`
+  fn add_balance(ctx: &ScFuncContext, owner: &ScAgentId, increment: bool) {
+    let balances: StorageMap<ScAgentId, U256> = ctx.get_state_map(KEY_BALANCES);
+    let balance: U256 = balances.get(owner).unwrap_or_default();
+
+    if increment {
+      balances.insert(owner, balance + U256::ONE);
+    } else {
+      balances.insert(owner, balance - U256::ONE);
+    }
+  }
+}
+
+impl IERC721 for StandardERC721 {
+  fn balanceOf(ctx: &ScViewContext, owner: &ScAgentId) -> U256 {
+    let balances: StorageMap<ScAgentId, U256, ScImmutableMap> = ctx.get_state_map(KEY_BALANCES);
+
+    balances.get(owner).unwrap_or_default()
+  }
+
+  fn ownerOf(ctx: &ScViewContext, tokenId: &U256) -> ScAgentId {
+    let owners: StorageMap<U256, ScAgentId, ScImmutableMap> = ctx.get_state_map(KEY_OWNERS);
+
+    match owners.get(tokenId) {
+      Some(owner) => owner,
+      None => fail("ERC721: invalid token id"),
+    }
+  }
+
+  fn getApproved(ctx: &ScViewContext, tokenId: &U256) -> ScAgentId {
+    let approvals: StorageMap<U256, ScAgentId, ScImmutableMap> = ctx.get_state_map(KEY_APPROVALS);
+
+    // Ensure the token exists
+    Self::ownerOf(ctx, tokenId);
+
+    approvals.get(tokenId).unwrap_or_else(zero_agent)
+  }
+
+  fn isApprovedForAll(ctx: &ScViewContext, owner: &ScAgentId, operator: &ScAgentId) -> bool {
+    let operators: ScImmutableMap = ctx.state().get_map(KEY_OPERATORS);
+
+    operators.get_map(owner).get_value::<_, i64>(operator) != 0
+  }
+
+  fn approve(ctx: &ScFuncContext, approved: &ScAgentId, tokenId: &U256) {
+    let caller: ScAgentId = ctx.caller();
+    let owner: ScAgentId = Self::ownerOf(ctx.view(), tokenId);
+
+    if *approved == owner {
+      fail("ERC721: approval to current owner");
+    }
+
+    if caller != owner && !Self::isApprovedForAll(ctx.view(), &owner, &caller) {
+      fail("ERC721: caller is not the owner or an operator");
+    }
+
+    let approvals: StorageMap<U256, ScAgentId> = ctx.get_state_map(KEY_APPROVALS);
+
+    approvals.insert(tokenId, approved.clone());
+
+    Self::Approval(owner, approved.clone(), *tokenId).emit(ctx);
+  }
+
+  fn setApprovalForAll(ctx: &ScFuncContext, operator: &ScAgentId, approved: bool) {
+    let owner: ScAgentId = ctx.caller();
+
+    if *operator == owner {
+      fail("ERC721: approve to caller");
+    }
+
+    let operators: ScMutableMap = ctx.state().get_map(KEY_OPERATORS);
+
+    operators.get_map(&owner).set(operator, &approved);
+
+    Self::ApprovalForAll(owner, operator.clone(), approved).emit(ctx);
+  }
+
+  fn transferFrom(ctx: &ScFuncContext, from: &ScAgentId, to: &ScAgentId, tokenId: &U256) {
+    let caller: ScAgentId = ctx.caller();
+    let owner: ScAgentId = Self::ownerOf(ctx.view(), tokenId);
+
+    if *from != owner {
+      fail("ERC721: transfer from incorrect owner");
+    }
+
+    if *to == zero_agent() {
+      fail("ERC721: transfer to the zero agent");
+    }
+
+    if caller != owner
+      && caller != Self::getApproved(ctx.view(), tokenId)
+      && !Self::isApprovedForAll(ctx.view(), &owner, &caller)
+    {
+      fail("ERC721: caller is not the owner or approved");
+    }
+
+    let owners: StorageMap<U256, ScAgentId> = ctx.get_state_map(KEY_OWNERS);
+    let approvals: StorageMap<U256, ScAgentId> = ctx.get_state_map(KEY_APPROVALS);
+
+    approvals.remove(tokenId);
+    owners.insert(tokenId, to.clone());
+
+    Self::add_balance(ctx, from, false);
+    Self::add_balance(ctx, to, true);
+    Self::Transfer(from.clone(), to.clone(), *tokenId).emit(ctx);
+  }
+}
@@ -3,4 +3,6 @@
 #![allow(missing_docs)]
 
 pub mod core;
+pub mod erc1155;
 pub mod erc20;
+pub mod erc721;
@@ -1,6 +1,6 @@
 //! Tools for working with IOTA Smart Contracts
 
-#![feature(core_intrinsics)]
+#![cfg_attr(not(feature = "testing"), feature(core_intrinsics))]
 #![feature(const_unreachable_unchecked)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![warn(
@@ -33,6 +33,7 @@ pub mod panic;
 
 pub mod consts;
 pub mod contracts;
+pub mod storage;
 pub mod traits;
 pub mod types;
 pub mod utils;
@@ -41,6 +42,10 @@ pub mod utils;
 #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
 pub mod rand;
 
+#[cfg(all(feature = "testing", not(target_arch = "wasm32")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub mod testing;
+
 pub mod prelude {
   //! The Scarab Prelude.
   //!
@@ -53,12 +58,45 @@ pub mod prelude {
   pub use crate::traits::extension::ValueExt;
   pub use crate::traits::math::Integer;
   pub use crate::traits::utility::Export;
+  pub use crate::traits::utility::Params;
 }
 
 #[doc(hidden)]
 pub mod export {
+  pub use crate::storage::open_map;
+  pub use crate::storage::State;
+  pub use crate::storage::StateField;
+  pub use crate::traits::core::Value;
+  pub use crate::traits::extension::ContextExt;
+  pub use crate::traits::extension::MapExt;
   pub use crate::traits::utility::Decode;
   pub use crate::traits::utility::Encode;
-  pub use wasmlib::BytesDecoder;
-  pub use wasmlib::BytesEncoder;
+  pub use crate::traits::utility::Event;
+  pub use crate::traits::utility::Export;
+  pub use crate::traits::utility::ParamValue;
+  pub use crate::traits::utility::Params;
+  pub use crate::traits::utility::Registry;
+  pub use crate::traits::utility::ResultValue;
+  pub use crate::traits::utility::Results;
+  pub use crate::traits::utility::TryDecode;
+  // `Decoder` provides the methods of the wasmlib `BytesDecoder` that `Decode`
+  // implementations were written against before it replaced it
+  pub use crate::types::DecodeError;
+  pub use crate::types::DecodeErrorKind;
+  pub use crate::types::Decoder as BytesDecoder;
+  pub use crate::types::Decoder;
+  pub use crate::types::Encoder as BytesEncoder;
+  pub use crate::types::Encoder;
+  pub use crate::types::EventSchema;
+  pub use crate::types::FuncSchema;
+  pub use crate::types::ParamsError;
+  pub use crate::types::ScTag;
+  pub use crate::types::Schema;
+  pub use wasmlib::ScExports;
+  pub use wasmlib::ScFuncContext;
+  pub use wasmlib::ScHname;
+  pub use wasmlib::ScImmutableMap;
+  pub use wasmlib::ScMutableMap;
+  pub use wasmlib::ScTransfers;
+  pub use wasmlib::ScViewContext;
 }
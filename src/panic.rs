@@ -1,4 +1,6 @@
+#[cfg(not(feature = "testing"))]
 use core::intrinsics::abort;
+use wasmlib::host;
 
 mod private {
   pub trait Sealed {}
@@ -15,7 +17,7 @@ impl<T> Unwrap<T> for Option<T> {
   fn unwrap_abort(self) -> T {
     match self {
       Some(item) => item,
-      None => abort(),
+      None => halt("called `unwrap_abort` on a `None` value"),
     }
   }
 }
@@ -24,7 +26,24 @@ impl<T, E> Unwrap<T> for Result<T, E> {
   fn unwrap_abort(self) -> T {
     match self {
       Ok(item) => item,
-      Err(_) => abort(),
+      Err(_) => halt("called `unwrap_abort` on an `Err` value"),
     }
   }
 }
+
+/// Reports `message` to the host and stops execution.
+pub fn fail(message: &str) -> ! {
+  host::panic(message);
+  halt(message)
+}
+
+#[cfg(not(feature = "testing"))]
+fn halt(_: &str) -> ! {
+  abort()
+}
+
+// Unwind instead of aborting so the mock host can report the failure
+#[cfg(feature = "testing")]
+fn halt(message: &str) -> ! {
+  std::panic::resume_unwind(Box::new(message.to_string()))
+}
@@ -0,0 +1,163 @@
+use core::marker::PhantomData;
+use wasmlib::host;
+use wasmlib::Key32;
+use wasmlib::MapKey;
+use wasmlib::ScImmutableBytes;
+use wasmlib::ScImmutableInt64;
+use wasmlib::ScImmutableMap;
+use wasmlib::ScMutableBytes;
+use wasmlib::ScMutableInt64;
+use wasmlib::ScMutableMap;
+
+use crate::storage::raw;
+use crate::traits::core::Array;
+use crate::traits::core::ArrayMut;
+use crate::traits::core::Proxy;
+use crate::types::ScBytes;
+
+/// An array of encoded values with an explicitly tracked length.
+///
+/// Host arrays can only be cleared as a whole so the length is stored
+/// separately, which allows the array to shrink one element at a time.
+/// Elements past the tracked length are left in place and overwritten by the
+/// next push.
+pub(crate) struct RawArray<M> {
+  obj_id: i32,
+  parent: i32,
+  length: Key32,
+  access: PhantomData<M>,
+}
+
+impl<M> RawArray<M> {
+  /// Opens the array stored under `key` in the map `parent`.
+  pub(crate) fn open(parent: i32, key: &str, length: &str) -> Self {
+    Self {
+      obj_id: raw::object(parent, key, host::TYPE_BYTES | host::TYPE_ARRAY),
+      parent,
+      length: length.get_key_id(),
+      access: PhantomData,
+    }
+  }
+
+  fn size(&self) -> usize {
+    ScImmutableInt64::new(self.parent, self.length).value() as usize
+  }
+}
+
+impl RawArray<ScMutableMap> {
+  /// Removes and returns the last value of the array.
+  pub(crate) fn pop(&self) -> Option<ScBytes> {
+    let size: usize = self.len().checked_sub(1)?;
+    let value: ScBytes = self.get(size);
+
+    self.resize(size);
+
+    Some(value)
+  }
+
+  /// Shortens the array to `size` values.
+  pub(crate) fn truncate(&self, size: usize) {
+    if size < self.len() {
+      self.resize(size);
+    }
+  }
+
+  fn resize(&self, size: usize) {
+    ScMutableInt64::new(self.parent, self.length).set_value(size as i64);
+  }
+}
+
+impl Array for RawArray<ScImmutableMap> {
+  type Value = ScImmutableBytes;
+
+  fn len(&self) -> usize {
+    self.size()
+  }
+
+  fn proxy(&self, index: usize) -> Self::Value {
+    ScImmutableBytes::new(self.obj_id, Key32(index as i32))
+  }
+}
+
+impl Array for RawArray<ScMutableMap> {
+  type Value = ScMutableBytes;
+
+  fn len(&self) -> usize {
+    self.size()
+  }
+
+  fn proxy(&self, index: usize) -> Self::Value {
+    ScMutableBytes::new(self.obj_id, Key32(index as i32))
+  }
+}
+
+impl ArrayMut for RawArray<ScMutableMap> {
+  fn erase(&self) {
+    host::clear(self.obj_id);
+    self.resize(0);
+  }
+
+  fn push(&self, value: <Self::Value as Proxy>::Value) {
+    let size: usize = self.len();
+
+    self.proxy(size).set_value(&value);
+    self.resize(size + 1);
+  }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+  use super::*;
+  use crate::testing::MockHost;
+
+  fn open<M>() -> RawArray<M> {
+    RawArray::open(raw::open("array"), "items", "length")
+  }
+
+  #[test]
+  fn test_push_pop() {
+    let _host: MockHost = MockHost::new("test");
+    let array: RawArray<ScMutableMap> = open();
+
+    assert_eq!(array.pop(), None);
+
+    for value in 0..4_u8 {
+      array.push(vec![value]);
+    }
+
+    assert_eq!(array.len(), 4);
+    assert_eq!(array.pop(), Some(vec![3]));
+    assert_eq!(array.len(), 3);
+
+    // Stale elements past the length are overwritten
+    array.push(vec![7]);
+
+    assert_eq!(array.to_vec(), vec![vec![0], vec![1], vec![2], vec![7]]);
+  }
+
+  #[test]
+  fn test_truncate() {
+    let _host: MockHost = MockHost::new("test");
+    let array: RawArray<ScMutableMap> = open();
+
+    for value in 0..5_u8 {
+      array.push(vec![value]);
+    }
+
+    array.truncate(8);
+    assert_eq!(array.len(), 5);
+
+    array.truncate(2);
+    assert_eq!(array.len(), 2);
+
+    let reader: RawArray<ScImmutableMap> = open();
+
+    assert_eq!(reader.iter().collect::<Vec<_>>(), vec![vec![0], vec![1]]);
+
+    array.push(vec![9]);
+    array.erase();
+
+    assert!(reader.is_empty());
+    assert_eq!(reader.iter().next(), None);
+  }
+}
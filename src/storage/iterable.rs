@@ -0,0 +1,268 @@
+use core::marker::PhantomData;
+use wasmlib::host;
+use wasmlib::MapKey;
+use wasmlib::ScImmutableMap;
+use wasmlib::ScMutableMap;
+
+use crate::storage::raw;
+use crate::storage::RawArray;
+use crate::storage::StorageMap;
+use crate::traits::core::Array;
+use crate::traits::core::ArrayMut;
+use crate::traits::core::Iter as ArrayIter;
+use crate::traits::utility::Decode;
+use crate::traits::utility::Encode;
+use crate::types::ScBytes;
+use crate::types::ScInt64;
+
+/// A typed map of [encoded][Encode] values that can enumerate its keys.
+///
+/// In addition to the entries of a [StorageMap], this keeps a companion array
+/// of encoded keys and the position of each key in the array. Removing an
+/// entry moves the last key into the vacated position so every operation
+/// touches a constant number of state values.
+pub struct IterableMap<K, V, M = ScMutableMap> {
+  keys: RawArray<M>,
+  index: StorageMap<K, ScInt64, M>,
+  values: StorageMap<K, V, M>,
+}
+
+impl<K, V, M> IterableMap<K, V, M>
+where
+  K: MapKey + Decode,
+  V: Decode,
+{
+  /// Opens the map stored under `prefix` in contract state.
+  pub fn open<T>(prefix: &T) -> Self
+  where
+    T: MapKey + ?Sized,
+  {
+    Self::from_obj_id(raw::open(prefix))
+  }
+
+  pub(crate) fn from_obj_id(obj_id: i32) -> Self {
+    Self {
+      keys: RawArray::open(obj_id, "keys", "length"),
+      index: StorageMap::from_obj_id(raw::object(obj_id, "index", host::TYPE_MAP)),
+      values: StorageMap::from_obj_id(raw::object(obj_id, "values", host::TYPE_MAP)),
+    }
+  }
+
+  /// Returns the value stored under `key`.
+  pub fn get(&self, key: &K) -> Option<V> {
+    self.values.get(key)
+  }
+
+  /// Returns `true` if the map contains a value for `key`.
+  pub fn contains_key(&self, key: &K) -> bool {
+    self.index.contains_key(key)
+  }
+}
+
+impl<K, V> IterableMap<K, V, ScMutableMap>
+where
+  K: MapKey + Encode + Decode,
+  V: Encode + Decode,
+{
+  /// Stores `value` under `key`, returning the previous value if present.
+  pub fn insert(&self, key: &K, value: V) -> Option<V> {
+    if !self.index.contains_key(key) {
+      self.index.insert(key, self.keys.len() as ScInt64);
+      self.keys.push(key.to_bytes());
+    }
+
+    self.values.insert(key, value)
+  }
+
+  /// Removes the value stored under `key`, returning it if present.
+  ///
+  /// Note: This moves the last key of the map into the position of `key`.
+  pub fn remove(&self, key: &K) -> Option<V> {
+    let index: usize = self.index.remove(key)? as usize;
+    let last: ScBytes = self.keys.pop()?;
+
+    if index < self.keys.len() {
+      self.keys.proxy(index).set_value(&last);
+      self.index.insert(&K::from_bytes(&last), index as ScInt64);
+    }
+
+    self.values.remove(key)
+  }
+
+  /// Removes all entries from the map.
+  pub fn clear(&self) {
+    for key in self.keys() {
+      self.index.delete(&key);
+      self.values.delete(&key);
+    }
+
+    self.keys.erase();
+  }
+}
+
+macro_rules! impl_IterableMap {
+  ($map:ident) => {
+    impl<K, V> IterableMap<K, V, $map>
+    where
+      K: MapKey + Decode,
+      V: Decode,
+    {
+      /// Returns the number of entries in the map.
+      pub fn len(&self) -> usize {
+        self.keys.len()
+      }
+
+      /// Returns `true` if the map contains no entries.
+      pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+      }
+
+      /// Returns an iterator over the keys of the map.
+      pub fn keys(&self) -> Keys<'_, K, $map> {
+        Keys {
+          inner: self.keys.iter(),
+          marker: PhantomData,
+        }
+      }
+
+      /// Returns an iterator over the values of the map.
+      pub fn values(&self) -> Values<'_, K, V, $map> {
+        Values {
+          keys: self.keys(),
+          values: &self.values,
+        }
+      }
+
+      /// Returns an iterator over the entries of the map.
+      pub fn iter(&self) -> Iter<'_, K, V, $map> {
+        Iter {
+          keys: self.keys(),
+          values: &self.values,
+        }
+      }
+    }
+
+    impl<'a, K> Iterator for Keys<'a, K, $map>
+    where
+      K: Decode,
+    {
+      type Item = K;
+
+      fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|bytes| K::from_bytes(&bytes))
+      }
+    }
+
+    impl<'a, K, V> Iterator for Values<'a, K, V, $map>
+    where
+      K: MapKey + Decode,
+      V: Decode,
+    {
+      type Item = V;
+
+      fn next(&mut self) -> Option<Self::Item> {
+        self.keys.next().and_then(|key| self.values.get(&key))
+      }
+    }
+
+    impl<'a, K, V> Iterator for Iter<'a, K, V, $map>
+    where
+      K: MapKey + Decode,
+      V: Decode,
+    {
+      type Item = (K, V);
+
+      fn next(&mut self) -> Option<Self::Item> {
+        let key: K = self.keys.next()?;
+        let value: V = self.values.get(&key)?;
+
+        Some((key, value))
+      }
+    }
+  };
+}
+
+impl_IterableMap!(ScImmutableMap);
+impl_IterableMap!(ScMutableMap);
+
+// =============================================================================
+// =============================================================================
+
+/// An iterator over the keys of an [IterableMap].
+pub struct Keys<'a, K, M> {
+  inner: ArrayIter<'a, RawArray<M>>,
+  marker: PhantomData<fn() -> K>,
+}
+
+/// An iterator over the values of an [IterableMap].
+pub struct Values<'a, K, V, M> {
+  keys: Keys<'a, K, M>,
+  values: &'a StorageMap<K, V, M>,
+}
+
+/// An iterator over the entries of an [IterableMap].
+pub struct Iter<'a, K, V, M> {
+  keys: Keys<'a, K, M>,
+  values: &'a StorageMap<K, V, M>,
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+  use super::*;
+  use crate::testing::MockHost;
+
+  #[test]
+  fn test_swap_remove() {
+    let _host: MockHost = MockHost::new("test");
+    let map: IterableMap<String, i64> = IterableMap::open("holders");
+
+    for (value, key) in ["a", "b", "c", "d", "e"].iter().enumerate() {
+      assert_eq!(map.insert(&key.to_string(), value as i64), None);
+    }
+
+    assert_eq!(map.insert(&"c".to_string(), 10), Some(2));
+    assert_eq!(map.len(), 5);
+
+    // The last key moves into the position of the removed key
+    assert_eq!(map.remove(&"b".to_string()), Some(1));
+    assert_eq!(map.remove(&"b".to_string()), None);
+    assert_eq!(map.keys().collect::<Vec<_>>(), ["a", "e", "c", "d"]);
+
+    // Removing the last key leaves the others in place
+    assert_eq!(map.remove(&"d".to_string()), Some(3));
+    assert_eq!(map.keys().collect::<Vec<_>>(), ["a", "e", "c"]);
+
+    map.insert(&"f".to_string(), 5);
+
+    assert_eq!(map.keys().collect::<Vec<_>>(), ["a", "e", "c", "f"]);
+    assert!(!map.contains_key(&"b".to_string()));
+    assert_eq!(map.get(&"e".to_string()), Some(4));
+  }
+
+  #[test]
+  fn test_iterators() {
+    let _host: MockHost = MockHost::new("test");
+    let map: IterableMap<String, i64> = IterableMap::open("holders");
+    let reader: IterableMap<String, i64, ScImmutableMap> = IterableMap::open("holders");
+
+    assert!(reader.is_empty());
+    assert_eq!(reader.iter().next(), None);
+
+    map.insert(&"x".to_string(), 1);
+    map.insert(&"y".to_string(), 2);
+    map.insert(&"z".to_string(), 3);
+    map.remove(&"x".to_string());
+
+    assert_eq!(
+      reader.iter().collect::<Vec<_>>(),
+      [("z".to_string(), 3), ("y".to_string(), 2)]
+    );
+    assert_eq!(reader.values().collect::<Vec<_>>(), [3, 2]);
+
+    map.clear();
+
+    assert!(reader.is_empty());
+    assert!(!reader.contains_key(&"y".to_string()));
+    assert_eq!(map.get(&"z".to_string()), None);
+  }
+}
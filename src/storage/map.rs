@@ -0,0 +1,250 @@
+use core::marker::PhantomData;
+use wasmlib::MapKey;
+use wasmlib::ScMutableMap;
+
+use crate::storage::raw;
+use crate::traits::utility::Decode;
+use crate::traits::utility::Encode;
+use crate::traits::utility::TryDecode;
+use crate::types::DecodeError;
+
+/// A typed map of [encoded][Encode] values persisted in contract state.
+///
+/// Values are stored as bytes in a nested state map under a prefix key. The
+/// `M` parameter mirrors the map type of the context the storage was opened
+/// from; only storage opened with mutable state can be modified.
+pub struct StorageMap<K: ?Sized, V, M = ScMutableMap> {
+  obj_id: i32,
+  marker: PhantomData<fn(&K) -> V>,
+  access: PhantomData<M>,
+}
+
+impl<K, V, M> StorageMap<K, V, M>
+where
+  K: MapKey + ?Sized,
+  V: Decode,
+{
+  /// Opens the map stored under `prefix` in contract state.
+  pub fn open<T>(prefix: &T) -> Self
+  where
+    T: MapKey + ?Sized,
+  {
+    Self::from_obj_id(raw::open(prefix))
+  }
+
+  pub(crate) fn from_obj_id(obj_id: i32) -> Self {
+    Self {
+      obj_id,
+      marker: PhantomData,
+      access: PhantomData,
+    }
+  }
+
+  /// Returns the value stored under `key`.
+  pub fn get(&self, key: &K) -> Option<V> {
+    raw::get(self.obj_id, key.get_key_id()).map(|bytes| V::from_bytes(&bytes))
+  }
+
+  /// Returns the value stored under `key`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the stored value could not be decoded.
+  pub fn try_get(&self, key: &K) -> Result<Option<V>, DecodeError>
+  where
+    V: TryDecode,
+  {
+    raw::get(self.obj_id, key.get_key_id())
+      .map(|bytes| V::try_from_bytes(&bytes))
+      .transpose()
+  }
+
+  /// Returns `true` if the map contains a value for `key`.
+  pub fn contains_key(&self, key: &K) -> bool {
+    raw::has(self.obj_id, key.get_key_id())
+  }
+}
+
+impl<K, V> StorageMap<K, V, ScMutableMap>
+where
+  K: MapKey + ?Sized,
+  V: Encode + Decode,
+{
+  /// Stores `value` under `key`, returning the previous value if present.
+  pub fn insert(&self, key: &K, value: V) -> Option<V> {
+    let previous: Option<V> = self.get(key);
+
+    self.insert_raw(key, &value);
+
+    previous
+  }
+
+  /// Removes the value stored under `key`, returning it if present.
+  pub fn remove(&self, key: &K) -> Option<V> {
+    let previous: Option<V> = self.get(key);
+
+    if previous.is_some() {
+      raw::del(self.obj_id, key.get_key_id());
+    }
+
+    previous
+  }
+
+  /// Returns the entry for `key` for in-place manipulation.
+  pub fn entry<'a>(&'a self, key: &'a K) -> Entry<'a, K, V> {
+    match self.get(key) {
+      Some(value) => Entry::Occupied(OccupiedEntry { map: self, key, value }),
+      None => Entry::Vacant(VacantEntry { map: self, key }),
+    }
+  }
+}
+
+// =============================================================================
+// =============================================================================
+
+/// A view into a single entry of a [StorageMap].
+pub enum Entry<'a, K: ?Sized, V> {
+  /// An entry with a stored value.
+  Occupied(OccupiedEntry<'a, K, V>),
+  /// An entry without a stored value.
+  Vacant(VacantEntry<'a, K, V>),
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+where
+  K: MapKey + ?Sized,
+  V: Encode + Decode,
+{
+  /// Returns the key of this entry.
+  pub fn key(&self) -> &'a K {
+    match self {
+      Self::Occupied(entry) => entry.key,
+      Self::Vacant(entry) => entry.key,
+    }
+  }
+
+  /// Stores `default` if the entry is vacant and returns the current value.
+  pub fn or_insert(self, default: V) -> V {
+    self.or_insert_with(|| default)
+  }
+
+  /// Stores the result of `default` if the entry is vacant and returns the
+  /// current value.
+  pub fn or_insert_with<F>(self, default: F) -> V
+  where
+    F: FnOnce() -> V,
+  {
+    match self {
+      Self::Occupied(entry) => entry.into_value(),
+      Self::Vacant(entry) => entry.insert(default()),
+    }
+  }
+
+  /// Stores `V::default()` if the entry is vacant and returns the current
+  /// value.
+  pub fn or_default(self) -> V
+  where
+    V: Default,
+  {
+    self.or_insert_with(V::default)
+  }
+
+  /// Modifies and stores the value of an occupied entry.
+  pub fn and_modify<F>(self, f: F) -> Self
+  where
+    F: FnOnce(&mut V),
+  {
+    match self {
+      Self::Occupied(mut entry) => {
+        f(&mut entry.value);
+        entry.map.insert_raw(entry.key, &entry.value);
+        Self::Occupied(entry)
+      }
+      Self::Vacant(entry) => Self::Vacant(entry),
+    }
+  }
+}
+
+/// An occupied [entry][Entry] of a [StorageMap].
+pub struct OccupiedEntry<'a, K: ?Sized, V> {
+  map: &'a StorageMap<K, V>,
+  key: &'a K,
+  value: V,
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V>
+where
+  K: MapKey + ?Sized,
+  V: Encode + Decode,
+{
+  /// Returns the key of this entry.
+  pub fn key(&self) -> &'a K {
+    self.key
+  }
+
+  /// Returns the stored value.
+  pub fn get(&self) -> &V {
+    &self.value
+  }
+
+  /// Consumes the entry, returning the stored value.
+  pub fn into_value(self) -> V {
+    self.value
+  }
+
+  /// Stores `value`, returning the previous value.
+  pub fn insert(&mut self, value: V) -> V {
+    self.map.insert_raw(self.key, &value);
+
+    core::mem::replace(&mut self.value, value)
+  }
+
+  /// Removes the entry from the map, returning the stored value.
+  pub fn remove(self) -> V {
+    raw::del(self.map.obj_id, self.key.get_key_id());
+
+    self.value
+  }
+}
+
+/// A vacant [entry][Entry] of a [StorageMap].
+pub struct VacantEntry<'a, K: ?Sized, V> {
+  map: &'a StorageMap<K, V>,
+  key: &'a K,
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V>
+where
+  K: MapKey + ?Sized,
+  V: Encode + Decode,
+{
+  /// Returns the key of this entry.
+  pub fn key(&self) -> &'a K {
+    self.key
+  }
+
+  /// Stores `value` in the entry and returns it.
+  pub fn insert(self, value: V) -> V {
+    self.map.insert_raw(self.key, &value);
+
+    value
+  }
+}
+
+// =============================================================================
+// =============================================================================
+
+impl<K, V> StorageMap<K, V, ScMutableMap>
+where
+  K: MapKey + ?Sized,
+  V: Encode,
+{
+  fn insert_raw(&self, key: &K, value: &V) {
+    raw::set(self.obj_id, key.get_key_id(), &value.to_bytes());
+  }
+
+  /// Removes the value stored under `key` without decoding it.
+  pub(crate) fn delete(&self, key: &K) {
+    raw::del(self.obj_id, key.get_key_id());
+  }
+}
@@ -0,0 +1,14 @@
+//! Typed collections persisted in smart contract state.
+
+mod array;
+mod iterable;
+mod map;
+mod raw;
+mod state;
+mod vec;
+
+pub(crate) use self::array::*;
+pub use self::iterable::*;
+pub use self::map::*;
+pub use self::state::*;
+pub use self::vec::*;
@@ -0,0 +1,47 @@
+use core::ptr;
+use wasmlib::host;
+use wasmlib::Key32;
+use wasmlib::MapKey;
+use wasmlib::KEY_STATE;
+
+/// Returns the object id of the contract state map.
+pub(crate) fn state() -> i32 {
+  host::get_object_id(host::OBJ_ID_ROOT, KEY_STATE, host::TYPE_MAP)
+}
+
+/// Returns the object id of the state map stored under `prefix`.
+pub(crate) fn open<T: MapKey + ?Sized>(prefix: &T) -> i32 {
+  object(state(), prefix, host::TYPE_MAP)
+}
+
+/// Returns the object id of the child object stored under `key`.
+pub(crate) fn object<T: MapKey + ?Sized>(obj_id: i32, key: &T, type_id: i32) -> i32 {
+  host::get_object_id(obj_id, key.get_key_id(), type_id)
+}
+
+/// Returns the bytes stored under `key` if present.
+pub(crate) fn get(obj_id: i32, key: Key32) -> Option<Vec<u8>> {
+  if host::exists(obj_id, key, host::TYPE_BYTES) {
+    Some(host::get_bytes(obj_id, key, host::TYPE_BYTES))
+  } else {
+    None
+  }
+}
+
+/// Returns `true` if a value is stored under `key`.
+pub(crate) fn has(obj_id: i32, key: Key32) -> bool {
+  host::exists(obj_id, key, host::TYPE_BYTES)
+}
+
+/// Stores `value` under `key`.
+pub(crate) fn set(obj_id: i32, key: Key32, value: &[u8]) {
+  host::set_bytes(obj_id, key, host::TYPE_BYTES, value);
+}
+
+/// Removes the value stored under `key`.
+pub(crate) fn del(obj_id: i32, key: Key32) {
+  // A negative size instructs the host to delete the key
+  unsafe {
+    host::hostSetBytes(obj_id, key.0, host::TYPE_BYTES, ptr::null(), -1);
+  }
+}
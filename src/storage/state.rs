@@ -0,0 +1,182 @@
+use wasmlib::host;
+use wasmlib::MapKey;
+use wasmlib::ScAddress;
+use wasmlib::ScAgentId;
+use wasmlib::ScChainId;
+use wasmlib::ScColor;
+use wasmlib::ScHash;
+use wasmlib::ScHname;
+use wasmlib::ScImmutableAddress;
+use wasmlib::ScImmutableAgentId;
+use wasmlib::ScImmutableBytes;
+use wasmlib::ScImmutableChainId;
+use wasmlib::ScImmutableColor;
+use wasmlib::ScImmutableHash;
+use wasmlib::ScImmutableHname;
+use wasmlib::ScImmutableInt64;
+use wasmlib::ScImmutableMap;
+use wasmlib::ScImmutableRequestId;
+use wasmlib::ScImmutableString;
+use wasmlib::ScMutableAddress;
+use wasmlib::ScMutableAgentId;
+use wasmlib::ScMutableBytes;
+use wasmlib::ScMutableChainId;
+use wasmlib::ScMutableColor;
+use wasmlib::ScMutableHash;
+use wasmlib::ScMutableHname;
+use wasmlib::ScMutableInt64;
+use wasmlib::ScMutableMap;
+use wasmlib::ScMutableRequestId;
+use wasmlib::ScMutableString;
+use wasmlib::ScRequestId;
+
+use crate::storage::raw;
+use crate::storage::IterableMap;
+use crate::storage::StorageMap;
+use crate::storage::StorageVec;
+use crate::traits::utility::Decode;
+use crate::types::ScBytes;
+use crate::types::ScImmutableI256;
+use crate::types::ScImmutableU256;
+use crate::types::ScInt64;
+use crate::types::ScMutableI256;
+use crate::types::ScMutableU256;
+use crate::types::ScString;
+use crate::types::I256;
+use crate::types::U256;
+
+/// A struct mapped to the keys of a state map.
+///
+/// This trait can be derived with `#[derive(State)]`, which generates an
+/// `Immutable{Name}` accessor for views and a `Mutable{Name}` accessor for
+/// funcs. Each field is stored under its own key:
+///
+/// * WasmLib values map to value proxies.
+/// * [StorageMap], [IterableMap] and [StorageVec] map to collections stored
+///   in a nested map.
+/// * Other `State` structs map to their accessor over a nested map.
+///
+/// The accessor for the contract state is returned by
+/// [ContextExt::get_state_struct][crate::traits::extension::ContextExt::get_state_struct].
+pub trait State<M>: StateField<M> {
+  #[doc(hidden)]
+  fn open_state(obj_id: i32) -> Self::Proxy;
+}
+
+/// A value that can be stored in a field of a [State] struct.
+pub trait StateField<M> {
+  /// The accessor for the field.
+  type Proxy;
+
+  #[doc(hidden)]
+  fn open_field(obj_id: i32, key: &str) -> Self::Proxy;
+}
+
+/// Returns the object id of the nested map stored under `key`.
+#[doc(hidden)]
+pub fn open_map(obj_id: i32, key: &str) -> i32 {
+  raw::object(obj_id, key, host::TYPE_MAP)
+}
+
+/// Opens the accessor for `T` over the contract state.
+pub(crate) fn open_state<T, M>() -> T::Proxy
+where
+  T: State<M>,
+{
+  T::open_state(raw::state())
+}
+
+macro_rules! impl_StateField {
+  (@int, $($ident:ident),+ $(,)*) => {
+    paste::paste! {
+      $(
+        impl StateField<ScImmutableMap> for $ident {
+          type Proxy = [<ScImmutable $ident>];
+
+          fn open_field(obj_id: i32, key: &str) -> Self::Proxy {
+            [<ScImmutable $ident>]::new(obj_id, key.get_key_id())
+          }
+        }
+
+        impl StateField<ScMutableMap> for $ident {
+          type Proxy = [<ScMutable $ident>];
+
+          fn open_field(obj_id: i32, key: &str) -> Self::Proxy {
+            [<ScMutable $ident>]::new(obj_id, key.get_key_id())
+          }
+        }
+      )+
+    }
+  };
+  ($($ident:ident),+ $(,)*) => {
+    paste::paste! {
+      $(
+        impl StateField<ScImmutableMap> for [<Sc $ident>] {
+          type Proxy = [<ScImmutable $ident>];
+
+          fn open_field(obj_id: i32, key: &str) -> Self::Proxy {
+            [<ScImmutable $ident>]::new(obj_id, key.get_key_id())
+          }
+        }
+
+        impl StateField<ScMutableMap> for [<Sc $ident>] {
+          type Proxy = [<ScMutable $ident>];
+
+          fn open_field(obj_id: i32, key: &str) -> Self::Proxy {
+            [<ScMutable $ident>]::new(obj_id, key.get_key_id())
+          }
+        }
+      )+
+    }
+  };
+}
+
+impl_StateField! {
+  Address,
+  AgentId,
+  Bytes,
+  ChainId,
+  Color,
+  Hash,
+  Hname,
+  Int64,
+  RequestId,
+  String,
+}
+
+impl_StateField!(@int, U256, I256);
+
+impl<K, V, M> StateField<M> for StorageMap<K, V>
+where
+  K: MapKey + ?Sized,
+  V: Decode,
+{
+  type Proxy = StorageMap<K, V, M>;
+
+  fn open_field(obj_id: i32, key: &str) -> Self::Proxy {
+    StorageMap::from_obj_id(open_map(obj_id, key))
+  }
+}
+
+impl<K, V, M> StateField<M> for IterableMap<K, V>
+where
+  K: MapKey + Decode,
+  V: Decode,
+{
+  type Proxy = IterableMap<K, V, M>;
+
+  fn open_field(obj_id: i32, key: &str) -> Self::Proxy {
+    IterableMap::from_obj_id(open_map(obj_id, key))
+  }
+}
+
+impl<T, M> StateField<M> for StorageVec<T>
+where
+  T: Decode,
+{
+  type Proxy = StorageVec<T, M>;
+
+  fn open_field(obj_id: i32, key: &str) -> Self::Proxy {
+    StorageVec::from_obj_id(open_map(obj_id, key))
+  }
+}
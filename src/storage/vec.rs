@@ -0,0 +1,361 @@
+use core::marker::PhantomData;
+use core::ops::Bound;
+use core::ops::RangeBounds;
+use wasmlib::MapKey;
+use wasmlib::ScImmutableMap;
+use wasmlib::ScMutableMap;
+
+use crate::panic::fail;
+use crate::storage::raw;
+use crate::storage::RawArray;
+use crate::traits::core::Array;
+use crate::traits::core::ArrayMut;
+use crate::traits::utility::Decode;
+use crate::traits::utility::Encode;
+use crate::types::ScBytes;
+
+/// A typed vector of [encoded][Encode] values persisted in contract state.
+///
+/// Values are stored as bytes in an array under a prefix key along with the
+/// number of values in the vector.
+pub struct StorageVec<T, M = ScMutableMap> {
+  items: RawArray<M>,
+  marker: PhantomData<fn() -> T>,
+}
+
+impl<T, M> StorageVec<T, M>
+where
+  T: Decode,
+{
+  /// Opens the vector stored under `prefix` in contract state.
+  pub fn open<P>(prefix: &P) -> Self
+  where
+    P: MapKey + ?Sized,
+  {
+    Self::from_obj_id(raw::open(prefix))
+  }
+
+  pub(crate) fn from_obj_id(obj_id: i32) -> Self {
+    Self {
+      items: RawArray::open(obj_id, "items", "length"),
+      marker: PhantomData,
+    }
+  }
+}
+
+impl<T> StorageVec<T, ScMutableMap>
+where
+  T: Encode + Decode,
+{
+  /// Appends a value to the back of the vector.
+  pub fn push(&self, value: T) {
+    self.items.push(value.to_bytes());
+  }
+
+  /// Removes the last value of the vector and returns it.
+  pub fn pop(&self) -> Option<T> {
+    self.items.pop().map(|bytes| T::from_bytes(&bytes))
+  }
+
+  /// Inserts a value at `index`, shifting all values after it to the right.
+  ///
+  /// Note: panics if `index > len`.
+  pub fn insert(&self, index: usize, value: T) {
+    let size: usize = self.items.len();
+
+    if index > size {
+      fail(&format!(
+        "insertion index (is {}) should be <= len (is {})",
+        index, size
+      ));
+    }
+
+    if index == size {
+      return self.push(value);
+    }
+
+    self.items.push(self.items.get(size - 1));
+
+    for offset in (index + 1..size).rev() {
+      self.items.proxy(offset).set_value(&self.items.get(offset - 1));
+    }
+
+    self.items.proxy(index).set_value(&value.to_bytes());
+  }
+
+  /// Removes the value at `index` and returns it, replacing it with the last
+  /// value of the vector.
+  ///
+  /// Note: panics if `index` is out of bounds.
+  pub fn swap_remove(&self, index: usize) -> T {
+    self.check(index);
+
+    let last: ScBytes = self.items.pop().unwrap_or_default();
+
+    if index == self.items.len() {
+      return T::from_bytes(&last);
+    }
+
+    let value: ScBytes = self.items.get(index);
+
+    self.items.proxy(index).set_value(&last);
+
+    T::from_bytes(&value)
+  }
+
+  /// Shortens the vector to `size` values, dropping the rest.
+  pub fn truncate(&self, size: usize) {
+    self.items.truncate(size);
+  }
+
+  /// Replaces the value at `index`.
+  ///
+  /// Note: panics if `index` is out of bounds.
+  pub fn set(&self, index: usize, value: T) {
+    self.check(index);
+    self.items.proxy(index).set_value(&value.to_bytes());
+  }
+
+  /// Removes all values from the vector.
+  pub fn clear(&self) {
+    self.items.erase();
+  }
+
+  fn check(&self, index: usize) {
+    let size: usize = self.items.len();
+
+    if index >= size {
+      fail(&format!(
+        "index out of bounds: the len is {} but the index is {}",
+        size, index
+      ));
+    }
+  }
+}
+
+macro_rules! impl_StorageVec {
+  ($map:ident) => {
+    impl<T> StorageVec<T, $map>
+    where
+      T: Decode,
+    {
+      /// Returns the number of values in the vector.
+      pub fn len(&self) -> usize {
+        self.items.len()
+      }
+
+      /// Returns `true` if the vector contains no values.
+      pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+      }
+
+      /// Returns the value at `index` if it is in bounds.
+      pub fn get(&self, index: usize) -> Option<T> {
+        if index < self.items.len() {
+          Some(T::from_bytes(&self.items.get(index)))
+        } else {
+          None
+        }
+      }
+
+      /// Returns the first value of the vector.
+      pub fn first(&self) -> Option<T> {
+        self.get(0)
+      }
+
+      /// Returns the last value of the vector.
+      pub fn last(&self) -> Option<T> {
+        self.items.len().checked_sub(1).and_then(|index| self.get(index))
+      }
+
+      /// Returns an iterator over the vector.
+      pub fn iter(&self) -> Items<'_, T, $map> {
+        self.range(..)
+      }
+
+      /// Returns an iterator over the given `range` of the vector.
+      ///
+      /// Note: panics if the range is out of bounds.
+      pub fn range<R>(&self, range: R) -> Items<'_, T, $map>
+      where
+        R: RangeBounds<usize>,
+      {
+        let size: usize = self.items.len();
+
+        let front: usize = match range.start_bound() {
+          Bound::Included(start) => *start,
+          Bound::Excluded(start) => start.saturating_add(1),
+          Bound::Unbounded => 0,
+        };
+
+        let back: usize = match range.end_bound() {
+          Bound::Included(end) => end.saturating_add(1),
+          Bound::Excluded(end) => *end,
+          Bound::Unbounded => size,
+        };
+
+        if front > back || back > size {
+          fail(&format!("range {}..{} out of bounds for length {}", front, back, size));
+        }
+
+        Items {
+          items: &self.items,
+          front,
+          back,
+          marker: PhantomData,
+        }
+      }
+    }
+
+    impl<'a, T> Iterator for Items<'a, T, $map>
+    where
+      T: Decode,
+    {
+      type Item = T;
+
+      fn next(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+          self.front += 1;
+          Some(T::from_bytes(&self.items.get(self.front - 1)))
+        } else {
+          None
+        }
+      }
+
+      fn size_hint(&self) -> (usize, Option<usize>) {
+        let size: usize = self.back - self.front;
+
+        (size, Some(size))
+      }
+    }
+
+    impl<'a, T> DoubleEndedIterator for Items<'a, T, $map>
+    where
+      T: Decode,
+    {
+      fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+          self.back -= 1;
+          Some(T::from_bytes(&self.items.get(self.back)))
+        } else {
+          None
+        }
+      }
+    }
+
+    impl<'a, T> ExactSizeIterator for Items<'a, T, $map> where T: Decode {}
+  };
+}
+
+impl_StorageVec!(ScImmutableMap);
+impl_StorageVec!(ScMutableMap);
+
+// =============================================================================
+// =============================================================================
+
+/// An iterator over a range of values in a [StorageVec].
+pub struct Items<'a, T, M> {
+  items: &'a RawArray<M>,
+  front: usize,
+  back: usize,
+  marker: PhantomData<fn() -> T>,
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+  use super::*;
+  use crate::testing::MockHost;
+
+  fn values(vec: &StorageVec<i64, ScImmutableMap>) -> Vec<i64> {
+    vec.iter().collect()
+  }
+
+  #[test]
+  fn test_insert() {
+    let _host: MockHost = MockHost::new("test");
+    let vec: StorageVec<i64> = StorageVec::open("log");
+    let reader: StorageVec<i64, ScImmutableMap> = StorageVec::open("log");
+
+    vec.insert(0, 1);
+    vec.insert(1, 3);
+    vec.insert(1, 2);
+    vec.insert(0, 0);
+
+    assert_eq!(values(&reader), [0, 1, 2, 3]);
+  }
+
+  #[test]
+  #[should_panic(expected = "insertion index (is 2) should be <= len (is 1)")]
+  fn test_insert_out_of_bounds() {
+    let _host: MockHost = MockHost::new("test");
+    let vec: StorageVec<i64> = StorageVec::open("log");
+
+    vec.push(0);
+    vec.insert(2, 1);
+  }
+
+  #[test]
+  fn test_swap_remove() {
+    let _host: MockHost = MockHost::new("test");
+    let vec: StorageVec<i64> = StorageVec::open("log");
+    let reader: StorageVec<i64, ScImmutableMap> = StorageVec::open("log");
+
+    for value in 0..5 {
+      vec.push(value);
+    }
+
+    assert_eq!(vec.swap_remove(1), 1);
+    assert_eq!(values(&reader), [0, 4, 2, 3]);
+
+    assert_eq!(vec.swap_remove(3), 3);
+    assert_eq!(values(&reader), [0, 4, 2]);
+
+    vec.push(5);
+
+    assert_eq!(values(&reader), [0, 4, 2, 5]);
+  }
+
+  #[test]
+  #[should_panic(expected = "index out of bounds: the len is 0 but the index is 0")]
+  fn test_swap_remove_out_of_bounds() {
+    let _host: MockHost = MockHost::new("test");
+    let vec: StorageVec<i64> = StorageVec::open("log");
+
+    vec.swap_remove(0);
+  }
+
+  #[test]
+  fn test_range() {
+    let _host: MockHost = MockHost::new("test");
+    let vec: StorageVec<i64> = StorageVec::open("log");
+
+    for value in 0..5 {
+      vec.push(value);
+    }
+
+    assert_eq!(vec.range(..).collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+    assert_eq!(vec.range(1..3).collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(vec.range(1..=3).rev().collect::<Vec<_>>(), [3, 2, 1]);
+    assert_eq!(vec.range(3..).len(), 2);
+    assert_eq!(vec.range(5..).next(), None);
+
+    let mut items: Items<'_, i64, ScMutableMap> = vec.range(..4);
+
+    assert_eq!(items.next(), Some(0));
+    assert_eq!(items.next_back(), Some(3));
+    assert_eq!(items.len(), 2);
+  }
+
+  #[test]
+  #[should_panic(expected = "range 2..6 out of bounds for length 5")]
+  fn test_range_out_of_bounds() {
+    let _host: MockHost = MockHost::new("test");
+    let vec: StorageVec<i64> = StorageVec::open("log");
+
+    for value in 0..5 {
+      vec.push(value);
+    }
+
+    vec.range(2..6);
+  }
+}
@@ -0,0 +1,888 @@
+//! Simulated core contracts of the mock chain.
+//!
+//! These implement the subset of the `root`, `accounts`, `blob` and
+//! `eventlog` contracts wrapped by [contracts::core][crate::contracts::core]
+//! directly on top of the host object store.
+
+use wasmlib::host::OBJ_ID_ROOT;
+use wasmlib::host::TYPE_ARRAY;
+use wasmlib::host::TYPE_BYTES;
+use wasmlib::host::TYPE_MAP;
+use wasmlib::ScAgentId;
+use wasmlib::ScColor;
+use wasmlib::ScFuncContext;
+use wasmlib::ScHash;
+use wasmlib::ScHname;
+use wasmlib::ScViewContext;
+use wasmlib::CORE_ACCOUNTS;
+use wasmlib::CORE_ACCOUNTS_FUNC_DEPOSIT;
+use wasmlib::CORE_ACCOUNTS_FUNC_WITHDRAW_TO_ADDRESS;
+use wasmlib::CORE_ACCOUNTS_PARAM_AGENT_ID;
+use wasmlib::CORE_ACCOUNTS_VIEW_ACCOUNTS;
+use wasmlib::CORE_ACCOUNTS_VIEW_BALANCE;
+use wasmlib::CORE_ACCOUNTS_VIEW_TOTAL_ASSETS;
+use wasmlib::CORE_BLOB;
+use wasmlib::CORE_BLOB_FUNC_STORE_BLOB;
+use wasmlib::CORE_BLOB_PARAM_FIELD;
+use wasmlib::CORE_BLOB_PARAM_HASH;
+use wasmlib::CORE_BLOB_VIEW_GET_BLOB_FIELD;
+use wasmlib::CORE_BLOB_VIEW_GET_BLOB_INFO;
+use wasmlib::CORE_BLOB_VIEW_LIST_BLOBS;
+use wasmlib::CORE_EVENTLOG;
+use wasmlib::CORE_EVENTLOG_PARAM_CONTRACT_HNAME;
+use wasmlib::CORE_EVENTLOG_PARAM_FROM_TS;
+use wasmlib::CORE_EVENTLOG_PARAM_MAX_LAST_RECORDS;
+use wasmlib::CORE_EVENTLOG_PARAM_TO_TS;
+use wasmlib::CORE_EVENTLOG_VIEW_GET_NUM_RECORDS;
+use wasmlib::CORE_EVENTLOG_VIEW_GET_RECORDS;
+use wasmlib::CORE_ROOT;
+use wasmlib::CORE_ROOT_FUNC_CLAIM_CHAIN_OWNERSHIP;
+use wasmlib::CORE_ROOT_FUNC_DELEGATE_CHAIN_OWNERSHIP;
+use wasmlib::CORE_ROOT_FUNC_DEPLOY_CONTRACT;
+use wasmlib::CORE_ROOT_FUNC_GRANT_DEPLOY_PERMISSION;
+use wasmlib::CORE_ROOT_FUNC_REVOKE_DEPLOY_PERMISSION;
+use wasmlib::CORE_ROOT_FUNC_SET_CONTRACT_FEE;
+use wasmlib::CORE_ROOT_FUNC_SET_DEFAULT_FEE;
+use wasmlib::CORE_ROOT_PARAM_CHAIN_OWNER;
+use wasmlib::CORE_ROOT_PARAM_DEPLOYER;
+use wasmlib::CORE_ROOT_PARAM_DESCRIPTION;
+use wasmlib::CORE_ROOT_PARAM_HNAME;
+use wasmlib::CORE_ROOT_PARAM_NAME;
+use wasmlib::CORE_ROOT_PARAM_OWNER_FEE;
+use wasmlib::CORE_ROOT_PARAM_PROGRAM_HASH;
+use wasmlib::CORE_ROOT_PARAM_VALIDATOR_FEE;
+use wasmlib::CORE_ROOT_VIEW_FIND_CONTRACT;
+use wasmlib::CORE_ROOT_VIEW_GET_CHAIN_INFO;
+use wasmlib::CORE_ROOT_VIEW_GET_FEE_INFO;
+use wasmlib::KEY_CALLER;
+use wasmlib::KEY_INCOMING;
+use wasmlib::KEY_PARAMS;
+use wasmlib::KEY_RESULTS;
+use wasmlib::KEY_STATE;
+
+use crate::consts::*;
+use crate::contracts::core::Accounts;
+use crate::contracts::core::Blob;
+use crate::contracts::core::Contract;
+use crate::contracts::core::ContractRecord;
+use crate::contracts::core::EventLog;
+use crate::contracts::core::Root;
+use crate::testing::host;
+use crate::testing::host::Handler;
+use crate::testing::host::Host;
+use crate::testing::host::KeyName;
+use crate::testing::host::RawTransfer;
+use crate::traits::utility::Encode;
+use crate::traits::utility::TryDecode;
+use crate::types::DecodeError;
+use crate::types::Decoder;
+
+/// The VM type recorded for programs uploaded to the mock chain.
+pub(crate) const VM_TYPE: &str = "mockvm";
+
+// Root state keys that are not part of the chain info
+const ROOT_VAR_DEPLOY_PERMISSIONS: &str = "dp";
+const ROOT_VAR_PENDING_OWNER: &str = "po";
+
+// Blob state keys
+const BLOB_VAR_FIELDS: &str = "f";
+const BLOB_VAR_SIZES: &str = "s";
+
+// Default maximum number of event records returned
+const EVENTLOG_MAX_RECORDS: i64 = 50;
+
+type Entry<T> = (ScHname, ScHname, fn(&T));
+
+/// Registers the core contracts and initializes the chain state.
+pub(crate) fn install(host: &mut Host, owner: &[u8]) {
+  let funcs: [Entry<ScFuncContext>; 10] = [
+    (CORE_ROOT, CORE_ROOT_FUNC_DEPLOY_CONTRACT, root_deploy_contract),
+    (CORE_ROOT, CORE_ROOT_FUNC_SET_DEFAULT_FEE, root_set_default_fee),
+    (CORE_ROOT, CORE_ROOT_FUNC_SET_CONTRACT_FEE, root_set_contract_fee),
+    (
+      CORE_ROOT,
+      CORE_ROOT_FUNC_DELEGATE_CHAIN_OWNERSHIP,
+      root_delegate_ownership,
+    ),
+    (CORE_ROOT, CORE_ROOT_FUNC_CLAIM_CHAIN_OWNERSHIP, root_claim_ownership),
+    (CORE_ROOT, CORE_ROOT_FUNC_GRANT_DEPLOY_PERMISSION, root_grant_permission),
+    (
+      CORE_ROOT,
+      CORE_ROOT_FUNC_REVOKE_DEPLOY_PERMISSION,
+      root_revoke_permission,
+    ),
+    (CORE_ACCOUNTS, CORE_ACCOUNTS_FUNC_DEPOSIT, accounts_deposit),
+    (CORE_ACCOUNTS, CORE_ACCOUNTS_FUNC_WITHDRAW_TO_ADDRESS, accounts_withdraw),
+    (CORE_BLOB, CORE_BLOB_FUNC_STORE_BLOB, blob_store),
+  ];
+
+  let views: [Entry<ScViewContext>; 11] = [
+    (CORE_ROOT, CORE_ROOT_VIEW_GET_CHAIN_INFO, root_get_chain_info),
+    (CORE_ROOT, CORE_ROOT_VIEW_FIND_CONTRACT, root_find_contract),
+    (CORE_ROOT, CORE_ROOT_VIEW_GET_FEE_INFO, root_get_fee_info),
+    (CORE_ACCOUNTS, CORE_ACCOUNTS_VIEW_BALANCE, accounts_balance),
+    (CORE_ACCOUNTS, CORE_ACCOUNTS_VIEW_TOTAL_ASSETS, accounts_total_assets),
+    (CORE_ACCOUNTS, CORE_ACCOUNTS_VIEW_ACCOUNTS, accounts_accounts),
+    (CORE_BLOB, CORE_BLOB_VIEW_GET_BLOB_FIELD, blob_get_field),
+    (CORE_BLOB, CORE_BLOB_VIEW_GET_BLOB_INFO, blob_get_info),
+    (CORE_BLOB, CORE_BLOB_VIEW_LIST_BLOBS, blob_list),
+    (
+      CORE_EVENTLOG,
+      CORE_EVENTLOG_VIEW_GET_NUM_RECORDS,
+      eventlog_get_num_records,
+    ),
+    (CORE_EVENTLOG, CORE_EVENTLOG_VIEW_GET_RECORDS, eventlog_get_records),
+  ];
+
+  for (contract, function, func) in funcs.iter() {
+    host.handlers.insert((contract.0, function.0), Handler::Func(*func));
+  }
+
+  for (contract, function, view) in views.iter() {
+    host.handlers.insert((contract.0, function.0), Handler::View(*view));
+  }
+
+  let mut scope: Scope<'_> = Scope::contract(host, CORE_ROOT.0);
+  let chain: Vec<u8> = scope.host.agent_id(0)[..33].to_vec();
+
+  scope.set(scope.state, CORE_ROOT_VAR_CHAIN_ID, chain);
+  scope.set(scope.state, CORE_ROOT_VAR_CHAIN_OWNER_ID, owner.to_vec());
+  scope.set(scope.state, CORE_ROOT_VAR_DESCRIPTION, b"Mock Chain".to_vec());
+  scope.set(scope.state, CORE_ROOT_VAR_FEE_COLOR, ScColor::IOTA.to_bytes().to_vec());
+  scope.set(
+    scope.state,
+    CORE_ROOT_VAR_DEFAULT_OWNER_FEE,
+    0_i64.to_le_bytes().to_vec(),
+  );
+  scope.set(
+    scope.state,
+    CORE_ROOT_VAR_DEFAULT_VALIDATOR_FEE,
+    0_i64.to_le_bytes().to_vec(),
+  );
+
+  for (name, description) in [
+    (Root::NAME, Root::DESC),
+    (Accounts::NAME, Accounts::DESC),
+    (Blob::NAME, Blob::DESC),
+    (EventLog::NAME, EventLog::DESC),
+  ]
+  .iter()
+  {
+    let record: ContractRecord = ContractRecord::new(
+      ScHash::from_bytes(&[0; 32]),
+      description.to_string(),
+      name.to_string(),
+      ScAgentId::from_bytes(owner),
+    );
+
+    scope.set_record(host::hname(name.as_bytes()), &record);
+  }
+}
+
+/// Handles a `KEY_DEPLOY` request made by the executing contract.
+pub(crate) fn deploy(request: &[u8]) {
+  let decoded: Result<(ScHash, String, String, i64), DecodeError> = (|| {
+    let mut decoder: Decoder<'_> = Decoder::new(request);
+
+    Ok((
+      decoder.try_hash()?,
+      decoder.try_string()?,
+      decoder.try_string()?,
+      decoder.try_int64()?,
+    ))
+  })();
+
+  let (program, name, description, params): (ScHash, String, String, i64) = match decoded {
+    Ok(decoded) => decoded,
+    Err(error) => return host::with(|host| host.fail(error.to_string())),
+  };
+
+  let prepared: (Vec<u8>, i32, i32) = host::with(|host| {
+    let params: i32 = if params == 0 {
+      host.store.create(TYPE_MAP, false)
+    } else {
+      params as i32
+    };
+
+    let mut scope: Scope<'_> = Scope::contract(host, CORE_ROOT.0);
+
+    scope.set(params, CORE_ROOT_PARAM_PROGRAM_HASH, program.to_bytes().to_vec());
+    scope.set(params, CORE_ROOT_PARAM_NAME, name.into_bytes());
+    scope.set(params, CORE_ROOT_PARAM_DESCRIPTION, description.into_bytes());
+
+    (host.agent_id(host.contract()), params, host.incoming(0))
+  });
+
+  let (caller, params, incoming): (Vec<u8>, i32, i32) = prepared;
+
+  host::dispatch(CORE_ROOT.0, CORE_ROOT_FUNC_DEPLOY_CONTRACT.0, caller, params, incoming);
+}
+
+/// Returns the fee color and the total fee charged for requests to `contract`.
+pub(crate) fn fees(host: &mut Host, contract: u32) -> (Vec<u8>, i64) {
+  let mut scope: Scope<'_> = Scope::contract(host, CORE_ROOT.0);
+  let (owner_fee, validator_fee): (i64, i64) = scope.fees(contract);
+
+  (
+    scope.get_or_default(scope.state, CORE_ROOT_VAR_FEE_COLOR),
+    owner_fee + validator_fee,
+  )
+}
+
+/// Returns the agent id of the chain owner.
+pub(crate) fn owner(host: &mut Host) -> Vec<u8> {
+  let mut scope: Scope<'_> = Scope::contract(host, CORE_ROOT.0);
+
+  scope.get_or_default(scope.state, CORE_ROOT_VAR_CHAIN_OWNER_ID)
+}
+
+/// Replaces the chain owner recorded in the `root` contract.
+pub(crate) fn set_owner(host: &mut Host, owner: &[u8]) {
+  let mut scope: Scope<'_> = Scope::contract(host, CORE_ROOT.0);
+
+  scope.set(scope.state, CORE_ROOT_VAR_CHAIN_OWNER_ID, owner.to_vec());
+}
+
+// =============================================================================
+// =============================================================================
+
+/// The objects available to a core contract while handling a request.
+struct Scope<'a> {
+  host: &'a mut Host,
+  params: i32,
+  results: i32,
+  state: i32,
+}
+
+impl<'a> Scope<'a> {
+  /// Creates a scope for the request that is currently executing.
+  fn current(host: &'a mut Host) -> Self {
+    Self {
+      params: host.store.child(OBJ_ID_ROOT, KEY_PARAMS.0).unwrap_or_default(),
+      results: host.store.child(OBJ_ID_ROOT, KEY_RESULTS.0).unwrap_or_default(),
+      state: host.store.child(OBJ_ID_ROOT, KEY_STATE.0).unwrap_or_default(),
+      host,
+    }
+  }
+
+  /// Creates a scope for accessing the state of `contract` outside a request.
+  fn contract(host: &'a mut Host, contract: u32) -> Self {
+    Self {
+      params: 0,
+      results: 0,
+      state: host.state(contract),
+      host,
+    }
+  }
+
+  fn key<K>(&mut self, key: K) -> i32
+  where
+    K: Into<KeyName>,
+  {
+    self.host.store.key_id(key)
+  }
+
+  fn get<K>(&mut self, obj_id: i32, key: K) -> Option<Vec<u8>>
+  where
+    K: Into<KeyName>,
+  {
+    let key: i32 = self.key(key);
+
+    self.host.store.get(obj_id, key).map(<[u8]>::to_vec)
+  }
+
+  fn get_or_default<K>(&mut self, obj_id: i32, key: K) -> Vec<u8>
+  where
+    K: Into<KeyName>,
+  {
+    self.get(obj_id, key).unwrap_or_default()
+  }
+
+  fn set<K>(&mut self, obj_id: i32, key: K, value: Vec<u8>)
+  where
+    K: Into<KeyName>,
+  {
+    let key: i32 = self.key(key);
+
+    self.host.store.set(obj_id, key, value);
+  }
+
+  fn del<K>(&mut self, obj_id: i32, key: K)
+  where
+    K: Into<KeyName>,
+  {
+    let key: i32 = self.key(key);
+
+    self.host.store.del(obj_id, key);
+  }
+
+  fn map<K>(&mut self, obj_id: i32, key: K) -> i32
+  where
+    K: Into<KeyName>,
+  {
+    let key: i32 = self.key(key);
+
+    self.host.store.object(obj_id, key, TYPE_MAP)
+  }
+
+  fn param(&mut self, name: &str) -> Result<Vec<u8>, String> {
+    self
+      .get(self.params, name)
+      .ok_or_else(|| format!("missing parameter: {}", name))
+  }
+
+  fn param_hname(&mut self, name: &str) -> Result<u32, String> {
+    Ok(host::int64(&self.param(name)?) as u32)
+  }
+
+  fn param_agent(&mut self, name: &str) -> Result<Option<Vec<u8>>, String> {
+    match self.get(self.params, name) {
+      Some(agent) if agent.len() == 37 => Ok(Some(agent)),
+      Some(_) => Err(format!("invalid parameter: {}", name)),
+      None => Ok(None),
+    }
+  }
+
+  fn caller(&self) -> Vec<u8> {
+    self.host.get_root(KEY_CALLER)
+  }
+
+  fn require_owner(&mut self) -> Result<(), String> {
+    if self.get(self.state, CORE_ROOT_VAR_CHAIN_OWNER_ID) == Some(self.caller()) {
+      Ok(())
+    } else {
+      Err("unauthorized: caller is not the chain owner".to_string())
+    }
+  }
+
+  fn record(&mut self, contract: u32) -> Result<ContractRecord, String> {
+    let registry: i32 = self.map(self.state, CORE_ROOT_VAR_CONTRACT_REGISTRY);
+
+    match self.get(registry, &contract.to_le_bytes()[..]) {
+      Some(bytes) => ContractRecord::try_from_bytes(&bytes).map_err(|error| error.to_string()),
+      None => Err(format!("contract not found: {:08x}", contract)),
+    }
+  }
+
+  fn set_record(&mut self, contract: u32, record: &ContractRecord) {
+    let registry: i32 = self.map(self.state, CORE_ROOT_VAR_CONTRACT_REGISTRY);
+
+    self.set(registry, &contract.to_le_bytes()[..], record.to_bytes());
+  }
+
+  fn fees(&mut self, contract: u32) -> (i64, i64) {
+    let record: Option<ContractRecord> = self.record(contract).ok();
+    let owner_fee: i64 = host::int64(&self.get_or_default(self.state, CORE_ROOT_VAR_DEFAULT_OWNER_FEE));
+    let validator_fee: i64 = host::int64(&self.get_or_default(self.state, CORE_ROOT_VAR_DEFAULT_VALIDATOR_FEE));
+
+    match record {
+      Some(record) => (
+        nonzero(record.owner_fee()).unwrap_or(owner_fee),
+        nonzero(record.validator_fee()).unwrap_or(validator_fee),
+      ),
+      None => (owner_fee, validator_fee),
+    }
+  }
+}
+
+/// Runs `f` with the scope of the current request, failing it on error.
+fn run<T>(f: impl FnOnce(&mut Scope<'_>) -> Result<T, String>) -> Option<T> {
+  host::with(|host| match f(&mut Scope::current(host)) {
+    Ok(value) => Some(value),
+    Err(error) => {
+      host.fail(error);
+      None
+    }
+  })
+}
+
+fn nonzero(value: i64) -> Option<i64> {
+  if value == 0 {
+    None
+  } else {
+    Some(value)
+  }
+}
+
+// =============================================================================
+// =============================================================================
+
+fn root_deploy_contract(_: &ScFuncContext) {
+  let deployed: Option<(u32, i32)> = run(|scope| {
+    let caller: Vec<u8> = scope.caller();
+    let permissions: i32 = scope.map(scope.state, ROOT_VAR_DEPLOY_PERMISSIONS);
+
+    if scope.require_owner().is_err() && scope.get(permissions, caller.as_slice()).is_none() {
+      return Err("unauthorized: deploy permission required".to_string());
+    }
+
+    let program: Vec<u8> = scope.param(CORE_ROOT_PARAM_PROGRAM_HASH)?;
+    let name: Vec<u8> = scope.param(CORE_ROOT_PARAM_NAME)?;
+    let description: Vec<u8> = match scope.get(scope.params, CORE_ROOT_PARAM_DESCRIPTION) {
+      Some(description) => description,
+      None => blob_field(scope.host, &program, CORE_BLOB_FIELD_PROGRAM_DESCRIPTION).unwrap_or_default(),
+    };
+    let contract: u32 = host::hname(&name);
+
+    let handlers: Vec<(u32, Handler)> = match scope.host.programs.get(&program) {
+      Some(handlers) => handlers.clone(),
+      None => return Err("unknown program hash".to_string()),
+    };
+
+    if scope.record(contract).is_ok() {
+      return Err(format!("contract already exists: {}", String::from_utf8_lossy(&name)));
+    }
+
+    let record: ContractRecord = ContractRecord::new(
+      ScHash::from_bytes(&program),
+      String::from_utf8_lossy(&description).into_owned(),
+      String::from_utf8_lossy(&name).into_owned(),
+      ScAgentId::from_bytes(&caller),
+    );
+
+    scope.set_record(contract, &record);
+
+    for (function, handler) in handlers {
+      scope.host.handlers.insert((contract, function), handler);
+    }
+
+    Ok((contract, scope.params))
+  });
+
+  // `init` is called by the root contract, not by the deployer
+  if let Some((contract, params)) = deployed {
+    let init: u32 = host::hname(INIT.as_bytes());
+
+    if host::with(|host| host.handlers.contains_key(&(contract, init))) {
+      let (root, incoming): (Vec<u8>, i32) = host::with(|host| (host.agent_id(CORE_ROOT.0), host.incoming(0)));
+
+      host::dispatch(contract, init, root, params, incoming);
+    }
+  }
+}
+
+fn root_set_default_fee(_: &ScFuncContext) {
+  run(|scope| {
+    scope.require_owner()?;
+
+    if let Some(fee) = scope.get(scope.params, CORE_ROOT_PARAM_OWNER_FEE) {
+      scope.set(scope.state, CORE_ROOT_VAR_DEFAULT_OWNER_FEE, fee);
+    }
+
+    if let Some(fee) = scope.get(scope.params, CORE_ROOT_PARAM_VALIDATOR_FEE) {
+      scope.set(scope.state, CORE_ROOT_VAR_DEFAULT_VALIDATOR_FEE, fee);
+    }
+
+    Ok(())
+  });
+}
+
+fn root_set_contract_fee(_: &ScFuncContext) {
+  run(|scope| {
+    scope.require_owner()?;
+
+    let contract: u32 = scope.param_hname(CORE_ROOT_PARAM_HNAME)?;
+    let owner_fee: Option<Vec<u8>> = scope.get(scope.params, CORE_ROOT_PARAM_OWNER_FEE);
+    let validator_fee: Option<Vec<u8>> = scope.get(scope.params, CORE_ROOT_PARAM_VALIDATOR_FEE);
+    let mut record: ContractRecord = scope.record(contract)?;
+
+    record.set_fees(
+      owner_fee.as_deref().map(host::int64),
+      validator_fee.as_deref().map(host::int64),
+    );
+    scope.set_record(contract, &record);
+
+    Ok(())
+  });
+}
+
+fn root_delegate_ownership(_: &ScFuncContext) {
+  run(|scope| {
+    scope.require_owner()?;
+
+    let owner: Vec<u8> = scope
+      .param_agent(CORE_ROOT_PARAM_CHAIN_OWNER)?
+      .ok_or_else(|| format!("missing parameter: {}", CORE_ROOT_PARAM_CHAIN_OWNER))?;
+
+    scope.set(scope.state, ROOT_VAR_PENDING_OWNER, owner);
+
+    Ok(())
+  });
+}
+
+fn root_claim_ownership(_: &ScFuncContext) {
+  run(|scope| {
+    let caller: Vec<u8> = scope.caller();
+
+    if scope.get(scope.state, ROOT_VAR_PENDING_OWNER) != Some(caller.clone()) {
+      return Err("unauthorized: ownership was not delegated to caller".to_string());
+    }
+
+    scope.set(scope.state, CORE_ROOT_VAR_CHAIN_OWNER_ID, caller);
+    scope.del(scope.state, ROOT_VAR_PENDING_OWNER);
+
+    Ok(())
+  });
+}
+
+fn root_grant_permission(_: &ScFuncContext) {
+  run(|scope| {
+    scope.require_owner()?;
+
+    let deployer: Vec<u8> = scope
+      .param_agent(CORE_ROOT_PARAM_DEPLOYER)?
+      .ok_or_else(|| format!("missing parameter: {}", CORE_ROOT_PARAM_DEPLOYER))?;
+
+    let permissions: i32 = scope.map(scope.state, ROOT_VAR_DEPLOY_PERMISSIONS);
+
+    scope.set(permissions, deployer.as_slice(), vec![1]);
+
+    Ok(())
+  });
+}
+
+fn root_revoke_permission(_: &ScFuncContext) {
+  run(|scope| {
+    scope.require_owner()?;
+
+    let deployer: Vec<u8> = scope
+      .param_agent(CORE_ROOT_PARAM_DEPLOYER)?
+      .ok_or_else(|| format!("missing parameter: {}", CORE_ROOT_PARAM_DEPLOYER))?;
+
+    let permissions: i32 = scope.map(scope.state, ROOT_VAR_DEPLOY_PERMISSIONS);
+
+    scope.del(permissions, deployer.as_slice());
+
+    Ok(())
+  });
+}
+
+fn root_get_chain_info(_: &ScViewContext) {
+  run(|scope| {
+    for key in [
+      CORE_ROOT_VAR_CHAIN_ID,
+      CORE_ROOT_VAR_CHAIN_OWNER_ID,
+      CORE_ROOT_VAR_DESCRIPTION,
+      CORE_ROOT_VAR_FEE_COLOR,
+      CORE_ROOT_VAR_DEFAULT_OWNER_FEE,
+      CORE_ROOT_VAR_DEFAULT_VALIDATOR_FEE,
+    ]
+    .iter()
+    {
+      let value: Vec<u8> = scope.get_or_default(scope.state, *key);
+      scope.set(scope.results, *key, value);
+    }
+
+    let source: i32 = scope.map(scope.state, CORE_ROOT_VAR_CONTRACT_REGISTRY);
+    let target: i32 = scope.map(scope.results, CORE_ROOT_VAR_CONTRACT_REGISTRY);
+
+    for (key, value) in scope.host.store.entries(source) {
+      scope.set(target, key, value);
+    }
+
+    Ok(())
+  });
+}
+
+fn root_find_contract(_: &ScViewContext) {
+  run(|scope| {
+    let contract: u32 = scope.param_hname(CORE_ROOT_PARAM_HNAME)?;
+    let record: ContractRecord = scope.record(contract)?;
+
+    scope.set(scope.results, CORE_ROOT_PARAM_DATA, record.to_bytes());
+
+    Ok(())
+  });
+}
+
+fn root_get_fee_info(_: &ScViewContext) {
+  run(|scope| {
+    let contract: u32 = scope.param_hname(CORE_ROOT_PARAM_HNAME)?;
+    let color: Vec<u8> = scope.get_or_default(scope.state, CORE_ROOT_VAR_FEE_COLOR);
+    let (owner_fee, validator_fee): (i64, i64) = scope.fees(contract);
+
+    scope.set(scope.results, CORE_ROOT_PARAM_FEE_COLOR, color);
+    scope.set(
+      scope.results,
+      CORE_ROOT_PARAM_OWNER_FEE,
+      owner_fee.to_le_bytes().to_vec(),
+    );
+    scope.set(
+      scope.results,
+      CORE_ROOT_PARAM_VALIDATOR_FEE,
+      validator_fee.to_le_bytes().to_vec(),
+    );
+
+    Ok(())
+  });
+}
+
+// =============================================================================
+// =============================================================================
+
+fn accounts_deposit(_: &ScFuncContext) {
+  run(|scope| {
+    let target: Vec<u8> = match scope.param_agent(CORE_ACCOUNTS_PARAM_AGENT_ID)? {
+      Some(agent) => agent,
+      None => scope.caller(),
+    };
+
+    let incoming: i32 = scope.host.store.child(OBJ_ID_ROOT, KEY_INCOMING.0).unwrap_or_default();
+    let source: i32 = scope.host.contract_account(CORE_ACCOUNTS.0);
+    let target: i32 = scope.host.account(&target);
+
+    scope.host.move_tokens(source, Some(target), incoming)
+  });
+}
+
+fn accounts_withdraw(_: &ScFuncContext) {
+  run(|scope| {
+    let caller: Vec<u8> = scope.caller();
+
+    if caller[33..] != [0; 4] {
+      return Err("caller must be an address".to_string());
+    }
+
+    let incoming: i32 = scope.host.store.child(OBJ_ID_ROOT, KEY_INCOMING.0).unwrap_or_default();
+    let holding: i32 = scope.host.contract_account(CORE_ACCOUNTS.0);
+    let account: i32 = scope.host.account(&caller);
+    let wallet: i32 = scope.host.wallet(&caller[..33]);
+
+    scope.host.move_tokens(holding, Some(account), incoming)?;
+
+    let balances: Vec<(Vec<u8>, i64)> = scope
+      .host
+      .store
+      .entries(account)
+      .into_iter()
+      .map(|(color, amount)| (color.into_bytes(), host::int64(&amount)))
+      .filter(|(_, amount)| *amount > 0)
+      .collect();
+
+    scope.host.move_tokens(account, Some(wallet), account)?;
+    scope.host.transfers.push(RawTransfer {
+      address: caller[..33].to_vec(),
+      balances,
+    });
+
+    Ok(())
+  });
+}
+
+fn accounts_balance(_: &ScViewContext) {
+  run(|scope| {
+    let agent: Vec<u8> = scope
+      .param_agent(CORE_ACCOUNTS_PARAM_AGENT_ID)?
+      .ok_or_else(|| format!("missing parameter: {}", CORE_ACCOUNTS_PARAM_AGENT_ID))?;
+
+    let account: i32 = scope.host.account(&agent);
+
+    for (color, amount) in scope.host.store.entries(account) {
+      scope
+        .host
+        .set_balance(scope.results, color.as_bytes(), host::int64(&amount));
+    }
+
+    Ok(())
+  });
+}
+
+fn accounts_total_assets(_: &ScViewContext) {
+  run(|scope| {
+    let accounts: Vec<i32> = scope.host.accounts.values().copied().collect();
+
+    for account in accounts {
+      for (color, amount) in scope.host.store.entries(account) {
+        let total: i64 = scope.host.balance(scope.results, color.as_bytes());
+        scope
+          .host
+          .set_balance(scope.results, color.as_bytes(), total + host::int64(&amount));
+      }
+    }
+
+    Ok(())
+  });
+}
+
+fn accounts_accounts(_: &ScViewContext) {
+  run(|scope| {
+    let accounts: Vec<(Vec<u8>, i32)> = scope
+      .host
+      .accounts
+      .iter()
+      .map(|(agent, account)| (agent.clone(), *account))
+      .collect();
+
+    for (agent, account) in accounts {
+      let entries: Vec<(KeyName, Vec<u8>)> = scope.host.store.entries(account);
+
+      if entries.iter().any(|(_, amount)| host::int64(amount) != 0) {
+        scope.set(scope.results, agent.as_slice(), Vec::new());
+      }
+    }
+
+    Ok(())
+  });
+}
+
+// =============================================================================
+// =============================================================================
+
+fn blob_store(_: &ScFuncContext) {
+  run(|scope| {
+    let mut fields: Vec<(KeyName, Vec<u8>)> = scope.host.store.entries(scope.params);
+
+    if fields.is_empty() {
+      return Err("blob must contain at least one field".to_string());
+    }
+
+    fields.sort();
+
+    let data: Vec<u8> = fields
+      .iter()
+      .flat_map(|(key, value)| key.as_bytes().iter().chain(value))
+      .copied()
+      .collect();
+    let hash: [u8; 32] = host::blake2b(&data);
+    let blobs: i32 = scope.map(scope.state, BLOB_VAR_FIELDS);
+    let blob: i32 = scope.map(blobs, &hash[..]);
+    let sizes: i32 = scope.map(scope.state, BLOB_VAR_SIZES);
+    let size: usize = fields.iter().map(|(_, value)| value.len()).sum();
+
+    for (key, value) in fields {
+      scope.set(blob, key, value);
+    }
+
+    scope.set(sizes, &hash[..], (size as u32).to_le_bytes().to_vec());
+    scope.set(scope.results, CORE_BLOB_PARAM_HASH, hash.to_vec());
+
+    Ok(())
+  });
+}
+
+fn blob_fields(scope: &mut Scope<'_>) -> Result<i32, String> {
+  let hash: Vec<u8> = scope.param(CORE_BLOB_PARAM_HASH)?;
+  let sizes: i32 = scope.map(scope.state, BLOB_VAR_SIZES);
+
+  if scope.get(sizes, hash.as_slice()).is_none() {
+    return Err("blob not found".to_string());
+  }
+
+  let blobs: i32 = scope.map(scope.state, BLOB_VAR_FIELDS);
+
+  Ok(scope.map(blobs, hash.as_slice()))
+}
+
+/// Returns a field of the blob identified by `hash`.
+fn blob_field(host: &mut Host, hash: &[u8], field: &str) -> Option<Vec<u8>> {
+  let mut scope: Scope<'_> = Scope::contract(host, CORE_BLOB.0);
+  let blobs: i32 = scope.map(scope.state, BLOB_VAR_FIELDS);
+  let blob: i32 = scope.map(blobs, hash);
+
+  scope.get(blob, field)
+}
+
+fn blob_get_field(_: &ScViewContext) {
+  run(|scope| {
+    let blob: i32 = blob_fields(scope)?;
+    let field: Vec<u8> = scope.param(CORE_BLOB_PARAM_FIELD)?;
+
+    let value: Vec<u8> = scope
+      .get(blob, KeyName::String(field.clone()))
+      .ok_or_else(|| format!("blob field not found: {}", String::from_utf8_lossy(&field)))?;
+
+    scope.set(scope.results, CORE_BLOB_PARAM_BYTES, value);
+
+    Ok(())
+  });
+}
+
+fn blob_get_info(_: &ScViewContext) {
+  run(|scope| {
+    let blob: i32 = blob_fields(scope)?;
+
+    for (key, value) in scope.host.store.entries(blob) {
+      scope.set(scope.results, key, (value.len() as u32).to_le_bytes().to_vec());
+    }
+
+    Ok(())
+  });
+}
+
+fn blob_list(_: &ScViewContext) {
+  run(|scope| {
+    let sizes: i32 = scope.map(scope.state, BLOB_VAR_SIZES);
+
+    for (key, value) in scope.host.store.entries(sizes) {
+      scope.set(scope.results, key, value);
+    }
+
+    Ok(())
+  });
+}
+
+// =============================================================================
+// =============================================================================
+
+fn eventlog_records(scope: &mut Scope<'_>) -> Result<Vec<Vec<u8>>, String> {
+  let contract: u32 = scope.param_hname(CORE_EVENTLOG_PARAM_CONTRACT_HNAME)?;
+  let from: i64 = host::int64(&scope.get_or_default(scope.params, CORE_EVENTLOG_PARAM_FROM_TS));
+  let to: i64 = host::int64(&scope.get_or_default(scope.params, CORE_EVENTLOG_PARAM_TO_TS));
+
+  let records: Vec<Vec<u8>> = scope
+    .host
+    .events
+    .iter()
+    .filter(|event| event.contract == contract)
+    .filter(|event| event.timestamp >= from && (to == 0 || event.timestamp <= to))
+    .map(|event| {
+      let mut record: Vec<u8> = event.timestamp.to_le_bytes().to_vec();
+      record.extend_from_slice(event.text.as_bytes());
+      record
+    })
+    .collect();
+
+  Ok(records)
+}
+
+fn eventlog_get_num_records(_: &ScViewContext) {
+  run(|scope| {
+    let contract: u32 = scope.param_hname(CORE_EVENTLOG_PARAM_CONTRACT_HNAME)?;
+    let count: usize = scope
+      .host
+      .events
+      .iter()
+      .filter(|event| event.contract == contract)
+      .count();
+
+    scope.set(
+      scope.results,
+      CORE_EVENTLOG_PARAM_NUM_RECORDS,
+      (count as i64).to_le_bytes().to_vec(),
+    );
+
+    Ok(())
+  });
+}
+
+// Records are encoded as the int64 timestamp followed by the event text
+fn eventlog_get_records(_: &ScViewContext) {
+  run(|scope| {
+    let records: Vec<Vec<u8>> = eventlog_records(scope)?;
+
+    let limit: i64 = scope
+      .get(scope.params, CORE_EVENTLOG_PARAM_MAX_LAST_RECORDS)
+      .map(|bytes| host::int64(&bytes))
+      .unwrap_or(EVENTLOG_MAX_RECORDS);
+
+    let key: i32 = scope.key(CORE_EVENTLOG_PARAM_RECORDS);
+    let array: i32 = scope.host.store.object(scope.results, key, TYPE_BYTES | TYPE_ARRAY);
+    let skip: usize = records.len().saturating_sub(limit.max(0) as usize);
+
+    for (index, record) in records.into_iter().skip(skip).enumerate() {
+      scope.host.store.set(array, index as i32, record);
+    }
+
+    Ok(())
+  });
+}
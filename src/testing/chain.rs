@@ -0,0 +1,453 @@
+use core::cell::Cell;
+use core::cell::RefCell;
+use core::marker::PhantomData;
+use wasmlib::host::OBJ_ID_ROOT;
+use wasmlib::host::TYPE_MAP;
+use wasmlib::ScAddress;
+use wasmlib::ScAgentId;
+use wasmlib::ScBaseContext;
+use wasmlib::ScChainId;
+use wasmlib::ScColor;
+use wasmlib::ScFuncContext;
+use wasmlib::ScHash;
+use wasmlib::ScImmutableMap;
+use wasmlib::ScMutableMap;
+use wasmlib::ScViewContext;
+use wasmlib::CORE_BLOB;
+use wasmlib::CORE_BLOB_FUNC_STORE_BLOB;
+use wasmlib::CORE_BLOB_PARAM_HASH;
+use wasmlib::CORE_ROOT;
+use wasmlib::CORE_ROOT_FUNC_DEPLOY_CONTRACT;
+use wasmlib::CORE_ROOT_PARAM_NAME;
+use wasmlib::CORE_ROOT_PARAM_PROGRAM_HASH;
+use wasmlib::KEY_CHAIN_ID;
+use wasmlib::KEY_RESULTS;
+use wasmlib::KEY_TIMESTAMP;
+
+use crate::consts::CORE_BLOB_FIELD_PROGRAM_BINARY;
+use crate::consts::CORE_BLOB_FIELD_PROGRAM_DESCRIPTION;
+use crate::consts::CORE_BLOB_FIELD_VM_TYPE;
+use crate::testing::builtin;
+use crate::testing::host;
+use crate::testing::host::Handler;
+use crate::testing::host::Snapshot;
+use crate::testing::Transfer;
+use crate::traits::utility::Export;
+use crate::traits::utility::Registry;
+
+/// A contract program that can be deployed to a [MockChain].
+#[derive(Clone)]
+pub struct Program {
+  name: String,
+  description: String,
+  handlers: Vec<(u32, Handler)>,
+}
+
+impl Program {
+  /// Creates a new, empty `Program` identified by `name`.
+  pub fn new(name: &str) -> Self {
+    Self {
+      name: name.to_string(),
+      description: String::new(),
+      handlers: Vec::new(),
+    }
+  }
+
+  /// Sets the description of the program.
+  pub fn description(mut self, value: &str) -> Self {
+    self.description = value.to_string();
+    self
+  }
+
+  /// Adds a func exported as `name`.
+  pub fn func(mut self, name: &str, func: fn(&ScFuncContext)) -> Self {
+    self.handlers.push((host::hname(name.as_bytes()), Handler::Func(func)));
+    self
+  }
+
+  /// Adds a view exported as `name`.
+  pub fn view(mut self, name: &str, view: fn(&ScViewContext)) -> Self {
+    self.handlers.push((host::hname(name.as_bytes()), Handler::View(view)));
+    self
+  }
+
+  /// Adds the funcs and views registered by the contract `T` with
+  /// [register_with][Export::register_with].
+  pub fn export<T: Export>(mut self) -> Self {
+    let handlers: Handlers = Handlers(RefCell::new(Vec::new()));
+
+    T::register_with(&handlers);
+
+    self.handlers.extend(handlers.0.into_inner());
+    self
+  }
+}
+
+// Collects the handlers of a contract registered with `Export::register_with`
+struct Handlers(RefCell<Vec<(u32, Handler)>>);
+
+impl Registry for Handlers {
+  fn add_func(&self, name: &'static str, func: fn(&ScFuncContext)) {
+    self
+      .0
+      .borrow_mut()
+      .push((host::hname(name.as_bytes()), Handler::Func(func)));
+  }
+
+  fn add_view(&self, name: &'static str, view: fn(&ScViewContext)) {
+    self
+      .0
+      .borrow_mut()
+      .push((host::hname(name.as_bytes()), Handler::View(view)));
+  }
+}
+
+// =============================================================================
+// =============================================================================
+
+/// A simulated chain running the core contracts in-process.
+///
+/// Creating a `MockChain` resets the host of the current thread. Programs are
+/// uploaded to the `blob` contract and deployed through the `root` contract,
+/// after which requests are posted from L1 addresses funded with
+/// [fund][Self::fund]. Tokens sent with a request are credited to the target
+/// contract after deducting the fees configured in the `root` contract, which
+/// are paid to the chain owner. Requests made by [upload][Self::upload] and
+/// [deploy][Self::deploy] are sent by the chain owner free of charge.
+///
+/// # Example
+///
+/// ```
+/// use scarab::contracts::core::Accounts;
+/// use scarab::testing::MockChain;
+/// use scarab::testing::Program;
+/// use scarab::wasmlib::ScAddress;
+/// use scarab::wasmlib::ScColor;
+/// use scarab::wasmlib::ScFuncContext;
+///
+/// fn donate(ctx: &ScFuncContext) {
+///   let amount: i64 = ctx.incoming().balance(&ScColor::IOTA);
+///   ctx.event(&format!("donation {}", amount));
+/// }
+///
+/// let chain: MockChain = MockChain::new();
+/// let program = chain.upload(&Program::new("donate").func("donate", donate)).unwrap();
+///
+/// chain.deploy(&program, "donate").unwrap();
+///
+/// let alice: ScAddress = chain.address("alice");
+///
+/// chain.fund(&alice, &ScColor::IOTA, 100);
+/// chain.post(&alice, "donate", "donate", &[(ScColor::IOTA, 42)]).unwrap();
+///
+/// assert_eq!(chain.balance(&chain.contract_id("donate"), &ScColor::IOTA), 42);
+/// assert_eq!(chain.events("donate"), vec!["donation 42"]);
+/// ```
+pub struct MockChain {
+  params: Cell<i32>,
+  // The host is thread-local
+  marker: PhantomData<*const ()>,
+}
+
+impl MockChain {
+  /// Creates a new chain owned by the address with seed `"owner"`.
+  pub fn new() -> Self {
+    host::reset();
+
+    let this: Self = Self {
+      params: Cell::new(0),
+      marker: PhantomData,
+    };
+
+    let owner: ScAgentId = this.address("owner").as_agent_id();
+    let mut chain: Vec<u8> = vec![2];
+
+    chain.extend_from_slice(&host::blake2b(b"chain"));
+
+    host::with(|host| {
+      host.set_root(KEY_CHAIN_ID, chain);
+      builtin::install(host, owner.to_bytes());
+    });
+
+    this
+  }
+
+  /// Returns the id of the chain.
+  pub fn chain_id(&self) -> ScChainId {
+    ScChainId::from_bytes(&host::with(|host| host.get_root(KEY_CHAIN_ID)))
+  }
+
+  /// Returns the agent id of the chain owner.
+  pub fn owner(&self) -> ScAgentId {
+    ScAgentId::from_bytes(&host::with(builtin::owner))
+  }
+
+  /// Returns a deterministic L1 address derived from `seed`.
+  pub fn address(&self, seed: &str) -> ScAddress {
+    let mut address: Vec<u8> = vec![0];
+
+    address.extend_from_slice(&host::blake2b(seed.as_bytes()));
+
+    ScAddress::from_bytes(&address)
+  }
+
+  /// Returns the agent id of the contract `name`.
+  pub fn contract_id(&self, name: &str) -> ScAgentId {
+    ScAgentId::from_bytes(&host::with(|host| host.agent_id(host::hname(name.as_bytes()))))
+  }
+
+  /// Sets the timestamp of the following requests.
+  pub fn set_timestamp(&self, timestamp: i64) {
+    host::with(|host| host.set_root(KEY_TIMESTAMP, timestamp.to_le_bytes().to_vec()));
+  }
+
+  /// Adds `amount` tokens of `color` to the L1 `address`.
+  pub fn fund(&self, address: &ScAddress, color: &ScColor, amount: i64) {
+    host::with(|host| {
+      let wallet: i32 = host.wallet(address.to_bytes());
+      let balance: i64 = host.balance(wallet, color.to_bytes());
+
+      host.set_balance(wallet, color.to_bytes(), balance + amount);
+    });
+  }
+
+  /// Returns the amount of `color` held by the L1 `address`.
+  pub fn l1_balance(&self, address: &ScAddress, color: &ScColor) -> i64 {
+    host::with(|host| {
+      let wallet: i32 = host.wallet(address.to_bytes());
+      host.balance(wallet, color.to_bytes())
+    })
+  }
+
+  /// Returns the amount of `color` held in the on-chain account of `agent`.
+  pub fn balance(&self, agent: &ScAgentId, color: &ScColor) -> i64 {
+    host::with(|host| {
+      let account: i32 = host.account(agent.to_bytes());
+      host.balance(account, color.to_bytes())
+    })
+  }
+
+  /// Returns a new, empty parameter map for the next request.
+  pub fn params(&self) -> ScMutableMap {
+    let (params, obj_id): (ScMutableMap, i32) = host::create_map();
+
+    self.params.set(obj_id);
+
+    params
+  }
+
+  /// Stores `program` in the `blob` contract and returns its hash.
+  ///
+  /// # Errors
+  ///
+  /// Returns the panic message if the request fails.
+  pub fn upload(&self, program: &Program) -> Result<ScHash, String> {
+    let params: ScMutableMap = self.params();
+
+    params.get_string(CORE_BLOB_FIELD_VM_TYPE).set_value(builtin::VM_TYPE);
+    params
+      .get_bytes(CORE_BLOB_FIELD_PROGRAM_BINARY)
+      .set_value(program.name.as_bytes());
+    params
+      .get_string(CORE_BLOB_FIELD_PROGRAM_DESCRIPTION)
+      .set_value(&program.description);
+
+    let owner: Vec<u8> = host::with(builtin::owner);
+    let results: ScImmutableMap = self.request(owner, CORE_BLOB.0, CORE_BLOB_FUNC_STORE_BLOB.0, None)?;
+    let hash: ScHash = results.get_hash(CORE_BLOB_PARAM_HASH).value();
+
+    host::with(|host| host.programs.insert(hash.to_bytes().to_vec(), program.handlers.clone()));
+
+    Ok(hash)
+  }
+
+  /// Deploys the uploaded `program` as the contract `name`.
+  ///
+  /// The parameters of the next request are passed to the `init` function of
+  /// the contract, if it exists. As on a node, `init` is called by the root
+  /// contract, so the deployer must be passed as a parameter if required.
+  ///
+  /// # Errors
+  ///
+  /// Returns the panic message if the request fails.
+  pub fn deploy(&self, program: &ScHash, name: &str) -> Result<(), String> {
+    if self.params.get() == 0 {
+      self.params();
+    }
+
+    let params: i32 = self.params.get();
+
+    host::with(|host| {
+      for (key, value) in [
+        (CORE_ROOT_PARAM_PROGRAM_HASH, program.to_bytes()),
+        (CORE_ROOT_PARAM_NAME, name.as_bytes()),
+      ]
+      .iter()
+      {
+        let key: i32 = host.store.key_id(*key);
+        host.store.set(params, key, value.to_vec());
+      }
+    });
+
+    let owner: Vec<u8> = host::with(builtin::owner);
+
+    self
+      .request(owner, CORE_ROOT.0, CORE_ROOT_FUNC_DEPLOY_CONTRACT.0, None)
+      .map(|_| ())
+  }
+
+  /// Posts a request from the L1 `address` to `function` of `contract`,
+  /// sending along the tokens in `transfer`.
+  ///
+  /// # Errors
+  ///
+  /// Returns the panic message if the request fails. All changes made by the
+  /// request, except for log messages, are rolled back.
+  pub fn post(
+    &self,
+    address: &ScAddress,
+    contract: &str,
+    function: &str,
+    transfer: &[(ScColor, i64)],
+  ) -> Result<ScImmutableMap, String> {
+    self.request(
+      address.as_agent_id().to_bytes().to_vec(),
+      host::hname(contract.as_bytes()),
+      host::hname(function.as_bytes()),
+      Some(transfer),
+    )
+  }
+
+  /// Calls the view `function` of `contract`.
+  ///
+  /// # Errors
+  ///
+  /// Returns the panic message if the view fails or `function` is not a view.
+  pub fn call_view(&self, contract: &str, function: &str) -> Result<ScImmutableMap, String> {
+    let contract: u32 = host::hname(contract.as_bytes());
+    let function: u32 = host::hname(function.as_bytes());
+
+    if let Some(Handler::Func(_)) = host::with(|host| host.handlers.get(&(contract, function)).copied()) {
+      return Err(format!("not a view: {:08x}::{:08x}", contract, function));
+    }
+
+    self.request(Vec::new(), contract, function, None)
+  }
+
+  /// Returns the events emitted by `contract`.
+  pub fn events(&self, contract: &str) -> Vec<String> {
+    let contract: u32 = host::hname(contract.as_bytes());
+
+    host::with(|host| {
+      host
+        .events
+        .iter()
+        .filter(|event| event.contract == contract)
+        .map(|event| event.text.clone())
+        .collect()
+    })
+  }
+
+  /// Returns the messages logged by all contracts.
+  pub fn logs(&self) -> Vec<String> {
+    host::with(|host| host.logs.clone())
+  }
+
+  /// Returns the transfers made from the chain to L1 addresses.
+  pub fn transfers(&self) -> Vec<Transfer> {
+    host::with(|host| host.transfers.iter().cloned().map(Transfer::from).collect())
+  }
+
+  // Requests without a transfer are not charged any fees
+  fn request(
+    &self,
+    caller: Vec<u8>,
+    contract: u32,
+    function: u32,
+    transfer: Option<&[(ScColor, i64)]>,
+  ) -> Result<ScImmutableMap, String> {
+    let params: i32 = self.params.replace(0);
+
+    let snapshot: Snapshot = host::with(|host| host.snapshot());
+
+    let prepared: Result<(i32, i32), String> = host::with(|host| {
+      let incoming: i32 = host.store.create(TYPE_MAP, false);
+
+      let params: i32 = if params == 0 {
+        host.store.create(TYPE_MAP, false)
+      } else {
+        params
+      };
+
+      host.panic = None;
+
+      let transfer: &[(ScColor, i64)] = match transfer {
+        Some(transfer) => transfer,
+        None => return Ok((params, incoming)),
+      };
+
+      for (color, amount) in transfer {
+        host.set_balance(incoming, color.to_bytes(), *amount);
+      }
+
+      charge(host, &caller, contract, incoming).map(|()| (params, incoming))
+    });
+
+    let (params, incoming): (i32, i32) = match prepared {
+      Ok(prepared) => prepared,
+      Err(error) => {
+        host::with(|host| host.restore(snapshot));
+        return Err(error);
+      }
+    };
+
+    let results: Option<i32> = host::dispatch(contract, function, caller, params, incoming);
+
+    host::with(|host| match (host.panic.take(), results) {
+      (None, Some(results)) => {
+        host.store.link(OBJ_ID_ROOT, KEY_RESULTS.0, results);
+        Ok(())
+      }
+      (error, _) => {
+        host.restore(snapshot);
+        Err(error.unwrap_or_default())
+      }
+    })?;
+
+    Ok(ScFuncContext {}.results().immutable())
+  }
+}
+
+impl Default for MockChain {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Moves the tokens of a request from the wallet of `caller` to `contract`
+/// and pays the request fee to the chain owner.
+fn charge(host: &mut host::Host, caller: &[u8], contract: u32, incoming: i32) -> Result<(), String> {
+  let (color, fee): (Vec<u8>, i64) = builtin::fees(host, contract);
+  let paid: i64 = host.balance(incoming, &color);
+
+  if paid < fee {
+    return Err(format!("insufficient fee: {} < {}", paid, fee));
+  }
+
+  let wallet: i32 = host.wallet(&caller[..33]);
+  let account: i32 = host.contract_account(contract);
+
+  host.move_tokens(wallet, Some(account), incoming)?;
+
+  if fee > 0 {
+    let owner: Vec<u8> = builtin::owner(host);
+    let owner: i32 = host.account(&owner);
+    let payment: i32 = host.store.create(TYPE_MAP, false);
+
+    host.set_balance(payment, &color, fee);
+    host.move_tokens(account, Some(owner), payment)?;
+    host.set_balance(incoming, &color, paid - fee);
+  }
+
+  Ok(())
+}
@@ -0,0 +1,972 @@
+//! The object store and host functions of the mock host.
+//!
+//! WasmLib talks to its host through four functions that operate on objects
+//! identified by integer ids. Objects are maps or arrays that hold values and
+//! child objects under integer keys; keys are either predefined (negative) or
+//! interned from strings and bytes (positive). As in Wasp, strings and bytes
+//! are interned separately, so the string `"abc"` and the bytes `b"abc"` map
+//! to different keys.
+
+#![allow(non_snake_case)]
+
+use blake2::digest::Update;
+use blake2::digest::VariableOutput;
+use blake2::VarBlake2b;
+use core::cell::RefCell;
+use core::mem;
+use core::slice;
+use sha3::Digest;
+use sha3::Sha3_256;
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::panic::catch_unwind;
+use std::panic::AssertUnwindSafe;
+use wasmlib::host::OBJ_ID_ROOT;
+use wasmlib::host::TYPE_ARRAY;
+use wasmlib::host::TYPE_COLOR;
+use wasmlib::host::TYPE_MAP;
+use wasmlib::ScFuncContext;
+use wasmlib::ScViewContext;
+use wasmlib::*;
+
+use crate::testing::builtin;
+use crate::types::DecodeError;
+use crate::types::Decoder;
+
+/// A function that can be invoked through `ScFuncContext::call`.
+#[derive(Clone, Copy)]
+pub(crate) enum Handler {
+  Func(fn(&ScFuncContext)),
+  View(fn(&ScViewContext)),
+}
+
+/// An event emitted by a contract.
+#[derive(Clone, Debug)]
+pub(crate) struct RawEvent {
+  pub(crate) contract: u32,
+  pub(crate) timestamp: i64,
+  pub(crate) text: String,
+}
+
+/// A transfer of tokens from a contract to an address.
+#[derive(Clone, Debug)]
+pub(crate) struct RawTransfer {
+  pub(crate) address: Vec<u8>,
+  pub(crate) balances: Vec<(Vec<u8>, i64)>,
+}
+
+/// The name of an interned key.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum KeyName {
+  String(Vec<u8>),
+  Bytes(Vec<u8>),
+}
+
+impl KeyName {
+  /// Returns the name of the key without its namespace.
+  pub(crate) fn as_bytes(&self) -> &[u8] {
+    match self {
+      Self::String(name) | Self::Bytes(name) => name,
+    }
+  }
+
+  /// Converts the key into its name without the namespace.
+  pub(crate) fn into_bytes(self) -> Vec<u8> {
+    match self {
+      Self::String(name) | Self::Bytes(name) => name,
+    }
+  }
+}
+
+impl From<&str> for KeyName {
+  fn from(other: &str) -> Self {
+    Self::String(other.as_bytes().to_vec())
+  }
+}
+
+impl From<&[u8]> for KeyName {
+  fn from(other: &[u8]) -> Self {
+    Self::Bytes(other.to_vec())
+  }
+}
+
+// =============================================================================
+// =============================================================================
+
+#[derive(Clone, Default)]
+struct Object {
+  parent: i32,
+  key: i32,
+  type_id: i32,
+  state: bool,
+  length: i32,
+  values: BTreeMap<i32, Vec<u8>>,
+  children: BTreeMap<i32, i32>,
+}
+
+impl Object {
+  fn is_array(&self) -> bool {
+    self.type_id & TYPE_ARRAY != 0
+  }
+}
+
+/// The objects and interned keys of the host.
+#[derive(Clone)]
+pub(crate) struct Store {
+  objects: Vec<Object>,
+  keys: HashMap<KeyName, i32>,
+  names: Vec<KeyName>,
+}
+
+impl Store {
+  fn new() -> Self {
+    let root: Object = Object {
+      type_id: TYPE_MAP,
+      ..Object::default()
+    };
+
+    Self {
+      objects: vec![Object::default(), root],
+      keys: HashMap::new(),
+      names: Vec::new(),
+    }
+  }
+
+  /// Returns the id of the key with the given name, interning it if needed.
+  pub(crate) fn key_id<K>(&mut self, name: K) -> i32
+  where
+    K: Into<KeyName>,
+  {
+    let name: KeyName = name.into();
+
+    if let Some(key) = self.keys.get(&name) {
+      return *key;
+    }
+
+    self.names.push(name.clone());
+    self.keys.insert(name, self.names.len() as i32);
+    self.names.len() as i32
+  }
+
+  /// Returns the name of an interned key.
+  pub(crate) fn key_name(&self, key: i32) -> Option<&KeyName> {
+    self.names.get((key - 1) as usize)
+  }
+
+  /// Returns the id of the child object under `key`, creating it if needed.
+  pub(crate) fn object(&mut self, obj_id: i32, key: i32, type_id: i32) -> i32 {
+    if let Some(child) = self.objects[obj_id as usize].children.get(&key) {
+      return *child;
+    }
+
+    let child: i32 = self.create(type_id, self.objects[obj_id as usize].state);
+
+    self.objects[child as usize].parent = obj_id;
+    self.objects[child as usize].key = key;
+    self.link(obj_id, key, child);
+
+    child
+  }
+
+  /// Creates a detached object.
+  pub(crate) fn create(&mut self, type_id: i32, state: bool) -> i32 {
+    self.objects.push(Object {
+      type_id,
+      state,
+      ..Object::default()
+    });
+
+    (self.objects.len() - 1) as i32
+  }
+
+  /// Makes `child` available under `key` of the given object.
+  pub(crate) fn link(&mut self, obj_id: i32, key: i32, child: i32) {
+    let object: &mut Object = &mut self.objects[obj_id as usize];
+
+    object.children.insert(key, child);
+
+    if object.is_array() && key >= object.length {
+      object.length = key + 1;
+    }
+  }
+
+  /// Returns the child object under `key` if it exists.
+  pub(crate) fn child(&self, obj_id: i32, key: i32) -> Option<i32> {
+    self.objects[obj_id as usize].children.get(&key).copied()
+  }
+
+  /// Returns the parent object and key of the given object.
+  pub(crate) fn parent(&self, obj_id: i32) -> (i32, i32) {
+    let object: &Object = &self.objects[obj_id as usize];
+
+    (object.parent, object.key)
+  }
+
+  pub(crate) fn get(&self, obj_id: i32, key: i32) -> Option<&[u8]> {
+    self.objects[obj_id as usize].values.get(&key).map(Vec::as_slice)
+  }
+
+  pub(crate) fn set(&mut self, obj_id: i32, key: i32, value: Vec<u8>) {
+    let object: &mut Object = &mut self.objects[obj_id as usize];
+
+    object.values.insert(key, value);
+
+    if object.is_array() && key >= object.length {
+      object.length = key + 1;
+    }
+  }
+
+  pub(crate) fn del(&mut self, obj_id: i32, key: i32) {
+    self.objects[obj_id as usize].values.remove(&key);
+  }
+
+  pub(crate) fn clear(&mut self, obj_id: i32) {
+    let object: &mut Object = &mut self.objects[obj_id as usize];
+
+    object.values.clear();
+    object.children.clear();
+    object.length = 0;
+  }
+
+  pub(crate) fn len(&self, obj_id: i32) -> i32 {
+    let object: &Object = &self.objects[obj_id as usize];
+
+    if object.is_array() {
+      object.length
+    } else {
+      (object.values.len() + object.children.len()) as i32
+    }
+  }
+
+  /// Returns the values stored in a map keyed by interned key names.
+  pub(crate) fn entries(&self, obj_id: i32) -> Vec<(KeyName, Vec<u8>)> {
+    self.objects[obj_id as usize]
+      .values
+      .iter()
+      .filter_map(|(key, value)| Some((self.key_name(*key)?.clone(), value.clone())))
+      .collect()
+  }
+
+  fn is_state(&self, obj_id: i32) -> bool {
+    self.objects[obj_id as usize].state
+  }
+}
+
+// =============================================================================
+// =============================================================================
+
+/// The complete state of the mock host.
+pub(crate) struct Host {
+  pub(crate) store: Store,
+  pub(crate) panic: Option<String>,
+  pub(crate) readonly: bool,
+  pub(crate) logs: Vec<String>,
+  pub(crate) traces: Vec<String>,
+  pub(crate) events: Vec<RawEvent>,
+  pub(crate) transfers: Vec<RawTransfer>,
+  pub(crate) handlers: HashMap<(u32, u32), Handler>,
+  pub(crate) programs: HashMap<Vec<u8>, Vec<(u32, Handler)>>,
+  pub(crate) states: HashMap<u32, i32>,
+  pub(crate) accounts: HashMap<Vec<u8>, i32>,
+  pub(crate) wallets: HashMap<Vec<u8>, i32>,
+  seed: u64,
+}
+
+impl Host {
+  fn new() -> Self {
+    Self {
+      store: Store::new(),
+      panic: None,
+      readonly: false,
+      logs: Vec::new(),
+      traces: Vec::new(),
+      events: Vec::new(),
+      transfers: Vec::new(),
+      handlers: HashMap::new(),
+      programs: HashMap::new(),
+      states: HashMap::new(),
+      accounts: HashMap::new(),
+      wallets: HashMap::new(),
+      seed: 0x2545_f491_4f6c_dd1d,
+    }
+  }
+
+  /// Records a failure of the current request.
+  pub(crate) fn fail(&mut self, message: String) {
+    if self.panic.is_none() {
+      self.panic = Some(message);
+    }
+  }
+
+  /// Returns the id of the root child object under `key`.
+  pub(crate) fn root(&mut self, key: Key32, type_id: i32) -> i32 {
+    self.store.object(OBJ_ID_ROOT, key.0, type_id)
+  }
+
+  /// Sets a value of the root object.
+  pub(crate) fn set_root(&mut self, key: Key32, value: Vec<u8>) {
+    self.store.set(OBJ_ID_ROOT, key.0, value);
+  }
+
+  /// Returns a value of the root object.
+  pub(crate) fn get_root(&self, key: Key32) -> Vec<u8> {
+    self
+      .store
+      .get(OBJ_ID_ROOT, key.0)
+      .map(<[u8]>::to_vec)
+      .unwrap_or_default()
+  }
+
+  /// Returns the state map of `contract`, creating it if needed.
+  pub(crate) fn state(&mut self, contract: u32) -> i32 {
+    match self.states.get(&contract) {
+      Some(state) => *state,
+      None => {
+        let state: i32 = self.store.create(TYPE_MAP, true);
+        self.states.insert(contract, state);
+        state
+      }
+    }
+  }
+
+  /// Returns the on-chain balances map of `agent`, creating it if needed.
+  pub(crate) fn account(&mut self, agent: &[u8]) -> i32 {
+    match self.accounts.get(agent) {
+      Some(account) => *account,
+      None => {
+        let account: i32 = self.store.create(TYPE_MAP, false);
+        self.accounts.insert(agent.to_vec(), account);
+        account
+      }
+    }
+  }
+
+  /// Returns the on-chain balances map of `contract`, creating it if needed.
+  pub(crate) fn contract_account(&mut self, contract: u32) -> i32 {
+    self.account(&self.agent_id(contract))
+  }
+
+  /// Returns the balances map of the L1 `address`, creating it if needed.
+  pub(crate) fn wallet(&mut self, address: &[u8]) -> i32 {
+    match self.wallets.get(address) {
+      Some(wallet) => *wallet,
+      None => {
+        let wallet: i32 = self.store.create(TYPE_MAP, false);
+        self.wallets.insert(address.to_vec(), wallet);
+        wallet
+      }
+    }
+  }
+
+  /// Returns the amount of `color` in the balances map `obj_id`.
+  pub(crate) fn balance(&mut self, obj_id: i32, color: &[u8]) -> i64 {
+    let key: i32 = self.store.key_id(color);
+
+    self.store.get(obj_id, key).map(int64).unwrap_or_default()
+  }
+
+  /// Sets the amount of `color` in the balances map `obj_id`.
+  pub(crate) fn set_balance(&mut self, obj_id: i32, color: &[u8], amount: i64) {
+    let key: i32 = self.store.key_id(color);
+    let colors: i32 = self.store.object(obj_id, KEY_COLOR.0, TYPE_COLOR | TYPE_ARRAY);
+
+    if self.store.get(obj_id, key).is_none() {
+      let index: i32 = self.store.len(colors);
+      self.store.set(colors, index, color.to_vec());
+    }
+
+    self.store.set(obj_id, key, amount.to_le_bytes().to_vec());
+  }
+
+  /// Moves the tokens in the transfer map `transfer` between balances maps.
+  pub(crate) fn move_tokens(&mut self, source: i32, target: Option<i32>, transfer: i32) -> Result<(), String> {
+    for (color, amount) in self.store.entries(transfer) {
+      let amount: i64 = int64(&amount);
+      let balance: i64 = self.balance(source, color.as_bytes());
+
+      if amount < 0 || balance < amount {
+        return Err(format!("insufficient funds: {} < {}", balance, amount));
+      }
+
+      self.set_balance(source, color.as_bytes(), balance - amount);
+
+      if let Some(target) = target {
+        let balance: i64 = self.balance(target, color.as_bytes());
+        self.set_balance(target, color.as_bytes(), balance + amount);
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Builds a balances map from the transfer map `transfer`.
+  pub(crate) fn incoming(&mut self, transfer: i32) -> i32 {
+    let incoming: i32 = self.store.create(TYPE_MAP, false);
+
+    if transfer != 0 {
+      for (color, amount) in self.store.entries(transfer) {
+        self.set_balance(incoming, color.as_bytes(), int64(&amount));
+      }
+    }
+
+    incoming
+  }
+
+  fn random(&mut self) -> i64 {
+    self.seed ^= self.seed << 13;
+    self.seed ^= self.seed >> 7;
+    self.seed ^= self.seed << 17;
+    self.seed as i64
+  }
+
+  fn get_bytes(&mut self, obj_id: i32, key: i32) -> Option<Vec<u8>> {
+    if key == KEY_LENGTH.0 {
+      return Some(i64::from(self.store.len(obj_id)).to_le_bytes().to_vec());
+    }
+
+    if key == KEY_RANDOM.0 && self.store.parent(obj_id) == (OBJ_ID_ROOT, KEY_UTILITY.0) {
+      return Some(self.random().to_le_bytes().to_vec());
+    }
+
+    self.store.get(obj_id, key).map(<[u8]>::to_vec)
+  }
+
+  fn set_bytes(&mut self, obj_id: i32, key: i32, value: Option<&[u8]>) -> Option<Request> {
+    if self.panic.is_some() {
+      return None;
+    }
+
+    if obj_id == OBJ_ID_ROOT {
+      match key {
+        _ if key == KEY_LOG.0 => self.logs.push(string(value)),
+        _ if key == KEY_TRACE.0 => self.traces.push(string(value)),
+        _ if key == KEY_PANIC.0 => self.fail(string(value)),
+        _ if key == KEY_EVENT.0 => self.event(string(value)),
+        _ if key == KEY_CALL.0 => return Some(Request::Call(value.unwrap_or_default().to_vec())),
+        _ if key == KEY_DEPLOY.0 => return Some(Request::Deploy(value.unwrap_or_default().to_vec())),
+        _ if key == KEY_POST.0 => return Some(Request::Post),
+        _ => self.store_bytes(obj_id, key, value),
+      }
+
+      return None;
+    }
+
+    if self.readonly && self.store.is_state(obj_id) {
+      self.fail("attempt to modify state in a view".to_string());
+      return None;
+    }
+
+    match self.store.parent(obj_id) {
+      (OBJ_ID_ROOT, parent) if parent == KEY_UTILITY.0 => self.utility(obj_id, key, value.unwrap_or_default()),
+      (array, _) if key == KEY_BALANCES.0 && self.store.parent(array) == (OBJ_ID_ROOT, KEY_TRANSFERS.0) => {
+        self.store_bytes(obj_id, key, value);
+        self.transfer(obj_id, value.map(int64).unwrap_or_default() as i32);
+      }
+      _ => self.store_bytes(obj_id, key, value),
+    }
+
+    None
+  }
+
+  fn store_bytes(&mut self, obj_id: i32, key: i32, value: Option<&[u8]>) {
+    match value {
+      _ if key == KEY_LENGTH.0 => self.store.clear(obj_id),
+      Some(value) => self.store.set(obj_id, key, value.to_vec()),
+      None => self.store.del(obj_id, key),
+    }
+  }
+
+  fn utility(&mut self, obj_id: i32, key: i32, value: &[u8]) {
+    let (output, result): (Key32, Result<Vec<u8>, String>) = match key {
+      _ if key == KEY_NAME.0 => (KEY_HNAME, Ok(hname(value).to_le_bytes().to_vec())),
+      _ if key == KEY_HASH_BLAKE2B.0 => (KEY_HASH_BLAKE2B, Ok(blake2b(value).to_vec())),
+      _ if key == KEY_HASH_SHA3.0 => (KEY_HASH_SHA3, Ok(Sha3_256::digest(value).to_vec())),
+      _ if key == KEY_BASE58_BYTES.0 => (KEY_BASE58_STRING, Ok(base58_encode(value).into_bytes())),
+      _ if key == KEY_BASE58_STRING.0 => (KEY_BASE58_BYTES, base58_decode(value)),
+      _ => (Key32(key), Err(format!("unsupported utility function: Key32({})", key))),
+    };
+
+    match result {
+      Ok(output_value) => self.store.set(obj_id, output.0, output_value),
+      Err(error) => self.fail(error),
+    }
+  }
+
+  fn transfer(&mut self, obj_id: i32, transfer: i32) {
+    let source: i32 = self.contract_account(self.contract());
+    let address: Vec<u8> = self
+      .store
+      .get(obj_id, KEY_ADDRESS.0)
+      .map(<[u8]>::to_vec)
+      .unwrap_or_default();
+
+    let balances: Vec<(Vec<u8>, i64)> = self
+      .store
+      .entries(transfer)
+      .into_iter()
+      .map(|(color, amount)| (color.into_bytes(), int64(&amount)))
+      .collect();
+
+    let target: i32 = self.wallet(&address);
+
+    match self.move_tokens(source, Some(target), transfer) {
+      Ok(()) => self.transfers.push(RawTransfer { address, balances }),
+      Err(error) => self.fail(error),
+    }
+  }
+
+  fn event(&mut self, text: String) {
+    let event: RawEvent = RawEvent {
+      contract: self.contract(),
+      timestamp: self.timestamp(),
+      text,
+    };
+
+    self.events.push(event);
+  }
+
+  /// Returns the timestamp of the current request.
+  pub(crate) fn timestamp(&self) -> i64 {
+    int64(&self.get_root(KEY_TIMESTAMP))
+  }
+
+  /// Returns the hname of the contract that is currently executing.
+  pub(crate) fn contract(&self) -> u32 {
+    let bytes: Vec<u8> = self.get_root(KEY_CONTRACT);
+
+    if bytes.len() == 4 {
+      u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+    } else {
+      0
+    }
+  }
+
+  /// Returns the agent id of `contract` on the current chain.
+  pub(crate) fn agent_id(&self, contract: u32) -> Vec<u8> {
+    let mut agent: Vec<u8> = self.get_root(KEY_CHAIN_ID);
+
+    agent.resize(33, 0);
+    agent.extend_from_slice(&contract.to_le_bytes());
+    agent
+  }
+
+  /// Switches execution to `contract`, returning the previous context.
+  pub(crate) fn enter(&mut self, contract: u32, caller: Vec<u8>, params: i32, incoming: i32, readonly: bool) -> Frame {
+    let state: i32 = self.state(contract);
+    let account: i32 = self.contract_account(contract);
+    let results: i32 = self.store.create(TYPE_MAP, false);
+
+    let params: i32 = if params == 0 {
+      self.store.create(TYPE_MAP, false)
+    } else {
+      params
+    };
+
+    let frame: Frame = Frame {
+      params: self.store.child(OBJ_ID_ROOT, KEY_PARAMS.0),
+      results: self.store.child(OBJ_ID_ROOT, KEY_RESULTS.0),
+      state: self.store.child(OBJ_ID_ROOT, KEY_STATE.0),
+      balances: self.store.child(OBJ_ID_ROOT, KEY_BALANCES.0),
+      incoming: self.store.child(OBJ_ID_ROOT, KEY_INCOMING.0),
+      caller: self.get_root(KEY_CALLER),
+      contract: self.get_root(KEY_CONTRACT),
+      contract_id: self.get_root(KEY_CONTRACT_ID),
+      readonly: mem::replace(&mut self.readonly, readonly),
+    };
+
+    self.store.link(OBJ_ID_ROOT, KEY_PARAMS.0, params);
+    self.store.link(OBJ_ID_ROOT, KEY_RESULTS.0, results);
+    self.store.link(OBJ_ID_ROOT, KEY_STATE.0, state);
+    self.store.link(OBJ_ID_ROOT, KEY_BALANCES.0, account);
+    self.store.link(OBJ_ID_ROOT, KEY_INCOMING.0, incoming);
+    self.set_root(KEY_CALLER, caller);
+    self.set_root(KEY_CONTRACT, contract.to_le_bytes().to_vec());
+    self.set_root(KEY_CONTRACT_ID, self.agent_id(contract));
+
+    frame
+  }
+
+  /// Restores the context saved by [enter][Self::enter] and returns the
+  /// results of the call.
+  pub(crate) fn leave(&mut self, frame: Frame) -> i32 {
+    let results: i32 = self.store.child(OBJ_ID_ROOT, KEY_RESULTS.0).unwrap_or_default();
+
+    for (key, child) in [
+      (KEY_PARAMS, frame.params),
+      (KEY_RESULTS, frame.results),
+      (KEY_STATE, frame.state),
+      (KEY_BALANCES, frame.balances),
+      (KEY_INCOMING, frame.incoming),
+    ]
+    .iter()
+    {
+      match child {
+        Some(child) => self.store.link(OBJ_ID_ROOT, key.0, *child),
+        None => {
+          self.store.objects[OBJ_ID_ROOT as usize].children.remove(&key.0);
+        }
+      }
+    }
+
+    self.set_root(KEY_CALLER, frame.caller);
+    self.set_root(KEY_CONTRACT, frame.contract);
+    self.set_root(KEY_CONTRACT_ID, frame.contract_id);
+    self.readonly = frame.readonly;
+
+    results
+  }
+}
+
+/// The execution context saved while a contract calls another contract.
+pub(crate) struct Frame {
+  params: Option<i32>,
+  results: Option<i32>,
+  state: Option<i32>,
+  balances: Option<i32>,
+  incoming: Option<i32>,
+  caller: Vec<u8>,
+  contract: Vec<u8>,
+  contract_id: Vec<u8>,
+  readonly: bool,
+}
+
+impl Host {
+  /// Captures the parts of the host that are rolled back when a request fails.
+  pub(crate) fn snapshot(&self) -> Snapshot {
+    Snapshot {
+      store: self.store.clone(),
+      events: self.events.clone(),
+      transfers: self.transfers.clone(),
+      handlers: self.handlers.clone(),
+      programs: self.programs.clone(),
+      states: self.states.clone(),
+      accounts: self.accounts.clone(),
+      wallets: self.wallets.clone(),
+    }
+  }
+
+  /// Rolls the host back to a previous snapshot.
+  pub(crate) fn restore(&mut self, snapshot: Snapshot) {
+    self.store = snapshot.store;
+    self.events = snapshot.events;
+    self.transfers = snapshot.transfers;
+    self.handlers = snapshot.handlers;
+    self.programs = snapshot.programs;
+    self.states = snapshot.states;
+    self.accounts = snapshot.accounts;
+    self.wallets = snapshot.wallets;
+  }
+}
+
+/// The parts of the host that are rolled back when a request fails.
+pub(crate) struct Snapshot {
+  store: Store,
+  events: Vec<RawEvent>,
+  transfers: Vec<RawTransfer>,
+  handlers: HashMap<(u32, u32), Handler>,
+  programs: HashMap<Vec<u8>, Vec<(u32, Handler)>>,
+  states: HashMap<u32, i32>,
+  accounts: HashMap<Vec<u8>, i32>,
+  wallets: HashMap<Vec<u8>, i32>,
+}
+
+/// A request that requires executing code outside of the host borrow.
+enum Request {
+  Call(Vec<u8>),
+  Deploy(Vec<u8>),
+  Post,
+}
+
+// =============================================================================
+// =============================================================================
+
+thread_local! {
+  static HOST: RefCell<Host> = RefCell::new(Host::new());
+}
+
+/// Creates a new map through WasmLib and returns it along with its id.
+pub(crate) fn create_map() -> (ScMutableMap, i32) {
+  let map: ScMutableMap = ScMutableMap::new();
+
+  // WasmLib appends new maps to the `KEY_MAPS` array of the root object
+  let obj_id: i32 = with(|host| {
+    let maps: i32 = host.root(KEY_MAPS, TYPE_MAP | TYPE_ARRAY);
+    let index: i32 = host.store.len(maps) - 1;
+
+    host.store.child(maps, index).expect("created map")
+  });
+
+  (map, obj_id)
+}
+
+/// Runs `f` with exclusive access to the host of the current thread.
+pub(crate) fn with<T>(f: impl FnOnce(&mut Host) -> T) -> T {
+  HOST.with(|host| f(&mut host.borrow_mut()))
+}
+
+/// Replaces the host of the current thread with an empty one.
+pub(crate) fn reset() {
+  HOST.with(|host| *host.borrow_mut() = Host::new());
+}
+
+/// Runs `f`, recording a failure of the current request if it panics.
+pub(crate) fn guard(f: impl FnOnce()) {
+  if let Err(payload) = catch_unwind(AssertUnwindSafe(f)) {
+    with(|host| host.fail(message(payload)));
+  }
+}
+
+/// Extracts the message from a panic payload.
+pub(crate) fn message(payload: Box<dyn Any + Send>) -> String {
+  match payload.downcast::<String>() {
+    Ok(message) => *message,
+    Err(payload) => match payload.downcast::<&'static str>() {
+      Ok(message) => (*message).to_string(),
+      Err(_) => "unknown panic".to_string(),
+    },
+  }
+}
+
+/// Invokes the contract function described by an encoded `KEY_CALL` request.
+fn call(request: &[u8]) {
+  let decoded: Result<(ScHname, ScHname, i64, i64), DecodeError> = (|| {
+    let mut decoder: Decoder<'_> = Decoder::new(request);
+
+    Ok((
+      decoder.try_hname()?,
+      decoder.try_hname()?,
+      decoder.try_int64()?,
+      decoder.try_int64()?,
+    ))
+  })();
+
+  let (contract, function, params, transfer): (ScHname, ScHname, i64, i64) = match decoded {
+    Ok(decoded) => decoded,
+    Err(error) => return with(|host| host.fail(error.to_string())),
+  };
+
+  let caller: Option<Vec<u8>> = with(|host| {
+    let caller: Vec<u8> = host.agent_id(host.contract());
+
+    if transfer != 0 {
+      let source: i32 = host.account(&caller);
+      let target: i32 = host.contract_account(contract.0);
+
+      if let Err(error) = host.move_tokens(source, Some(target), transfer as i32) {
+        host.fail(error);
+        return None;
+      }
+    }
+
+    Some(caller)
+  });
+
+  if let Some(caller) = caller {
+    let incoming: i32 = with(|host| host.incoming(transfer as i32));
+
+    if let Some(results) = dispatch(contract.0, function.0, caller, params as i32, incoming) {
+      with(|host| host.store.link(OBJ_ID_ROOT, KEY_RETURN.0, results));
+    }
+  }
+}
+
+/// Invokes a registered function on behalf of `caller` and returns the id of
+/// its results map.
+pub(crate) fn dispatch(contract: u32, function: u32, caller: Vec<u8>, params: i32, incoming: i32) -> Option<i32> {
+  let frame: Option<(Handler, Frame)> = with(|host| {
+    let handler: Handler = match host.handlers.get(&(contract, function)) {
+      Some(handler) => *handler,
+      None => {
+        host.fail(format!("unknown function: {:08x}::{:08x}", contract, function));
+        return None;
+      }
+    };
+
+    let readonly: bool = matches!(handler, Handler::View(_));
+
+    if host.readonly && !readonly {
+      host.fail("attempt to call a func from a view".to_string());
+      return None;
+    }
+
+    Some((handler, host.enter(contract, caller, params, incoming, readonly)))
+  });
+
+  let (handler, frame): (Handler, Frame) = frame?;
+
+  guard(|| match handler {
+    Handler::Func(func) => func(&ScFuncContext {}),
+    Handler::View(view) => view(&ScViewContext {}),
+  });
+
+  Some(with(|host| host.leave(frame)))
+}
+
+/// Handles requests that are not supported by the host.
+fn unsupported(name: &str) {
+  with(|host| host.fail(format!("unsupported host request: {}", name)));
+}
+
+#[no_mangle]
+extern "C" fn hostGetBytes(obj_id: i32, key_id: i32, _type_id: i32, value: *mut u8, size: i32) -> i32 {
+  let (bytes, child): (Option<Vec<u8>>, bool) = with(|host| {
+    (
+      host.get_bytes(obj_id, key_id),
+      host.store.child(obj_id, key_id).is_some(),
+    )
+  });
+
+  // Child objects exist under their key as well
+  if size < 0 {
+    return if bytes.is_some() || child { 0 } else { -1 };
+  }
+
+  let bytes: Vec<u8> = bytes.unwrap_or_default();
+
+  if size > 0 && !value.is_null() {
+    let count: usize = bytes.len().min(size as usize);
+
+    // SAFETY: WasmLib provides a buffer of at least `size` bytes
+    unsafe {
+      slice::from_raw_parts_mut(value, count).copy_from_slice(&bytes[..count]);
+    }
+  }
+
+  bytes.len() as i32
+}
+
+#[no_mangle]
+extern "C" fn hostGetKeyId(key: *const u8, size: i32) -> i32 {
+  // A negative size marks a key interned from bytes
+  let name: KeyName = if size < 0 {
+    // SAFETY: WasmLib provides a key of `-size - 1` bytes
+    KeyName::Bytes(unsafe { slice::from_raw_parts(key, (-size - 1) as usize) }.to_vec())
+  } else {
+    // SAFETY: WasmLib provides a key of `size` bytes
+    KeyName::String(unsafe { slice::from_raw_parts(key, size as usize) }.to_vec())
+  };
+
+  with(|host| host.store.key_id(name))
+}
+
+#[no_mangle]
+extern "C" fn hostGetObjectId(obj_id: i32, key_id: i32, type_id: i32) -> i32 {
+  with(|host| host.store.object(obj_id, key_id, type_id))
+}
+
+#[no_mangle]
+extern "C" fn hostSetBytes(obj_id: i32, key_id: i32, _type_id: i32, value: *const u8, size: i32) {
+  // A negative size deletes the key
+  let value: Option<&[u8]> = if size < 0 {
+    None
+  } else if size == 0 {
+    Some(&[])
+  } else {
+    // SAFETY: WasmLib provides a value of `size` bytes
+    Some(unsafe { slice::from_raw_parts(value, size as usize) })
+  };
+
+  match with(|host| host.set_bytes(obj_id, key_id, value)) {
+    Some(Request::Call(request)) => call(&request),
+    Some(Request::Deploy(request)) => builtin::deploy(&request),
+    Some(Request::Post) => unsupported("post"),
+    None => {}
+  }
+}
+
+// =============================================================================
+// =============================================================================
+
+pub(crate) fn int64(bytes: &[u8]) -> i64 {
+  let mut buffer: [u8; 8] = [0; 8];
+  let size: usize = bytes.len().min(8);
+
+  buffer[..size].copy_from_slice(&bytes[..size]);
+  i64::from_le_bytes(buffer)
+}
+
+fn string(bytes: Option<&[u8]>) -> String {
+  String::from_utf8_lossy(bytes.unwrap_or_default()).into_owned()
+}
+
+/// Returns the BLAKE2b-256 hash of `data`.
+pub(crate) fn blake2b(data: &[u8]) -> [u8; 32] {
+  let mut output: [u8; 32] = [0; 32];
+  let mut hasher: VarBlake2b = VarBlake2b::new(32).expect("valid output size");
+
+  hasher.update(data);
+  hasher.finalize_variable(|hash| output.copy_from_slice(hash));
+
+  output
+}
+
+/// Returns the hash name of `name` as computed by Wasp.
+pub(crate) fn hname(name: &[u8]) -> u32 {
+  let hash: [u8; 32] = blake2b(name);
+
+  for chunk in hash.chunks(4) {
+    let value: u32 = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+
+    if value != 0 && value != u32::MAX {
+      return value;
+    }
+  }
+
+  0
+}
+
+const BASE58: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+fn base58_encode(data: &[u8]) -> String {
+  let mut digits: Vec<u8> = Vec::new();
+
+  for byte in data {
+    let mut carry: u32 = u32::from(*byte);
+
+    for digit in digits.iter_mut() {
+      carry += u32::from(*digit) << 8;
+      *digit = (carry % 58) as u8;
+      carry /= 58;
+    }
+
+    while carry > 0 {
+      digits.push((carry % 58) as u8);
+      carry /= 58;
+    }
+  }
+
+  let zeros: usize = data.iter().take_while(|byte| **byte == 0).count();
+
+  (0..zeros)
+    .map(|_| b'1')
+    .chain(digits.iter().rev().map(|digit| BASE58[*digit as usize]))
+    .map(char::from)
+    .collect()
+}
+
+fn base58_decode(data: &[u8]) -> Result<Vec<u8>, String> {
+  let mut bytes: Vec<u8> = Vec::new();
+
+  for character in data {
+    let mut carry: u32 = match BASE58.iter().position(|digit| digit == character) {
+      Some(index) => index as u32,
+      None => return Err(format!("invalid base58 character: {}", char::from(*character))),
+    };
+
+    for byte in bytes.iter_mut() {
+      carry += u32::from(*byte) * 58;
+      *byte = carry as u8;
+      carry >>= 8;
+    }
+
+    while carry > 0 {
+      bytes.push(carry as u8);
+      carry >>= 8;
+    }
+  }
+
+  let zeros: usize = data.iter().take_while(|character| **character == b'1').count();
+
+  Ok((0..zeros).map(|_| 0).chain(bytes.into_iter().rev()).collect())
+}
@@ -0,0 +1,281 @@
+use core::marker::PhantomData;
+use std::panic::catch_unwind;
+use std::panic::AssertUnwindSafe;
+use wasmlib::host::OBJ_ID_ROOT;
+use wasmlib::host::TYPE_MAP;
+use wasmlib::ScAddress;
+use wasmlib::ScAgentId;
+use wasmlib::ScBaseContext;
+use wasmlib::ScChainId;
+use wasmlib::ScColor;
+use wasmlib::ScFuncContext;
+use wasmlib::ScHname;
+use wasmlib::ScImmutableMap;
+use wasmlib::ScMutableMap;
+use wasmlib::ScViewContext;
+use wasmlib::KEY_CALLER;
+use wasmlib::KEY_CHAIN_ID;
+use wasmlib::KEY_CHAIN_OWNER_ID;
+use wasmlib::KEY_CONTRACT_CREATOR;
+use wasmlib::KEY_INCOMING;
+use wasmlib::KEY_PARAMS;
+use wasmlib::KEY_RESULTS;
+use wasmlib::KEY_TIMESTAMP;
+
+use crate::testing::builtin;
+use crate::testing::host;
+use crate::testing::host::Handler;
+use crate::testing::host::RawTransfer;
+use crate::testing::host::Snapshot;
+
+/// A transfer of tokens from a contract to an address.
+#[derive(Clone, PartialEq)]
+pub struct Transfer {
+  /// The address receiving the tokens.
+  pub address: ScAddress,
+  /// The amount of tokens transferred per color.
+  pub balances: Vec<(ScColor, i64)>,
+}
+
+impl From<RawTransfer> for Transfer {
+  fn from(other: RawTransfer) -> Self {
+    Self {
+      address: ScAddress::from_bytes(&other.address),
+      balances: other
+        .balances
+        .into_iter()
+        .map(|(color, amount)| (ScColor::from_bytes(&color), amount))
+        .collect(),
+    }
+  }
+}
+
+// =============================================================================
+// =============================================================================
+
+/// An in-memory implementation of the WasmLib host.
+///
+/// Creating a `MockHost` resets the host of the current thread and makes it
+/// execute as the given contract, so functions using [ScFuncContext] and
+/// [ScViewContext] can be called directly from `cargo test`. Each test runs
+/// on its own thread and therefore gets its own host.
+///
+/// # Example
+///
+/// ```
+/// use scarab::testing::MockHost;
+/// use scarab::wasmlib::ScBaseContext;
+/// use scarab::wasmlib::ScFuncContext;
+///
+/// fn increment(ctx: &ScFuncContext) {
+///   let counter = ctx.state().get_int64("counter");
+///   counter.set_value(counter.value() + ctx.params().get_int64("amount").value());
+/// }
+///
+/// let host: MockHost = MockHost::new("counter");
+///
+/// host.params().get_int64("amount").set_value(5);
+/// host.call_func(increment).unwrap();
+///
+/// assert_eq!(host.state().get_int64("counter").value(), 5);
+/// ```
+pub struct MockHost {
+  contract: ScHname,
+  // The host is thread-local
+  marker: PhantomData<*const ()>,
+}
+
+impl MockHost {
+  /// Creates a new host executing as the contract `name`.
+  pub fn new(name: &str) -> Self {
+    host::reset();
+
+    let contract: ScHname = ScHname(host::hname(name.as_bytes()));
+
+    host::with(|host| {
+      let incoming: i32 = host.incoming(0);
+
+      host.set_root(KEY_CHAIN_ID, vec![0; 33]);
+      builtin::install(host, &[0; 37]);
+      host.enter(contract.0, host.agent_id(0), 0, incoming, false);
+    });
+
+    Self {
+      contract,
+      marker: PhantomData,
+    }
+  }
+
+  /// Returns the hname of the contract under test.
+  pub fn contract(&self) -> ScHname {
+    ScHname(self.contract.0)
+  }
+
+  /// Returns the agent id of the contract under test.
+  pub fn contract_id(&self) -> ScAgentId {
+    ScAgentId::from_bytes(&host::with(|host| host.agent_id(self.contract.0)))
+  }
+
+  /// Returns the agent id of the contract `name` on the simulated chain.
+  pub fn agent_id(&self, name: &str) -> ScAgentId {
+    ScAgentId::from_bytes(&host::with(|host| host.agent_id(host::hname(name.as_bytes()))))
+  }
+
+  /// Sets the id of the chain the contract is deployed on.
+  pub fn set_chain_id(&self, chain: &ScChainId) {
+    host::with(|host| host.set_root(KEY_CHAIN_ID, chain.to_bytes().to_vec()));
+  }
+
+  /// Sets the owner of the chain.
+  pub fn set_chain_owner_id(&self, owner: &ScAgentId) {
+    host::with(|host| {
+      host.set_root(KEY_CHAIN_OWNER_ID, owner.to_bytes().to_vec());
+      builtin::set_owner(host, owner.to_bytes());
+    });
+  }
+
+  /// Sets the agent that deployed the contract.
+  pub fn set_contract_creator(&self, creator: &ScAgentId) {
+    host::with(|host| host.set_root(KEY_CONTRACT_CREATOR, creator.to_bytes().to_vec()));
+  }
+
+  /// Sets the agent that sent the next request.
+  pub fn set_caller(&self, caller: &ScAgentId) {
+    host::with(|host| host.set_root(KEY_CALLER, caller.to_bytes().to_vec()));
+  }
+
+  /// Sets the timestamp of the next request.
+  pub fn set_timestamp(&self, timestamp: i64) {
+    host::with(|host| host.set_root(KEY_TIMESTAMP, timestamp.to_le_bytes().to_vec()));
+  }
+
+  /// Sets the balance of `color` held by the contract.
+  pub fn set_balance(&self, color: &ScColor, amount: i64) {
+    host::with(|host| {
+      let account: i32 = host.contract_account(self.contract.0);
+      host.set_balance(account, color.to_bytes(), amount);
+    });
+  }
+
+  /// Returns the balance of `color` held by the contract.
+  pub fn balance(&self, color: &ScColor) -> i64 {
+    host::with(|host| {
+      let account: i32 = host.contract_account(self.contract.0);
+      host.balance(account, color.to_bytes())
+    })
+  }
+
+  /// Sets the amount of `color` sent along with the next request.
+  ///
+  /// Note: incoming tokens are not added to the balances of the contract.
+  pub fn set_incoming(&self, color: &ScColor, amount: i64) {
+    host::with(|host| {
+      let incoming: i32 = host.root(KEY_INCOMING, TYPE_MAP);
+      host.set_balance(incoming, color.to_bytes(), amount);
+    });
+  }
+
+  /// Returns a new, empty parameter map for the next request.
+  pub fn params(&self) -> ScMutableMap {
+    let (params, obj_id): (ScMutableMap, i32) = host::create_map();
+
+    host::with(|host| host.store.link(OBJ_ID_ROOT, KEY_PARAMS.0, obj_id));
+
+    params
+  }
+
+  /// Returns the state of the contract.
+  pub fn state(&self) -> ScMutableMap {
+    ScFuncContext {}.state()
+  }
+
+  /// Returns the results of the last request.
+  pub fn results(&self) -> ScImmutableMap {
+    ScFuncContext {}.results().immutable()
+  }
+
+  /// Registers a func that can be called through `ScFuncContext::call`.
+  pub fn register_func(&self, contract: &str, function: &str, func: fn(&ScFuncContext)) {
+    Self::register(contract, function, Handler::Func(func));
+  }
+
+  /// Registers a view that can be called through `ScFuncContext::call` and
+  /// `ScViewContext::call`.
+  pub fn register_view(&self, contract: &str, function: &str, view: fn(&ScViewContext)) {
+    Self::register(contract, function, Handler::View(view));
+  }
+
+  /// Executes `func` as a request to the contract.
+  ///
+  /// # Errors
+  ///
+  /// Returns the panic message if the request fails. All changes made by the
+  /// request, except for log messages, are rolled back.
+  pub fn call_func(&self, func: fn(&ScFuncContext)) -> Result<(), String> {
+    self.execute(false, || func(&ScFuncContext {}))
+  }
+
+  /// Executes `view` as a request to the contract.
+  ///
+  /// # Errors
+  ///
+  /// Returns the panic message if the request fails, including attempts to
+  /// modify the state of the contract.
+  pub fn call_view(&self, view: fn(&ScViewContext)) -> Result<(), String> {
+    self.execute(true, || view(&ScViewContext {}))
+  }
+
+  /// Returns the messages logged by the contract.
+  pub fn logs(&self) -> Vec<String> {
+    host::with(|host| host.logs.clone())
+  }
+
+  /// Returns the trace messages logged by the contract.
+  pub fn traces(&self) -> Vec<String> {
+    host::with(|host| host.traces.clone())
+  }
+
+  /// Returns the events emitted by the contract.
+  pub fn events(&self) -> Vec<String> {
+    host::with(|host| host.events.iter().map(|event| event.text.clone()).collect())
+  }
+
+  /// Returns the transfers made by the contract.
+  pub fn transfers(&self) -> Vec<Transfer> {
+    host::with(|host| host.transfers.iter().cloned().map(Transfer::from).collect())
+  }
+
+  fn register(contract: &str, function: &str, handler: Handler) {
+    host::with(|host| {
+      let key: (u32, u32) = (host::hname(contract.as_bytes()), host::hname(function.as_bytes()));
+      host.handlers.insert(key, handler);
+    });
+  }
+
+  fn execute(&self, readonly: bool, f: impl FnOnce()) -> Result<(), String> {
+    let snapshot: Snapshot = host::with(|host| {
+      let snapshot: Snapshot = host.snapshot();
+      let results: i32 = host.store.create(TYPE_MAP, false);
+
+      host.panic = None;
+      host.readonly = readonly;
+      host.store.link(OBJ_ID_ROOT, KEY_RESULTS.0, results);
+
+      snapshot
+    });
+
+    let result: Result<(), String> = catch_unwind(AssertUnwindSafe(f)).map_err(host::message);
+
+    host::with(|host| {
+      host.readonly = false;
+
+      match (host.panic.take(), result) {
+        (None, Ok(())) => Ok(()),
+        (Some(error), _) | (None, Err(error)) => {
+          host.restore(snapshot);
+          Err(error)
+        }
+      }
+    })
+  }
+}
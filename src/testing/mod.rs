@@ -0,0 +1,18 @@
+//! An in-memory host for testing contracts with `cargo test`.
+//!
+//! Contract code calls into its host for every access to parameters, results,
+//! state and balances. This module provides an implementation of those host
+//! functions backed by plain Rust collections so contracts can be exercised
+//! natively without a Wasp node.
+//!
+//! Note: a request failing through `ScBaseContext::panic` does not stop the
+//! executing code; any further changes are ignored and the request is rolled
+//! back once it returns. Failures from this crate unwind immediately.
+
+mod builtin;
+mod chain;
+mod host;
+mod mock;
+
+pub use self::chain::*;
+pub use self::mock::*;
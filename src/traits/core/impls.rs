@@ -6,8 +6,19 @@ use crate::traits::core::Proxy;
 use crate::traits::core::ProxyMut;
 use crate::traits::core::Value;
 use crate::types::ScBytes;
+use crate::types::ScImmutableI256;
+use crate::types::ScImmutableI256Array;
+use crate::types::ScImmutableU256;
+use crate::types::ScImmutableU256Array;
 use crate::types::ScInt64;
+use crate::types::ScMutableI256;
+use crate::types::ScMutableI256Array;
+use crate::types::ScMutableU256;
+use crate::types::ScMutableU256Array;
 use crate::types::ScString;
+use crate::types::ScTag;
+use crate::types::I256;
+use crate::types::U256;
 
 macro_rules! impl_ScValue {
   (Hname, $primitive:ty, $getter:ident) => {
@@ -32,6 +43,7 @@ macro_rules! impl_ScValue {
     paste::paste! {
       impl Value for [<Sc $ident>] {
         type Primitive = $primitive;
+        const TAG: ScTag = ScTag::$ident;
         type Proxy = [<ScImmutable $ident>];
         type ProxyMut = [<ScMutable $ident>];
         type Array = [<ScImmutable $ident Array>];
@@ -117,3 +129,50 @@ impl ProxyMut for ScMutableInt64 {
     self.set_value(*value);
   }
 }
+
+// Integers without a WasmLib type are stored as little-endian bytes
+macro_rules! impl_IntValue {
+  ($($ident:ident),+ $(,)*) => {
+    paste::paste! {
+      $(
+        impl Value for $ident {
+          type Primitive = [u8; 32];
+          const TAG: ScTag = ScTag::Bytes;
+          type Proxy = [<ScImmutable $ident>];
+          type ProxyMut = [<ScMutable $ident>];
+          type Array = [<ScImmutable $ident Array>];
+          type ArrayMut = [<ScMutable $ident Array>];
+        }
+
+        impl_ScValue!(@proxy, [<ScImmutable $ident>], $ident);
+        impl_ScValue!(@proxy, [<ScMutable $ident>], $ident);
+        impl_ScValue!(@array, [<ScImmutable $ident Array>], [<ScImmutable $ident>], [<get_ $ident:lower>]);
+        impl_ScValue!(@array, [<ScMutable $ident Array>], [<ScMutable $ident>], [<get_ $ident:lower>]);
+        impl_ScValue!(@array_mut, [<ScMutable $ident Array>]);
+
+        impl ProxyMut for [<ScMutable $ident>] {
+          fn set(&self, value: &Self::Value) {
+            self.set_value(*value);
+          }
+        }
+      )+
+    }
+  };
+}
+
+impl_IntValue!(U256, I256);
+
+// 256-bit integers can be used as map keys, e.g. for token ids
+macro_rules! impl_MapKeyInt {
+  ($($ident:ident),+ $(,)*) => {
+    $(
+      impl MapKey for $ident {
+        fn get_key_id(&self) -> Key32 {
+          host::get_key_id_from_bytes(&self.to_le_bytes())
+        }
+      }
+    )+
+  };
+}
+
+impl_MapKeyInt!(U256, I256);
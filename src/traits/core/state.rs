@@ -1,8 +1,22 @@
 use wasmlib::*;
 
+use crate::traits::core::Array;
+use crate::traits::core::ArrayMut;
+use crate::traits::core::ProxyMut;
+use crate::traits::core::Value;
 use crate::types::ScBytes;
+use crate::types::ScImmutableI256;
+use crate::types::ScImmutableI256Array;
+use crate::types::ScImmutableU256;
+use crate::types::ScImmutableU256Array;
 use crate::types::ScInt64;
+use crate::types::ScMutableI256;
+use crate::types::ScMutableI256Array;
+use crate::types::ScMutableU256;
+use crate::types::ScMutableU256Array;
 use crate::types::ScString;
+use crate::types::I256;
+use crate::types::U256;
 
 /// A common interface for retrieving values from a map.
 pub trait MapGet<Map: ?Sized> {
@@ -79,6 +93,71 @@ impl_MapValue! {
 impl_MapValue!(@set, ScMutableMap, [u8], get_bytes);
 impl_MapValue!(@set, ScMutableMap, str, get_string);
 
+// Integers without a WasmLib type are stored as little-endian bytes
+macro_rules! impl_MapInt {
+  ($($ident:ident),+ $(,)*) => {
+    paste::paste! {
+      $(
+        impl MapGet<ScImmutableMap> for [<ScImmutable $ident>] {
+          fn map_get<Key: MapKey + ?Sized>(map: &ScImmutableMap, key: &Key) -> Self {
+            Self::from_bytes(map.get_bytes(key))
+          }
+        }
+
+        impl MapGet<ScImmutableMap> for [<ScImmutable $ident Array>] {
+          fn map_get<Key: MapKey + ?Sized>(map: &ScImmutableMap, key: &Key) -> Self {
+            Self::from_bytes(map.get_bytes_array(key))
+          }
+        }
+
+        impl MapGet<ScMutableMap> for [<ScMutable $ident>] {
+          fn map_get<Key: MapKey + ?Sized>(map: &ScMutableMap, key: &Key) -> Self {
+            Self::from_bytes(map.get_bytes(key))
+          }
+        }
+
+        impl MapGet<ScMutableMap> for [<ScMutable $ident Array>] {
+          fn map_get<Key: MapKey + ?Sized>(map: &ScMutableMap, key: &Key) -> Self {
+            Self::from_bytes(map.get_bytes_array(key))
+          }
+        }
+
+        impl MapSet<ScMutableMap> for $ident {
+          fn map_set<Key: MapKey + ?Sized>(&self, map: &ScMutableMap, key: &Key) {
+            map.get_bytes(key).set_value(&self.to_le_bytes());
+          }
+        }
+      )+
+    }
+  };
+}
+
+impl_MapInt!(U256, I256);
+
+// Booleans are stored as an `Int64` of `0` or `1`, matching their results
+impl MapSet<ScMutableMap> for bool {
+  fn map_set<Key: MapKey + ?Sized>(&self, map: &ScMutableMap, key: &Key) {
+    map.get_int64(key).set_value(i64::from(*self));
+  }
+}
+
+// Slices are stored as a native array, replacing any existing values
+impl<T> MapSet<ScMutableMap> for [T]
+where
+  T: Value,
+  T::ArrayMut: MapGet<ScMutableMap>,
+{
+  fn map_set<Key: MapKey + ?Sized>(&self, map: &ScMutableMap, key: &Key) {
+    let array: T::ArrayMut = T::ArrayMut::map_get(map, key);
+
+    array.erase();
+
+    for (index, value) in self.iter().enumerate() {
+      array.proxy(index).set(value);
+    }
+  }
+}
+
 // Allow retrieving maps from maps.
 impl MapGet<ScImmutableMap> for ScImmutableMap {
   fn map_get<Key: MapKey + ?Sized>(map: &ScImmutableMap, key: &Key) -> Self {
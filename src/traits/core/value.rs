@@ -2,12 +2,16 @@ use crate::traits::core::Array;
 use crate::traits::core::ArrayMut;
 use crate::traits::core::Proxy;
 use crate::traits::core::ProxyMut;
+use crate::types::ScTag;
 
 /// A common interface for smart contract values.
 pub trait Value: Sized {
   /// The primitive type of this smart contract value.
   type Primitive: ?Sized;
 
+  /// The host type of this smart contract value.
+  const TAG: ScTag;
+
   /// The immutable proxy for this value.
   type Proxy: Proxy<Value = Self>;
 
@@ -5,11 +5,22 @@ use wasmlib::ScImmutableMap;
 use wasmlib::ScMutableMap;
 use wasmlib::ScViewContext;
 
+use crate::panic::fail;
+use crate::storage::open_state;
+use crate::storage::IterableMap;
+use crate::storage::State;
+use crate::storage::StorageMap;
+use crate::storage::StorageVec;
 use crate::traits::core::MapGet;
-use crate::traits::core::MapSet;
 use crate::traits::core::Proxy;
 use crate::traits::core::Value;
 use crate::traits::extension::MapExt;
+use crate::traits::utility::Decode;
+use crate::traits::utility::ParamValue;
+use crate::traits::utility::Params;
+use crate::traits::utility::ResultValue;
+use crate::traits::utility::Results;
+use crate::types::ParamsError;
 
 mod private {
   pub struct Private;
@@ -68,6 +79,44 @@ pub trait ContextExt: private::Sealed {
     self.get_required_param_proxy::<T, U::Proxy>(key).get()
   }
 
+  /// Reads the param `key` as a [ParamValue], failing the request if it is
+  /// missing or can't be represented as `U`.
+  fn get_required_param_value<T, U>(&self, key: &T) -> U
+  where
+    T: MapKey + ?Sized,
+    U: ParamValue,
+  {
+    match U::read_param(&self.params(), key) {
+      Ok(Some(value)) => value,
+      Ok(None) => fail(&format!("missing required param: Key32({})", intkey(key))),
+      Err(error) => fail(&format!("invalid param: Key32({}): {}", intkey(key), error)),
+    }
+  }
+
+  /// Reads the request parameters as `T`, failing the request with every
+  /// missing or invalid parameter.
+  fn get_params<T>(&self) -> T
+  where
+    T: Params,
+  {
+    match T::from_params(&self.params()) {
+      Ok(params) => params,
+      Err(error) => fail(&error.to_string()),
+    }
+  }
+
+  /// Reads the request parameters as `T`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error listing every missing or invalid parameter.
+  fn try_get_params<T>(&self) -> Result<T, ParamsError>
+  where
+    T: Params,
+  {
+    T::from_params(&self.params())
+  }
+
   // ===========================================================================
   // State
   // ===========================================================================
@@ -112,6 +161,39 @@ pub trait ContextExt: private::Sealed {
     self.get_required_state_proxy::<T, U::Proxy>(key).get()
   }
 
+  fn get_state_map<T, K, V>(&self, prefix: &T) -> StorageMap<K, V, Self::State>
+  where
+    T: MapKey + ?Sized,
+    K: MapKey + ?Sized,
+    V: Decode,
+  {
+    StorageMap::open(prefix)
+  }
+
+  fn get_state_iterable_map<T, K, V>(&self, prefix: &T) -> IterableMap<K, V, Self::State>
+  where
+    T: MapKey + ?Sized,
+    K: MapKey + Decode,
+    V: Decode,
+  {
+    IterableMap::open(prefix)
+  }
+
+  fn get_state_vec<T, U>(&self, prefix: &T) -> StorageVec<U, Self::State>
+  where
+    T: MapKey + ?Sized,
+    U: Decode,
+  {
+    StorageVec::open(prefix)
+  }
+
+  fn get_state_struct<T>(&self) -> T::Proxy
+  where
+    T: State<Self::State>,
+  {
+    open_state::<T, Self::State>()
+  }
+
   // ===========================================================================
   // Misc. Helpers
   // ===========================================================================
@@ -119,9 +201,16 @@ pub trait ContextExt: private::Sealed {
   fn result<T, U>(&self, key: &T, value: U)
   where
     T: MapKey + ?Sized,
-    U: MapSet<ScMutableMap>,
+    U: ResultValue,
+  {
+    value.write_result(&self.results(), key);
+  }
+
+  fn set_results<T>(&self, value: &T)
+  where
+    T: Results,
   {
-    value.map_set(&self.results(), key);
+    value.write_results(&self.results());
   }
 
   fn view(&self) -> &ScViewContext;
@@ -21,6 +21,16 @@ pub trait MapExt: private::Sealed {
     U::Proxy::map_get(self, key).get()
   }
 
+  /// Returns the value specified by `key` if present, otherwise [None].
+  fn get_opt<T, U>(&self, key: &T) -> Option<U>
+  where
+    T: MapKey + ?Sized,
+    U: Value,
+    U::Proxy: MapGet<Self>,
+  {
+    U::Proxy::map_get(self, key).opt()
+  }
+
   /// Returns the value proxy specified by `key`.
   fn get<T, U>(&self, key: &T) -> U
   where
@@ -3,6 +3,8 @@ use wasmlib::*;
 
 use crate::panic::Unwrap;
 use crate::traits::core::Value;
+use crate::types::I256;
+use crate::types::U256;
 
 /// Extensions for smart contract [values][Value].
 pub trait ValueExt: Value {
@@ -69,3 +71,22 @@ impl_ValueExt! {
   ScHash,
   ScRequestId,
 }
+
+// Implementation for integers stored as bytes
+macro_rules! impl_ValueExtInt {
+  ($($ident:ident),+ $(,)*) => {
+    $(
+      impl ValueExt for $ident {
+        fn from_primitive(this: Self::Primitive) -> Self {
+          Self::from_le_bytes(this)
+        }
+
+        fn into_primitive(self) -> Self::Primitive {
+          self.to_le_bytes()
+        }
+      }
+    )+
+  };
+}
+
+impl_ValueExtInt!(U256, I256);
@@ -3,6 +3,9 @@ use core::mem;
 
 use crate::traits::core::Proxy;
 use crate::types::ScBytes;
+use crate::types::VarintError;
+use crate::types::I256;
+use crate::types::U256;
 
 mod private {
   pub trait Sealed {}
@@ -31,6 +34,7 @@ pub trait Integer: private::Sealed + Sized {
 macro_rules! impl_Integer {
   ($ident:ident) => {
     impl private::Sealed for $ident {}
+    impl private::Sealed for BigEndian<$ident> {}
 
     impl Integer for $ident {
       type Output = [u8; mem::size_of::<$ident>()];
@@ -43,6 +47,18 @@ macro_rules! impl_Integer {
         self.to_le_bytes()
       }
     }
+
+    impl Integer for BigEndian<$ident> {
+      type Output = [u8; mem::size_of::<$ident>()];
+
+      fn decode(slice: &[u8]) -> Option<Self> {
+        slice.try_into().ok().map($ident::from_be_bytes).map(BigEndian)
+      }
+
+      fn encode(&self) -> Self::Output {
+        self.0.to_be_bytes()
+      }
+    }
   };
   ($($ident:ident),+ $(,)*) => {
     $(
@@ -51,11 +67,109 @@ macro_rules! impl_Integer {
   };
 }
 
+// Note: `usize` and `isize` use the pointer width of the target (4 bytes on wasm32)
 impl_Integer! {
-  u8, u16, u32, u64,
-  i8, i16, i32, i64,
+  u8, u16, u32, u64, u128, usize,
+  i8, i16, i32, i64, i128, isize,
+  U256, I256,
+}
+
+/// An [Integer] encoded as big-endian bytes.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BigEndian<T>(pub T);
+
+impl<T> BigEndian<T> {
+  /// Returns the wrapped integer.
+  pub fn into_inner(self) -> T {
+    self.0
+  }
 }
 
+/// An unsigned [Integer] encoded as an unsigned LEB128 varint.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Uvarint<T>(pub T);
+
+impl<T> Uvarint<T> {
+  /// Returns the wrapped integer.
+  pub fn into_inner(self) -> T {
+    self.0
+  }
+}
+
+macro_rules! impl_Uvarint {
+  ($($ident:ident),+ $(,)*) => {
+    $(
+      impl private::Sealed for Uvarint<$ident> {}
+
+      impl Uvarint<$ident> {
+        /// Decodes a varint from the start of `slice`, returning the value and
+        /// the number of bytes read.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if the input ends before the varint or the value
+        /// does not fit in the integer type.
+        pub fn try_decode_prefix(slice: &[u8]) -> Result<(Self, usize), VarintError> {
+          let mut value: $ident = 0;
+
+          for (index, byte) in slice.iter().enumerate() {
+            let shift: u32 = index as u32 * 7;
+            let bits: $ident = $ident::from(byte & 0x7F);
+
+            if shift >= $ident::BITS || (bits << shift) >> shift != bits {
+              return Err(VarintError::Overflow);
+            }
+
+            value |= bits << shift;
+
+            if byte & 0x80 == 0 {
+              return Ok((Self(value), index + 1));
+            }
+          }
+
+          Err(VarintError::Truncated)
+        }
+
+        /// Decodes a varint that spans all of `slice`.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if the input is truncated, has trailing bytes or
+        /// the value does not fit in the integer type.
+        pub fn try_decode(slice: &[u8]) -> Result<Self, VarintError> {
+          match Self::try_decode_prefix(slice)? {
+            (value, size) if size == slice.len() => Ok(value),
+            _ => Err(VarintError::Trailing),
+          }
+        }
+      }
+
+      impl Integer for Uvarint<$ident> {
+        type Output = Vec<u8>;
+
+        fn decode(slice: &[u8]) -> Option<Self> {
+          Self::try_decode(slice).ok()
+        }
+
+        fn encode(&self) -> Self::Output {
+          let mut output: Vec<u8> = Vec::new();
+          let mut value: $ident = self.0;
+
+          while value >= 0x80 {
+            output.push(value as u8 | 0x80);
+            value >>= 7;
+          }
+
+          output.push(value as u8);
+          output
+        }
+      }
+    )+
+  };
+}
+
+impl_Uvarint!(u8, u16, u32, u64, u128, usize);
+
 /// An interface for converting values to [integers][Integer].
 pub trait ToInteger {
   fn to_integer<I: Integer>(&self) -> Option<I>;
@@ -78,3 +192,93 @@ impl<T: Proxy<Value = ScBytes>> ToInteger for T {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_uvarint_roundtrip() {
+    assert_eq!(Uvarint(0_u64).encode(), [0x00]);
+    assert_eq!(Uvarint(127_u64).encode(), [0x7F]);
+    assert_eq!(Uvarint(300_u64).encode(), [0xAC, 0x02]);
+    assert_eq!(Uvarint(u64::MAX).encode().len(), 10);
+    assert_eq!(Uvarint(u128::MAX).encode().len(), 19);
+
+    for value in [0, 1, 127, 128, 16_383, 16_384, u128::MAX / 3, u128::MAX] {
+      assert_eq!(Uvarint::<u128>::decode(&Uvarint(value).encode()), Some(Uvarint(value)));
+    }
+
+    assert_eq!(
+      Uvarint::<u64>::try_decode_prefix(&[0xAC, 0x02, 0xFF]),
+      Ok((Uvarint(300), 2))
+    );
+  }
+
+  #[test]
+  fn test_uvarint_truncated() {
+    assert_eq!(Uvarint::<u64>::try_decode(&[]), Err(VarintError::Truncated));
+    assert_eq!(Uvarint::<u64>::try_decode(&[0xAC]), Err(VarintError::Truncated));
+    assert_eq!(Uvarint::<u64>::try_decode(&[0xFF; 3]), Err(VarintError::Truncated));
+    assert_eq!(
+      Uvarint::<u64>::try_decode(&[0xAC, 0x02, 0x00]),
+      Err(VarintError::Trailing)
+    );
+    assert_eq!(Uvarint::<u64>::decode(&[0xAC]), None);
+  }
+
+  #[test]
+  fn test_uvarint_overflow() {
+    assert_eq!(Uvarint::<u8>::try_decode(&[0xFF, 0x01]), Ok(Uvarint(u8::MAX)));
+    assert_eq!(Uvarint::<u8>::try_decode(&[0x80, 0x02]), Err(VarintError::Overflow));
+    assert_eq!(Uvarint::<u8>::try_decode(&[0xAC, 0x02]), Err(VarintError::Overflow));
+
+    let max: Vec<u8> = Uvarint(u64::MAX).encode();
+
+    assert_eq!(Uvarint::<u64>::try_decode(&max), Ok(Uvarint(u64::MAX)));
+    assert_eq!(Uvarint::<u32>::try_decode(&max), Err(VarintError::Overflow));
+
+    // The last byte of a `u64` only holds a single bit
+    assert_eq!(
+      Uvarint::<u64>::try_decode(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02]),
+      Err(VarintError::Overflow)
+    );
+
+    // Padding past the width of the type is rejected even if the bits are zero
+    assert_eq!(
+      Uvarint::<u8>::try_decode(&[0x81, 0x80, 0x00]),
+      Err(VarintError::Overflow)
+    );
+  }
+
+  #[test]
+  fn test_big_endian() {
+    let bytes: ScBytes = vec![0x01, 0x02, 0x03, 0x04];
+
+    assert_eq!(bytes.to_integer::<BigEndian<u32>>(), Some(BigEndian(0x0102_0304)));
+    assert_eq!(bytes.to_integer::<u32>(), Some(0x0403_0201));
+    assert_eq!(bytes.to_integer::<BigEndian<u16>>(), None);
+    assert_eq!(BigEndian(0x0102_u16).encode(), [0x01, 0x02]);
+    assert_eq!(BigEndian(-2_i16).encode(), [0xFF, 0xFE]);
+    assert_eq!(BigEndian(0x0102_u16).into_inner(), 0x0102);
+
+    let value: U256 = U256::from(0x0102_u32);
+
+    assert_eq!(BigEndian(value).encode(), value.to_be_bytes());
+    assert_eq!(BigEndian::<U256>::decode(&value.to_be_bytes()), Some(BigEndian(value)));
+    assert_eq!(
+      BigEndian::<I256>::decode_vec(BigEndian(I256::from(-5_i8)).encode_vec()),
+      Some(BigEndian(I256::from(-5_i8)))
+    );
+  }
+
+  #[test]
+  fn test_integer_widths() {
+    let bytes: ScBytes = u128::MAX.encode_vec();
+
+    assert_eq!(bytes.to_integer::<u128>(), Some(u128::MAX));
+    assert_eq!(bytes.to_integer::<i128>(), Some(-1));
+    assert_eq!(bytes.to_integer::<u64>(), None);
+    assert_eq!(bytes.to_integer::<Uvarint<u64>>(), None);
+  }
+}
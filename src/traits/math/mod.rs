@@ -2,6 +2,8 @@
 
 mod danger;
 mod integer;
+mod safe;
 
 pub use self::danger::*;
 pub use self::integer::*;
+pub use self::safe::*;
@@ -0,0 +1,235 @@
+use core::fmt::Display;
+use wasmlib::ScMutableInt64;
+
+use crate::panic::fail;
+use crate::traits::core::Proxy;
+use crate::traits::core::ProxyMut;
+use crate::types::MathError;
+use crate::types::ScMutableI256;
+use crate::types::ScMutableU256;
+
+/// Checked math operations on mutable value proxies.
+pub trait SafeMath: ProxyMut {
+  /// Adds `amount` to the stored value, returning the new value.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error and leaves the value unchanged if the result is out of
+  /// range.
+  fn checked_add_value(&self, amount: Self::Value) -> Result<Self::Value, MathError>;
+
+  /// Subtracts `amount` from the stored value, returning the new value.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error and leaves the value unchanged if the result is out of
+  /// range.
+  fn checked_sub_value(&self, amount: Self::Value) -> Result<Self::Value, MathError>;
+
+  /// Adds `amount` to the stored value, failing the request if the result is
+  /// out of range.
+  fn add_value(&self, amount: Self::Value) -> Self::Value
+  where
+    Self::Value: Copy + Display,
+  {
+    match self.checked_add_value(amount) {
+      Ok(value) => value,
+      Err(error) => fail(&format!("{}: {} + {}", error, self.get(), amount)),
+    }
+  }
+
+  /// Subtracts `amount` from the stored value, failing the request if the
+  /// result is out of range.
+  fn sub_value(&self, amount: Self::Value) -> Self::Value
+  where
+    Self::Value: Copy + Display,
+  {
+    match self.checked_sub_value(amount) {
+      Ok(value) => value,
+      Err(error) => fail(&format!("{}: {} - {}", error, self.get(), amount)),
+    }
+  }
+}
+
+macro_rules! impl_SafeMath {
+  ($($ident:ident),+ $(,)*) => {
+    $(
+      impl SafeMath for $ident {
+        fn checked_add_value(&self, amount: Self::Value) -> Result<Self::Value, MathError> {
+          let value: Self::Value = self.get().checked_add(amount).ok_or_else(|| {
+            if amount < Self::Value::default() {
+              MathError::Underflow
+            } else {
+              MathError::Overflow
+            }
+          })?;
+
+          self.set(&value);
+
+          Ok(value)
+        }
+
+        fn checked_sub_value(&self, amount: Self::Value) -> Result<Self::Value, MathError> {
+          let value: Self::Value = self.get().checked_sub(amount).ok_or_else(|| {
+            if amount < Self::Value::default() {
+              MathError::Overflow
+            } else {
+              MathError::Underflow
+            }
+          })?;
+
+          self.set(&value);
+
+          Ok(value)
+        }
+      }
+    )+
+  };
+}
+
+impl_SafeMath!(ScMutableInt64, ScMutableU256, ScMutableI256);
+
+/// Moves `amount` from the value of `from` to the value of `to`.
+///
+/// Both values are left unchanged if the transfer fails.
+///
+/// # Errors
+///
+/// Returns an error if `amount` is negative, `from` holds less than `amount`
+/// or the value of `to` would overflow.
+pub fn try_transfer_between<T>(from: &T, to: &T, amount: T::Value) -> Result<(), MathError>
+where
+  T: SafeMath,
+  T::Value: Copy + Default + PartialOrd,
+{
+  if amount < T::Value::default() {
+    return Err(MathError::Negative);
+  }
+
+  let balance: T::Value = from.get();
+
+  if balance < amount {
+    return Err(MathError::Insufficient);
+  }
+
+  from.checked_sub_value(amount)?;
+
+  // `to` is read after `from` is updated in case both proxies refer to the same value
+  if let Err(error) = to.checked_add_value(amount) {
+    from.set(&balance);
+    return Err(error);
+  }
+
+  Ok(())
+}
+
+/// Moves `amount` from the value of `from` to the value of `to`, failing the
+/// request if the transfer fails.
+pub fn transfer_between<T>(from: &T, to: &T, amount: T::Value)
+where
+  T: SafeMath,
+  T::Value: Copy + Default + PartialOrd + Display,
+{
+  if let Err(error) = try_transfer_between(from, to, amount) {
+    fail(&format!("{}: cannot transfer {} from {}", error, amount, from.get()));
+  }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+  use wasmlib::ScMutableMap;
+
+  use super::*;
+  use crate::testing::MockHost;
+  use crate::traits::extension::MapExt;
+  use crate::types::U256;
+
+  fn balances(host: &MockHost) -> (ScMutableInt64, ScMutableInt64) {
+    let state: ScMutableMap = host.state();
+
+    (state.get_int64("alice"), state.get_int64("bob"))
+  }
+
+  #[test]
+  fn test_checked_values() {
+    let host: MockHost = MockHost::new("test");
+    let (value, _): (ScMutableInt64, ScMutableInt64) = balances(&host);
+
+    assert_eq!(value.checked_add_value(10), Ok(10));
+    assert_eq!(value.checked_sub_value(11), Ok(-1));
+    assert_eq!(value.checked_add_value(-1), Ok(-2));
+    assert_eq!(value.checked_sub_value(-2), Ok(0));
+
+    value.set_value(i64::MIN);
+
+    assert_eq!(value.checked_sub_value(1), Err(MathError::Underflow));
+    assert_eq!(value.checked_add_value(-1), Err(MathError::Underflow));
+    assert_eq!(value.value(), i64::MIN);
+
+    value.set_value(i64::MAX);
+
+    assert_eq!(value.checked_add_value(1), Err(MathError::Overflow));
+    assert_eq!(value.checked_sub_value(-1), Err(MathError::Overflow));
+    assert_eq!(value.value(), i64::MAX);
+
+    let value: ScMutableU256 = host.state().get("supply");
+
+    assert_eq!(value.checked_sub_value(U256::ONE), Err(MathError::Underflow));
+    assert_eq!(value.checked_add_value(U256::MAX), Ok(U256::MAX));
+    assert_eq!(value.checked_add_value(U256::ONE), Err(MathError::Overflow));
+    assert_eq!(value.get(), U256::MAX);
+  }
+
+  #[test]
+  fn test_transfer_between() {
+    let host: MockHost = MockHost::new("test");
+    let (alice, bob): (ScMutableInt64, ScMutableInt64) = balances(&host);
+
+    alice.set_value(10);
+
+    assert_eq!(try_transfer_between(&alice, &bob, 4), Ok(()));
+    assert_eq!((alice.value(), bob.value()), (6, 4));
+    assert_eq!(try_transfer_between(&alice, &bob, 0), Ok(()));
+    assert_eq!(try_transfer_between(&alice, &bob, 7), Err(MathError::Insufficient));
+    assert_eq!(try_transfer_between(&alice, &bob, -1), Err(MathError::Negative));
+    assert_eq!((alice.value(), bob.value()), (6, 4));
+  }
+
+  #[test]
+  fn test_transfer_between_self() {
+    let host: MockHost = MockHost::new("test");
+    let (alice, _): (ScMutableInt64, ScMutableInt64) = balances(&host);
+
+    alice.set_value(i64::MAX);
+
+    // The full balance can be moved even though adding it first would overflow
+    assert_eq!(try_transfer_between(&alice, &alice, i64::MAX), Ok(()));
+    assert_eq!(alice.value(), i64::MAX);
+    assert_eq!(try_transfer_between(&alice, &alice, 1), Ok(()));
+    assert_eq!(alice.value(), i64::MAX);
+  }
+
+  #[test]
+  fn test_transfer_between_rollback() {
+    let host: MockHost = MockHost::new("test");
+    let (alice, bob): (ScMutableInt64, ScMutableInt64) = balances(&host);
+
+    alice.set_value(6);
+    bob.set_value(i64::MAX - 1);
+
+    assert_eq!(try_transfer_between(&alice, &bob, 2), Err(MathError::Overflow));
+    assert_eq!((alice.value(), bob.value()), (6, i64::MAX - 1));
+    assert_eq!(try_transfer_between(&alice, &bob, 1), Ok(()));
+    assert_eq!((alice.value(), bob.value()), (5, i64::MAX));
+  }
+
+  #[test]
+  #[should_panic(expected = "insufficient balance: cannot transfer 7 from 6")]
+  fn test_transfer_between_fails() {
+    let host: MockHost = MockHost::new("test");
+    let (alice, bob): (ScMutableInt64, ScMutableInt64) = balances(&host);
+
+    alice.set_value(6);
+    transfer_between(&alice, &bob, 7);
+  }
+}
@@ -1,14 +1,62 @@
+use core::convert::TryInto;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use wasmlib::*;
 
+use crate::types::check;
+use crate::types::Decimal;
+use crate::types::DecodeError;
+use crate::types::DecodeErrorKind;
+use crate::types::Decoder;
+use crate::types::Encoder;
 use crate::types::ScBytes;
 use crate::types::ScInt64;
 use crate::types::ScString;
+use crate::types::I256;
+use crate::types::U256;
 
+/// A common interface for values that can be encoded as bytes.
+///
+/// The derive macro supports the following attributes:
+///
+/// * `#[scarab(bound = "...")]` - replaces the inferred `T: Encode` bounds.
+/// * `#[scarab(version = N)]` - encodes the container as a versioned record.
+/// * `#[scarab(tag = N)]` - sets the tag written before an enum variant.
+/// * `#[scarab(skip)]` - excludes a field from the encoding.
+/// * `#[scarab(with = "module")]` - encodes a field with `module::encode`.
+///
+/// # Wire Format
+///
+/// Values are written with [Encoder] in the format of the wasmlib
+/// [BytesEncoder] so they can be read in order by the `BytesDecoder` of the Go
+/// host:
+///
+/// * `ScInt64` - a signed LEB128 integer.
+/// * `ScBytes`, `ScString` and the fixed-size wasmlib types - an `Int64`
+///   length followed by the raw bytes.
+/// * `U256`/`I256` - 32 little-endian bytes, without a length prefix. This is
+///   the same as the value stored in a map.
+/// * `Decimal<SCALE>` - the same as the raw `I256` value.
+/// * `bool` - an `Int64` of `0` or `1`.
+/// * `Option<T>` - a `bool` presence flag, followed by the value if present.
+/// * `Vec<T>`/`[T]` - an `Int64` length followed by each element.
+/// * `[T; N]` - each element in order, without a length prefix.
+/// * `(A, B, ...)` - each element in order; implemented for up to 12 elements.
+/// * `Box<T>` - the same as `T`.
+/// * `BTreeMap<K, V>` - an `Int64` length followed by each key and value in
+///   ascending key order.
+/// * `BTreeSet<T>` - an `Int64` length followed by each element in ascending
+///   order.
+///
+/// Enums derived with this trait write the `Int64` tag of the variant
+/// followed by its fields; structs write their fields in declaration order.
+/// Versioned records write their `Int64` version followed by their contents
+/// as `ScBytes`, which allows fields to be added in later versions.
 pub trait Encode {
-  fn encode(&self, encoder: &mut BytesEncoder);
+  fn encode(&self, encoder: &mut Encoder);
 
   fn to_bytes(&self) -> Vec<u8> {
-    let mut encoder: BytesEncoder = BytesEncoder::new();
+    let mut encoder: Encoder = Encoder::new();
 
     self.encode(&mut encoder);
 
@@ -20,16 +68,63 @@ impl<'a, T> Encode for &'a T
 where
   T: Encode,
 {
-  fn encode(&self, encoder: &mut BytesEncoder) {
+  fn encode(&self, encoder: &mut Encoder) {
     (**self).encode(encoder);
   }
 }
 
+/// A common interface for values that can be decoded from bytes.
+///
+/// In addition to the attributes supported by [Encode], the derive macro
+/// supports the following field attributes:
+///
+/// * `#[scarab(skip)]` - sets the field to `Default::default()`.
+/// * `#[scarab(with = "module")]` - decodes a field with `module::decode`.
+/// * `#[scarab(default)]`/`#[scarab(default = "path")]` - uses a default value
+///   if the field is missing from the end of the record.
+/// * `#[scarab(since = N)]` - marks a field as added in version `N`; records
+///   of earlier versions use the default value. Requires a versioned record.
+///
+/// `default` and `since` fields must be trailing. Records of a newer version
+/// than the container fail to decode.
+///
+/// Containers without a version can still gain trailing `default` fields,
+/// which are missing if the input ends before them. This is only reliable if
+/// the value is the last thing in its input, such as a value stored on its
+/// own under a state key; when decoding a value followed by other data, the
+/// following data is read as the new fields. Types that are nested in other
+/// values or collections should be versioned records instead. Adding a
+/// version changes the encoding, so existing data of an unversioned type must
+/// be rewritten, e.g. by decoding it as the old type and encoding it again.
 pub trait Decode: Sized {
-  fn decode(decoder: &mut BytesDecoder<'_>) -> Self;
+  fn decode(decoder: &mut Decoder<'_>) -> Self;
 
   fn from_bytes(bytes: &[u8]) -> Self {
-    Self::decode(&mut BytesDecoder::new(bytes))
+    Self::decode(&mut Decoder::new(bytes))
+  }
+}
+
+/// A common interface for values that can be decoded from untrusted bytes.
+///
+/// This is the fallible counterpart to [Decode] and is implemented by the
+/// same derive macro. Custom `decode` bounds apply to both implementations
+/// and modules used with `#[scarab(with = "module")]` must provide a
+/// `try_decode` function in addition to `decode`.
+pub trait TryDecode: Sized {
+  /// Decodes a value from the given decoder.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the input is truncated or malformed.
+  fn try_decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError>;
+
+  /// Decodes a value from a slice of bytes.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the input is truncated or malformed.
+  fn try_from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+    Self::try_decode(&mut Decoder::new(bytes))
   }
 }
 
@@ -40,21 +135,29 @@ pub trait Decode: Sized {
 macro_rules! impl_Encode {
   (@encode, $ident:ident, $fn:ident) => {
     impl Encode for $ident {
-      fn encode(&self, encoder: &mut BytesEncoder) {
+      fn encode(&self, encoder: &mut Encoder) {
         encoder.$fn(self);
       }
     }
   };
   (@decode, $ident:ident, $fn:ident) => {
     impl Decode for $ident {
-      fn decode(decoder: &mut BytesDecoder<'_>) -> Self {
+      fn decode(decoder: &mut Decoder<'_>) -> Self {
         decoder.$fn()
       }
     }
+
+    paste::paste! {
+      impl TryDecode for $ident {
+        fn try_decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+          decoder.[<try_ $fn>]()
+        }
+      }
+    }
   };
   (ScInt64, $fn:ident) => {
     impl Encode for ScInt64 {
-      fn encode(&self, encoder: &mut BytesEncoder) {
+      fn encode(&self, encoder: &mut Encoder) {
         encoder.$fn(*self);
       }
     }
@@ -62,10 +165,16 @@ macro_rules! impl_Encode {
   };
   (ScBytes, $fn:ident) => {
     impl Decode for ScBytes {
-      fn decode(decoder: &mut BytesDecoder<'_>) -> Self {
+      fn decode(decoder: &mut Decoder<'_>) -> Self {
         decoder.$fn().to_vec()
       }
     }
+
+    impl TryDecode for ScBytes {
+      fn try_decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        decoder.try_bytes().map(<[u8]>::to_vec)
+      }
+    }
     impl_Encode!(@encode, ScBytes, $fn);
   };
   ($ident:ident, $fn:ident) => {
@@ -94,14 +203,814 @@ impl_Encode! {
 
 // Add support for encoding byte slices
 impl Encode for [u8] {
-  fn encode(&self, encoder: &mut BytesEncoder) {
+  fn encode(&self, encoder: &mut Encoder) {
     encoder.bytes(self);
   }
 }
 
 // Add support for encoding string slices
 impl Encode for str {
-  fn encode(&self, encoder: &mut BytesEncoder) {
+  fn encode(&self, encoder: &mut Encoder) {
     encoder.string(self);
   }
 }
+
+// Add support for 256-bit integers
+macro_rules! impl_EncodeInt {
+  ($(($ident:ident, $fn:ident),)+) => {
+    $(
+      impl Encode for $ident {
+        fn encode(&self, encoder: &mut Encoder) {
+          encoder.$fn(self);
+        }
+      }
+
+      impl_Encode!(@decode, $ident, $fn);
+    )+
+  };
+}
+
+impl_EncodeInt! {
+  (U256, u256),
+  (I256, i256),
+}
+
+// Add support for fixed-point decimals
+impl<const SCALE: u32> Encode for Decimal<SCALE> {
+  fn encode(&self, encoder: &mut Encoder) {
+    self.raw().encode(encoder);
+  }
+}
+
+impl<const SCALE: u32> Decode for Decimal<SCALE> {
+  fn decode(decoder: &mut Decoder<'_>) -> Self {
+    Self::from_raw(decoder.i256())
+  }
+}
+
+impl<const SCALE: u32> TryDecode for Decimal<SCALE> {
+  fn try_decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+    decoder.try_i256().map(Self::from_raw)
+  }
+}
+
+// =============================================================================
+// Implementations for Std Types
+// =============================================================================
+
+impl Encode for bool {
+  fn encode(&self, encoder: &mut Encoder) {
+    encoder.int64(i64::from(*self));
+  }
+}
+
+impl Decode for bool {
+  fn decode(decoder: &mut Decoder<'_>) -> Self {
+    decoder.bool()
+  }
+}
+
+impl TryDecode for bool {
+  fn try_decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+    decoder.try_bool()
+  }
+}
+
+impl<T> Encode for Option<T>
+where
+  T: Encode,
+{
+  fn encode(&self, encoder: &mut Encoder) {
+    self.is_some().encode(encoder);
+
+    if let Some(value) = self {
+      value.encode(encoder);
+    }
+  }
+}
+
+impl<T> Decode for Option<T>
+where
+  T: Decode,
+{
+  fn decode(decoder: &mut Decoder<'_>) -> Self {
+    if decoder.bool() {
+      Some(T::decode(decoder))
+    } else {
+      None
+    }
+  }
+}
+
+impl<T> TryDecode for Option<T>
+where
+  T: TryDecode,
+{
+  fn try_decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+    if decoder.try_bool()? {
+      T::try_decode(decoder).map(Some)
+    } else {
+      Ok(None)
+    }
+  }
+}
+
+impl<T> Encode for Box<T>
+where
+  T: Encode + ?Sized,
+{
+  fn encode(&self, encoder: &mut Encoder) {
+    (**self).encode(encoder);
+  }
+}
+
+impl<T> Decode for Box<T>
+where
+  T: Decode,
+{
+  fn decode(decoder: &mut Decoder<'_>) -> Self {
+    Box::new(T::decode(decoder))
+  }
+}
+
+impl<T> TryDecode for Box<T>
+where
+  T: TryDecode,
+{
+  fn try_decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+    T::try_decode(decoder).map(Box::new)
+  }
+}
+
+// Note: `[u8]` and `Vec<u8>` are encoded as `ScBytes` which shares this layout
+impl<T> Encode for [T]
+where
+  T: Encode,
+{
+  fn encode(&self, encoder: &mut Encoder) {
+    encoder.int64(self.len() as i64);
+
+    for item in self {
+      item.encode(encoder);
+    }
+  }
+}
+
+impl<T> Encode for Vec<T>
+where
+  T: Encode,
+{
+  fn encode(&self, encoder: &mut Encoder) {
+    self.as_slice().encode(encoder);
+  }
+}
+
+impl<T> Decode for Vec<T>
+where
+  T: Decode,
+{
+  fn decode(decoder: &mut Decoder<'_>) -> Self {
+    let size: usize = decoder.length("Vec");
+    let mut output: Self = Self::with_capacity(size.min(decoder.remaining()));
+
+    for _ in 0..size {
+      output.push(T::decode(decoder));
+    }
+
+    output
+  }
+}
+
+impl<T> TryDecode for Vec<T>
+where
+  T: TryDecode,
+{
+  fn try_decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+    let size: usize = decoder.try_length("Vec")?;
+    let mut output: Self = Self::with_capacity(size.min(decoder.remaining()));
+
+    for _ in 0..size {
+      output.push(T::try_decode(decoder)?);
+    }
+
+    Ok(output)
+  }
+}
+
+impl<T, const N: usize> Encode for [T; N]
+where
+  T: Encode,
+{
+  fn encode(&self, encoder: &mut Encoder) {
+    for item in self {
+      item.encode(encoder);
+    }
+  }
+}
+
+impl<T, const N: usize> Decode for [T; N]
+where
+  T: Decode,
+{
+  fn decode(decoder: &mut Decoder<'_>) -> Self {
+    let output: Vec<T> = (0..N).map(|_| T::decode(decoder)).collect();
+
+    match output.try_into() {
+      Ok(output) => output,
+      Err(_) => unreachable!(),
+    }
+  }
+}
+
+impl<T, const N: usize> TryDecode for [T; N]
+where
+  T: TryDecode,
+{
+  fn try_decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+    let output: Vec<T> = (0..N).map(|_| T::try_decode(decoder)).collect::<Result<_, _>>()?;
+
+    match output.try_into() {
+      Ok(output) => Ok(output),
+      Err(_) => unreachable!(),
+    }
+  }
+}
+
+impl<K, V> Encode for BTreeMap<K, V>
+where
+  K: Encode,
+  V: Encode,
+{
+  fn encode(&self, encoder: &mut Encoder) {
+    encoder.int64(self.len() as i64);
+
+    for (key, value) in self {
+      key.encode(encoder);
+      value.encode(encoder);
+    }
+  }
+}
+
+impl<K, V> Decode for BTreeMap<K, V>
+where
+  K: Decode + Ord,
+  V: Decode,
+{
+  fn decode(decoder: &mut Decoder<'_>) -> Self {
+    let size: usize = decoder.length("BTreeMap");
+    let mut output: Self = Self::new();
+
+    for _ in 0..size {
+      let offset: usize = decoder.offset();
+      let key: K = K::decode(decoder);
+
+      check(ordered("BTreeMap", output.keys().next_back(), &key, offset));
+
+      output.insert(key, V::decode(decoder));
+    }
+
+    output
+  }
+}
+
+impl<K, V> TryDecode for BTreeMap<K, V>
+where
+  K: TryDecode + Ord,
+  V: TryDecode,
+{
+  fn try_decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+    let size: usize = decoder.try_length("BTreeMap")?;
+    let mut output: Self = Self::new();
+
+    for _ in 0..size {
+      let offset: usize = decoder.offset();
+      let key: K = K::try_decode(decoder)?;
+
+      ordered("BTreeMap", output.keys().next_back(), &key, offset)?;
+
+      output.insert(key, V::try_decode(decoder)?);
+    }
+
+    Ok(output)
+  }
+}
+
+impl<T> Encode for BTreeSet<T>
+where
+  T: Encode,
+{
+  fn encode(&self, encoder: &mut Encoder) {
+    encoder.int64(self.len() as i64);
+
+    for item in self {
+      item.encode(encoder);
+    }
+  }
+}
+
+impl<T> Decode for BTreeSet<T>
+where
+  T: Decode + Ord,
+{
+  fn decode(decoder: &mut Decoder<'_>) -> Self {
+    let size: usize = decoder.length("BTreeSet");
+    let mut output: Self = Self::new();
+
+    for _ in 0..size {
+      let offset: usize = decoder.offset();
+      let item: T = T::decode(decoder);
+
+      check(ordered("BTreeSet", output.iter().next_back(), &item, offset));
+
+      output.insert(item);
+    }
+
+    output
+  }
+}
+
+impl<T> TryDecode for BTreeSet<T>
+where
+  T: TryDecode + Ord,
+{
+  fn try_decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+    let size: usize = decoder.try_length("BTreeSet")?;
+    let mut output: Self = Self::new();
+
+    for _ in 0..size {
+      let offset: usize = decoder.offset();
+      let item: T = T::try_decode(decoder)?;
+
+      ordered("BTreeSet", output.iter().next_back(), &item, offset)?;
+
+      output.insert(item);
+    }
+
+    Ok(output)
+  }
+}
+
+// Ensures keys are strictly ascending so every map has a single encoding
+fn ordered<K>(expected: &'static str, last: Option<&K>, key: &K, offset: usize) -> Result<(), DecodeError>
+where
+  K: Ord,
+{
+  match last {
+    Some(last) if last >= key => Err(DecodeError::new(offset, expected, DecodeErrorKind::Unordered)),
+    _ => Ok(()),
+  }
+}
+
+macro_rules! impl_Tuple {
+  ($($ident:ident)+) => {
+    paste::paste! {
+      impl<$($ident),+> Encode for ($($ident,)+)
+      where
+        $($ident: Encode,)+
+      {
+        fn encode(&self, encoder: &mut Encoder) {
+          let ($([<$ident:lower>],)+) = self;
+          $([<$ident:lower>].encode(encoder);)+
+        }
+      }
+    }
+
+    impl<$($ident),+> Decode for ($($ident,)+)
+    where
+      $($ident: Decode,)+
+    {
+      fn decode(decoder: &mut Decoder<'_>) -> Self {
+        ($($ident::decode(decoder),)+)
+      }
+    }
+
+    impl<$($ident),+> TryDecode for ($($ident,)+)
+    where
+      $($ident: TryDecode,)+
+    {
+      fn try_decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        Ok(($($ident::try_decode(decoder)?,)+))
+      }
+    }
+  };
+  ($(($($ident:ident)+),)+) => {
+    $(
+      impl_Tuple!($($ident)+);
+    )+
+  };
+}
+
+impl_Tuple! {
+  (A),
+  (A B),
+  (A B C),
+  (A B C D),
+  (A B C D E),
+  (A B C D E F),
+  (A B C D E F G),
+  (A B C D E F G H),
+  (A B C D E F G H I),
+  (A B C D E F G H I J),
+  (A B C D E F G H I J K),
+  (A B C D E F G H I J K L),
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::Decode;
+  use crate::Encode;
+
+  #[derive(Debug, PartialEq, Encode, Decode)]
+  enum Side {
+    Buy,
+    Sell = 5,
+    Hold,
+    #[scarab(tag = -3)]
+    Cancel,
+  }
+
+  #[derive(Debug, PartialEq, Encode, Decode)]
+  #[repr(i64)]
+  enum Wide {
+    Min = -9223372036854775808,
+    Max = 9223372036854775807,
+  }
+
+  #[derive(Debug, PartialEq, Encode, Decode)]
+  enum Message {
+    Ping,
+    Text(i64, String),
+    Move { from: i64, to: Vec<u8> },
+  }
+
+  #[derive(Encode, Decode)]
+  enum Never {}
+
+  #[derive(Debug, PartialEq, Encode, Decode)]
+  struct Entry<K, V: Clone>(K, V)
+  where
+    K: Ord;
+
+  #[derive(Debug, PartialEq, Encode, Decode)]
+  enum Choice<T> {
+    Empty,
+    Value(T),
+    Pair { first: T, second: Option<T> },
+  }
+
+  // `Wrapper<T>` is encoded through `Box<T>`, so the inferred bounds suffice
+  #[derive(Debug, PartialEq, Encode, Decode)]
+  struct Wrapper<T> {
+    inner: Box<T>,
+  }
+
+  #[derive(Debug, PartialEq, Encode, Decode)]
+  #[scarab(bound = "T: Encode + Decode + TryDecode + Copy")]
+  struct Custom<T> {
+    value: T,
+  }
+
+  #[derive(Debug, PartialEq, Encode, Decode)]
+  #[scarab(bound(encode = "T: Encode", decode = "T: Decode + TryDecode"))]
+  struct Split<T> {
+    values: Vec<T>,
+  }
+
+  #[derive(Debug, PartialEq, Encode, Decode)]
+  #[scarab(version = 1)]
+  struct RecordV1 {
+    value: i64,
+  }
+
+  #[derive(Debug, PartialEq, Encode, Decode)]
+  #[scarab(version = 2)]
+  struct RecordV2 {
+    value: i64,
+    #[scarab(since = 2)]
+    label: String,
+    #[scarab(default = "seven")]
+    limit: i64,
+  }
+
+  fn seven() -> i64 {
+    7
+  }
+
+  #[derive(Debug, PartialEq, Encode, Decode)]
+  struct AccountV1 {
+    balance: i64,
+  }
+
+  // Unversioned, so `frozen` is only absent at the end of the input
+  #[derive(Debug, PartialEq, Encode, Decode)]
+  struct AccountV2 {
+    balance: i64,
+    #[scarab(default)]
+    frozen: bool,
+    #[scarab(default = "seven")]
+    limit: i64,
+  }
+
+  #[derive(Debug, Default, PartialEq)]
+  struct Cache(i64);
+
+  struct Marker;
+
+  // Neither `Cache` nor `Marker` implement `Encode` or `Decode`
+  #[derive(Debug, PartialEq, Encode, Decode)]
+  struct Cached<T, M> {
+    value: i64,
+    #[scarab(skip)]
+    cache: T,
+    #[scarab(skip)]
+    marker: core::marker::PhantomData<M>,
+  }
+
+  #[derive(Debug, PartialEq, Encode, Decode)]
+  enum Formatted {
+    Text(#[scarab(with = "as_string")] i64),
+    Skipped(#[scarab(skip)] i64, bool),
+  }
+
+  mod as_string {
+    use super::*;
+
+    pub(super) fn encode(value: &i64, encoder: &mut Encoder) {
+      encoder.string(&value.to_string());
+    }
+
+    pub(super) fn decode(decoder: &mut Decoder<'_>) -> i64 {
+      check(try_decode(decoder))
+    }
+
+    pub(super) fn try_decode(decoder: &mut Decoder<'_>) -> Result<i64, DecodeError> {
+      let offset: usize = decoder.offset();
+
+      decoder
+        .try_string()?
+        .parse()
+        .map_err(|_| DecodeError::new(offset, "i64", DecodeErrorKind::Length(0)))
+    }
+  }
+
+  // Written against the `BytesDecoder` of wasmlib
+  struct Legacy(i64);
+
+  impl Decode for Legacy {
+    fn decode(decoder: &mut crate::export::BytesDecoder<'_>) -> Self {
+      Self(decoder.int64())
+    }
+  }
+
+  #[test]
+  fn test_enum_tags() {
+    assert_eq!(Side::Buy.to_bytes(), 0_i64.to_bytes());
+    assert_eq!(Side::Sell.to_bytes(), 5_i64.to_bytes());
+    assert_eq!(Side::Hold.to_bytes(), 6_i64.to_bytes());
+    assert_eq!(Side::Cancel.to_bytes(), (-3_i64).to_bytes());
+    assert_eq!(Wide::Min.to_bytes(), i64::MIN.to_bytes());
+    assert_eq!(Wide::Max.to_bytes(), i64::MAX.to_bytes());
+
+    for side in [Side::Buy, Side::Sell, Side::Hold, Side::Cancel] {
+      assert_eq!(Side::try_from_bytes(&side.to_bytes()), Ok(side));
+    }
+
+    for wide in [Wide::Min, Wide::Max] {
+      assert_eq!(Wide::from_bytes(&wide.to_bytes()), wide);
+    }
+  }
+
+  #[test]
+  fn test_enum_roundtrip() {
+    let messages: [Message; 3] = [
+      Message::Ping,
+      Message::Text(-7, "hello".to_string()),
+      Message::Move {
+        from: 1,
+        to: vec![1, 2, 3],
+      },
+    ];
+
+    for message in messages {
+      let bytes: Vec<u8> = message.to_bytes();
+
+      assert_eq!(Message::try_from_bytes(&bytes), Ok(Message::from_bytes(&bytes)));
+      assert_eq!(Message::from_bytes(&bytes), message);
+    }
+
+    let bytes: Vec<u8> = Message::Text(1, "x".to_string()).to_bytes();
+    let error: DecodeError = Message::try_from_bytes(&bytes[..bytes.len() - 1]).unwrap_err();
+
+    assert_eq!(error.expected(), "ScString");
+    assert_eq!(error.kind(), DecodeErrorKind::Insufficient(1));
+  }
+
+  #[test]
+  fn test_enum_invalid_tag() {
+    let error: DecodeError = Side::try_from_bytes(&7_i64.to_bytes()).unwrap_err();
+
+    assert_eq!(error.offset(), 0);
+    assert_eq!(error.expected(), "Side");
+    assert_eq!(error.kind(), DecodeErrorKind::Tag(7));
+    assert!(Never::try_from_bytes(&0_i64.to_bytes()).is_err());
+  }
+
+  #[test]
+  fn test_generic_roundtrip() {
+    let entry: Entry<i64, String> = Entry(1, "one".to_string());
+    let choices: [Choice<i64>; 3] = [
+      Choice::Empty,
+      Choice::Value(5),
+      Choice::Pair {
+        first: 1,
+        second: Some(2),
+      },
+    ];
+
+    assert_eq!(Entry::try_from_bytes(&entry.to_bytes()), Ok(entry));
+
+    for choice in choices {
+      assert_eq!(Choice::try_from_bytes(&choice.to_bytes()), Ok(choice));
+    }
+
+    let wrapper: Wrapper<Vec<bool>> = Wrapper {
+      inner: Box::new(vec![true, false]),
+    };
+
+    assert_eq!(Wrapper::from_bytes(&wrapper.to_bytes()), wrapper);
+  }
+
+  #[test]
+  fn test_generic_custom_bounds() {
+    let custom: Custom<i64> = Custom { value: 7 };
+    let split: Split<i64> = Split { values: vec![-1, 1] };
+
+    assert_eq!(custom.to_bytes(), 7_i64.to_bytes());
+    assert_eq!(Custom::try_from_bytes(&custom.to_bytes()), Ok(custom));
+    assert_eq!(split.to_bytes(), vec![-1_i64, 1].to_bytes());
+    assert_eq!(Split::try_from_bytes(&split.to_bytes()), Ok(split));
+  }
+
+  #[test]
+  fn test_versioned_records() {
+    let old: Vec<RecordV1> = vec![RecordV1 { value: 1 }, RecordV1 { value: 2 }];
+    let new: Vec<RecordV2> = Vec::try_from_bytes(&old.to_bytes()).unwrap();
+
+    assert_eq!(
+      new,
+      vec![
+        RecordV2 {
+          value: 1,
+          label: String::new(),
+          limit: 7,
+        },
+        RecordV2 {
+          value: 2,
+          label: String::new(),
+          limit: 7,
+        },
+      ]
+    );
+
+    let bytes: Vec<u8> = (RecordV1 { value: 3 }, -1_i64).to_bytes();
+
+    assert_eq!(
+      <(RecordV2, i64)>::try_from_bytes(&bytes),
+      Ok((
+        RecordV2 {
+          value: 3,
+          label: String::new(),
+          limit: 7,
+        },
+        -1
+      ))
+    );
+
+    let record: RecordV2 = RecordV2 {
+      value: 4,
+      label: "four".to_string(),
+      limit: 8,
+    };
+
+    assert_eq!(RecordV2::from_bytes(&record.to_bytes()), record);
+  }
+
+  #[test]
+  fn test_versioned_records_malformed() {
+    let newer: Vec<u8> = RecordV2 {
+      value: 1,
+      label: String::new(),
+      limit: 0,
+    }
+    .to_bytes();
+
+    let error: DecodeError = RecordV1::try_from_bytes(&newer).unwrap_err();
+
+    assert_eq!((error.expected(), error.offset()), ("RecordV1", 0));
+    assert_eq!(error.kind(), DecodeErrorKind::Version(2));
+
+    // A version 2 record without the trailing `default` field
+    let mut encoder: Encoder = Encoder::new();
+    encoder.int64(2);
+    encoder.bytes(&(5_i64, "five".to_string()).to_bytes());
+
+    assert_eq!(
+      RecordV2::try_from_bytes(&encoder.data()),
+      Ok(RecordV2 {
+        value: 5,
+        label: "five".to_string(),
+        limit: 7,
+      })
+    );
+
+    let mut encoder: Encoder = Encoder::new();
+    encoder.int64(1);
+    encoder.bytes(&(5_i64, 6_i64).to_bytes());
+
+    let error: DecodeError = RecordV1::try_from_bytes(&encoder.data()).unwrap_err();
+
+    assert_eq!((error.offset(), error.kind()), (3, DecodeErrorKind::Trailing(1)));
+  }
+
+  #[test]
+  fn test_unversioned_defaults() {
+    let old: Vec<u8> = AccountV1 { balance: 3 }.to_bytes();
+
+    assert_eq!(
+      AccountV2::try_from_bytes(&old),
+      Ok(AccountV2 {
+        balance: 3,
+        frozen: false,
+        limit: 7,
+      })
+    );
+    assert_eq!(
+      AccountV2::from_bytes(&(3_i64, true).to_bytes()),
+      AccountV2 {
+        balance: 3,
+        frozen: true,
+        limit: 7,
+      }
+    );
+
+    let account: AccountV2 = AccountV2 {
+      balance: 4,
+      frozen: true,
+      limit: 8,
+    };
+
+    // The unframed encoding is unchanged by the new fields
+    assert_eq!(account.to_bytes(), (4_i64, true, 8_i64).to_bytes());
+    assert_eq!(AccountV2::try_from_bytes(&account.to_bytes()), Ok(account));
+  }
+
+  #[test]
+  fn test_field_attributes() {
+    assert_eq!(Formatted::Text(42).to_bytes(), (0_i64, "42".to_string()).to_bytes());
+    assert_eq!(Formatted::Skipped(1, true).to_bytes(), (1_i64, true).to_bytes());
+    assert_eq!(
+      Formatted::try_from_bytes(&Formatted::Text(42).to_bytes()),
+      Ok(Formatted::Text(42))
+    );
+    assert_eq!(
+      Formatted::try_from_bytes(&Formatted::Skipped(1, true).to_bytes()),
+      Ok(Formatted::Skipped(0, true))
+    );
+  }
+
+  #[test]
+  fn test_skipped_generic_fields() {
+    let cached: Cached<Cache, Marker> = Cached {
+      value: 1,
+      cache: Cache(2),
+      marker: core::marker::PhantomData,
+    };
+
+    let decoded: Cached<Cache, Marker> = Cached::try_from_bytes(&cached.to_bytes()).unwrap();
+
+    assert_eq!(decoded.value, 1);
+    assert_eq!(decoded.cache, Cache(0));
+    assert_eq!(Legacy::from_bytes(&5_i64.to_bytes()).0, 5);
+  }
+
+  #[test]
+  fn test_int256_fixed() {
+    let value: U256 = U256::MAX - U256::ONE;
+    let signed: I256 = I256::from(-2_i8);
+
+    assert_eq!(value.to_bytes(), value.to_le_bytes());
+    assert_eq!(signed.to_bytes(), signed.to_le_bytes());
+    assert_eq!(U256::try_from_bytes(&value.to_bytes()), Ok(value));
+    assert_eq!(I256::try_from_bytes(&signed.to_bytes()), Ok(signed));
+    assert_eq!(
+      <(U256, i64)>::try_from_bytes(&(value, 7_i64).to_bytes()),
+      Ok((value, 7))
+    );
+
+    let error: DecodeError = U256::try_from_bytes(&[0; 31]).unwrap_err();
+
+    assert_eq!((error.expected(), error.offset()), ("U256", 0));
+    assert_eq!(error.kind(), DecodeErrorKind::Insufficient(32));
+  }
+}
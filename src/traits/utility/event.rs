@@ -0,0 +1,50 @@
+use wasmlib::ScFuncContext;
+
+use crate::traits::utility::Encode;
+use crate::traits::utility::TryDecode;
+use crate::types::decode_text;
+use crate::types::encode_text;
+use crate::types::EventError;
+use crate::types::EventSchema;
+
+/// A common interface for events emitted by a contract.
+///
+/// Events are written to the event log as their [`NAME`][Self::NAME],
+/// followed by a space and the hex-encoded bytes of the [encoded][Encode]
+/// event. Records returned by the event log are decoded with
+/// [EventRecord][crate::types::EventRecord].
+pub trait Event: Sized {
+  /// The name the event is emitted under.
+  const NAME: &'static str;
+
+  /// Returns a description of the event for contract schemas.
+  fn schema() -> EventSchema;
+
+  /// Returns the text the event is emitted as.
+  fn to_text(&self) -> String
+  where
+    Self: Encode,
+  {
+    encode_text(Self::NAME, &self.to_bytes())
+  }
+
+  /// Decodes an event from the text it was emitted as.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `text` does not contain an event of this type.
+  fn from_text(text: &str) -> Result<Self, EventError>
+  where
+    Self: TryDecode,
+  {
+    decode_text(text)
+  }
+
+  /// Writes the event to the event log of the chain.
+  fn emit(&self, ctx: &ScFuncContext)
+  where
+    Self: Encode,
+  {
+    ctx.event(&self.to_text());
+  }
+}
@@ -1,4 +1,31 @@
 use wasmlib::ScExports;
+use wasmlib::ScFuncContext;
+use wasmlib::ScViewContext;
+
+use crate::types::Schema;
+
+/// A set of exported funcs and views.
+///
+/// This is implemented by [ScExports] and by the hosts in
+/// [testing][crate::testing], which load contracts without the wasmlib export
+/// table.
+pub trait Registry {
+  /// Exports `func` as `name`.
+  fn add_func(&self, name: &'static str, func: fn(&ScFuncContext));
+
+  /// Exports `view` as `name`.
+  fn add_view(&self, name: &'static str, view: fn(&ScViewContext));
+}
+
+impl Registry for ScExports {
+  fn add_func(&self, name: &'static str, func: fn(&ScFuncContext)) {
+    ScExports::add_func(self, name, func);
+  }
+
+  fn add_view(&self, name: &'static str, view: fn(&ScViewContext)) {
+    ScExports::add_view(self, name, view);
+  }
+}
 
 pub trait Export {
   fn register(exports: &ScExports);
@@ -6,4 +33,24 @@ pub trait Export {
   fn export() {
     Self::register(&ScExports::new())
   }
+
+  /// Adds the exported funcs and views to `registry`.
+  ///
+  /// Contracts declared with `#[scarab::contract]` register the same exports
+  /// as [register][Self::register]; the default implementation adds nothing.
+  fn register_with(registry: &dyn Registry) {
+    let _ = registry;
+  }
+
+  /// Adds the exported funcs, views and events to `schema`.
+  fn describe(schema: &mut Schema) {
+    let _ = schema;
+  }
+
+  /// Returns the interface schema of the contract `name`.
+  fn schema(name: &'static str) -> Schema {
+    let mut schema: Schema = Schema::new(name);
+    Self::describe(&mut schema);
+    schema
+  }
 }
@@ -1,7 +1,13 @@
 //! Misc. utility traits
 
 mod encode;
+mod event;
 mod export;
+mod params;
+mod results;
 
 pub use self::encode::*;
+pub use self::event::*;
 pub use self::export::*;
+pub use self::params::*;
+pub use self::results::*;
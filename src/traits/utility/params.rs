@@ -0,0 +1,218 @@
+use core::convert::TryFrom;
+use wasmlib::*;
+
+use crate::traits::core::Array;
+use crate::traits::core::MapGet;
+use crate::traits::core::MapSet;
+use crate::traits::core::Value;
+use crate::traits::extension::MapExt;
+use crate::types::FieldSchema;
+use crate::types::ParamsError;
+use crate::types::ScBytes;
+use crate::types::ScInt64;
+use crate::types::ScString;
+use crate::types::ScTag;
+use crate::types::I256;
+use crate::types::U256;
+
+/// A set of request parameters read from a params map.
+///
+/// This trait can be derived with `#[derive(Params)]`.
+pub trait Params: Sized {
+  /// Reads `Self` from the given map of request parameters.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error listing every missing or invalid parameter.
+  fn from_params(params: &ScImmutableMap) -> Result<Self, ParamsError>;
+}
+
+/// A common interface for values stored in a map of request parameters.
+///
+/// Values are stored the same as a [ResultValue][super::ResultValue]: WasmLib
+/// values as-is, `bool` and integers smaller than 64 bits as an `Int64` and
+/// `Vec<T>` or `[T]` as a native array.
+pub trait ParamValue {
+  /// The host type the value is stored as.
+  const TAG: ScTag;
+
+  /// Writes the value to `key` of `map`.
+  fn write_param<K: MapKey + ?Sized>(&self, map: &ScMutableMap, key: &K);
+
+  /// Reads the value of `key` from `map` if present.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the stored value can't be represented as `Self`.
+  fn read_param<K: MapKey + ?Sized>(map: &ScImmutableMap, key: &K) -> Result<Option<Self>, String>
+  where
+    Self: Sized;
+
+  /// Returns a description of the value stored at `key` for contract schemas.
+  fn schema(key: &'static str) -> FieldSchema {
+    FieldSchema::new(key, Self::TAG)
+  }
+}
+
+macro_rules! impl_ParamValue {
+  (@native, $($ident:ty),+ $(,)*) => {
+    $(
+      impl ParamValue for $ident {
+        const TAG: ScTag = <$ident as Value>::TAG;
+
+        fn write_param<K: MapKey + ?Sized>(&self, map: &ScMutableMap, key: &K) {
+          self.map_set(map, key);
+        }
+
+        fn read_param<K: MapKey + ?Sized>(map: &ScImmutableMap, key: &K) -> Result<Option<Self>, String> {
+          Ok(map.get_opt(key))
+        }
+      }
+    )+
+  };
+  (@int64, $($ident:ty),+ $(,)*) => {
+    $(
+      impl ParamValue for $ident {
+        const TAG: ScTag = ScTag::Int64;
+
+        fn write_param<K: MapKey + ?Sized>(&self, map: &ScMutableMap, key: &K) {
+          map.get_int64(key).set_value(i64::from(*self));
+        }
+
+        fn read_param<K: MapKey + ?Sized>(map: &ScImmutableMap, key: &K) -> Result<Option<Self>, String> {
+          match map.get_opt::<_, i64>(key) {
+            Some(value) => match <$ident>::try_from(value) {
+              Ok(value) => Ok(Some(value)),
+              Err(_) => Err(format!("`{}` out of range: {}", stringify!($ident), value)),
+            },
+            None => Ok(None),
+          }
+        }
+      }
+    )+
+  };
+}
+
+impl_ParamValue! {
+  @native,
+  ScAddress,
+  ScAgentId,
+  ScBytes,
+  ScChainId,
+  ScColor,
+  ScHash,
+  ScHname,
+  ScInt64,
+  ScRequestId,
+  ScString,
+  U256,
+  I256,
+}
+
+impl_ParamValue! {
+  @int64,
+  u8, u16, u32,
+  i8, i16, i32,
+}
+
+impl ParamValue for bool {
+  const TAG: ScTag = ScTag::Int64;
+
+  fn write_param<K: MapKey + ?Sized>(&self, map: &ScMutableMap, key: &K) {
+    map.get_int64(key).set_value(*self as i64);
+  }
+
+  fn read_param<K: MapKey + ?Sized>(map: &ScImmutableMap, key: &K) -> Result<Option<Self>, String> {
+    match map.get_opt::<_, i64>(key) {
+      Some(0) => Ok(Some(false)),
+      Some(1) => Ok(Some(true)),
+      Some(value) => Err(format!("`bool` out of range: {}", value)),
+      None => Ok(None),
+    }
+  }
+}
+
+// Slices and vectors are stored as a native array of their values
+impl<T> ParamValue for [T]
+where
+  T: Value,
+  T::ArrayMut: MapGet<ScMutableMap>,
+{
+  const TAG: ScTag = T::TAG;
+
+  fn schema(key: &'static str) -> FieldSchema {
+    FieldSchema::array(key, T::TAG)
+  }
+
+  fn write_param<K: MapKey + ?Sized>(&self, map: &ScMutableMap, key: &K) {
+    self.map_set(map, key);
+  }
+}
+
+impl<T> ParamValue for Vec<T>
+where
+  T: Value,
+  T::Array: MapGet<ScImmutableMap>,
+  T::ArrayMut: MapGet<ScMutableMap>,
+{
+  const TAG: ScTag = T::TAG;
+
+  fn schema(key: &'static str) -> FieldSchema {
+    FieldSchema::array(key, T::TAG)
+  }
+
+  fn write_param<K: MapKey + ?Sized>(&self, map: &ScMutableMap, key: &K) {
+    self.as_slice().map_set(map, key);
+  }
+
+  fn read_param<K: MapKey + ?Sized>(map: &ScImmutableMap, key: &K) -> Result<Option<Self>, String> {
+    // Opening the array proxy creates the array, so check the key first
+    if map.get_bytes(key).exists() {
+      Ok(Some(T::Array::map_get(map, key).to_vec()))
+    } else {
+      Ok(None)
+    }
+  }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+  use super::*;
+  use crate::testing::MockHost;
+
+  #[test]
+  fn test_param_values() {
+    let _host: MockHost = MockHost::new("test");
+    let map: ScMutableMap = ScMutableMap::new();
+
+    true.write_param(&map, "flag");
+    7_u8.write_param(&map, "small");
+    300_i64.write_param(&map, "large");
+    2_i64.write_param(&map, "two");
+    [1_i64, 2][..].write_param(&map, "values");
+
+    assert_eq!(bool::read_param(&map.immutable(), "flag"), Ok(Some(true)));
+    assert_eq!(u8::read_param(&map.immutable(), "small"), Ok(Some(7)));
+    assert_eq!(u8::read_param(&map.immutable(), "missing"), Ok(None));
+    assert_eq!(
+      u8::read_param(&map.immutable(), "large"),
+      Err("`u8` out of range: 300".to_string())
+    );
+    assert_eq!(
+      bool::read_param(&map.immutable(), "two"),
+      Err("`bool` out of range: 2".to_string())
+    );
+    assert_eq!(Vec::<i64>::read_param(&map.immutable(), "values"), Ok(Some(vec![1, 2])));
+    assert_eq!(Vec::<i64>::read_param(&map.immutable(), "missing"), Ok(None));
+  }
+
+  #[test]
+  fn test_param_schema() {
+    assert_eq!(bool::schema("flag"), FieldSchema::new("flag", ScTag::Int64));
+    assert_eq!(
+      <[ScAgentId]>::schema("owners"),
+      FieldSchema::array("owners", ScTag::AgentId)
+    );
+    assert_eq!(Vec::<U256>::schema("ids"), FieldSchema::array("ids", ScTag::Bytes));
+  }
+}
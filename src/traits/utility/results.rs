@@ -0,0 +1,195 @@
+use core::convert::TryFrom;
+use wasmlib::*;
+
+use crate::panic::fail;
+use crate::traits::core::Array;
+use crate::traits::core::MapGet;
+use crate::traits::core::MapSet;
+use crate::traits::core::Value;
+use crate::traits::extension::MapExt;
+use crate::types::FieldSchema;
+use crate::types::ScBytes;
+use crate::types::ScInt64;
+use crate::types::ScString;
+use crate::types::ScTag;
+use crate::types::I256;
+use crate::types::U256;
+
+/// A common interface for values stored in a map of request results.
+///
+/// WasmLib values are stored as-is; `bool` and integers smaller than 64 bits
+/// are stored as an `Int64` and `Vec<T>` as a native array.
+pub trait ResultValue: Sized {
+  /// The host type the value is stored as.
+  const TAG: ScTag;
+
+  /// Writes the value to `key` of `map`.
+  fn write_result<K: MapKey + ?Sized>(&self, map: &ScMutableMap, key: &K);
+
+  /// Reads the value of `key` from `map` if present.
+  fn read_result<K: MapKey + ?Sized>(map: &ScImmutableMap, key: &K) -> Option<Self>;
+
+  /// Returns a description of the value stored at `key` for contract schemas.
+  fn schema(key: &'static str) -> FieldSchema {
+    FieldSchema::new(key, Self::TAG)
+  }
+}
+
+macro_rules! impl_ResultValue {
+  (@native, $($ident:ty),+ $(,)*) => {
+    $(
+      impl ResultValue for $ident {
+        const TAG: ScTag = <$ident as Value>::TAG;
+
+        fn write_result<K: MapKey + ?Sized>(&self, map: &ScMutableMap, key: &K) {
+          self.map_set(map, key);
+        }
+
+        fn read_result<K: MapKey + ?Sized>(map: &ScImmutableMap, key: &K) -> Option<Self> {
+          map.get_opt(key)
+        }
+      }
+    )+
+  };
+  (@int64, $($ident:ty),+ $(,)*) => {
+    $(
+      impl ResultValue for $ident {
+        const TAG: ScTag = ScTag::Int64;
+
+        fn write_result<K: MapKey + ?Sized>(&self, map: &ScMutableMap, key: &K) {
+          map.get_int64(key).set_value(i64::from(*self));
+        }
+
+        fn read_result<K: MapKey + ?Sized>(map: &ScImmutableMap, key: &K) -> Option<Self> {
+          map.get_opt(key).map(|value: i64| match <$ident>::try_from(value) {
+            Ok(value) => value,
+            Err(_) => fail(&format!("invalid `{}` result: {}", stringify!($ident), value)),
+          })
+        }
+      }
+    )+
+  };
+}
+
+impl_ResultValue! {
+  @native,
+  ScAddress,
+  ScAgentId,
+  ScBytes,
+  ScChainId,
+  ScColor,
+  ScHash,
+  ScHname,
+  ScInt64,
+  ScRequestId,
+  ScString,
+  U256,
+  I256,
+}
+
+impl_ResultValue! {
+  @int64,
+  u8, u16, u32,
+  i8, i16, i32,
+}
+
+impl ResultValue for bool {
+  const TAG: ScTag = ScTag::Int64;
+
+  fn write_result<K: MapKey + ?Sized>(&self, map: &ScMutableMap, key: &K) {
+    map.get_int64(key).set_value(*self as i64);
+  }
+
+  fn read_result<K: MapKey + ?Sized>(map: &ScImmutableMap, key: &K) -> Option<Self> {
+    map.get_opt(key).map(|value: i64| match value {
+      0 => false,
+      1 => true,
+      _ => fail(&format!("invalid `bool` result: {}", value)),
+    })
+  }
+}
+
+// Vectors are stored as a native array of their values
+impl<T> ResultValue for Vec<T>
+where
+  T: Value,
+  T::Array: MapGet<ScImmutableMap>,
+  T::ArrayMut: MapGet<ScMutableMap>,
+{
+  const TAG: ScTag = T::TAG;
+
+  fn schema(key: &'static str) -> FieldSchema {
+    FieldSchema::array(key, T::TAG)
+  }
+
+  fn write_result<K: MapKey + ?Sized>(&self, map: &ScMutableMap, key: &K) {
+    self.as_slice().map_set(map, key);
+  }
+
+  fn read_result<K: MapKey + ?Sized>(map: &ScImmutableMap, key: &K) -> Option<Self> {
+    // Opening the array proxy creates the array, so check the key first
+    if map.get_bytes(key).exists() {
+      Some(T::Array::map_get(map, key).to_vec())
+    } else {
+      None
+    }
+  }
+}
+
+// =============================================================================
+// =============================================================================
+
+/// A set of request results written to a results map.
+///
+/// This trait can be derived with `#[derive(Results)]`.
+pub trait Results: Sized {
+  /// Writes `self` to the given map of request results.
+  fn write_results(&self, results: &ScMutableMap);
+
+  /// Reads `Self` from the results of a call.
+  ///
+  /// Fails the request if a required result is missing.
+  fn from_results(results: &ScImmutableMap) -> Self;
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+  use super::*;
+  use crate::testing::MockHost;
+
+  #[test]
+  fn test_vec_results() {
+    let _host: MockHost = MockHost::new("test");
+    let map: ScMutableMap = ScMutableMap::new();
+
+    vec![1_i64, 2, 3].write_result(&map, "values");
+    Vec::<ScString>::new().write_result(&map, "names");
+
+    assert_eq!(Vec::<i64>::read_result(&map.immutable(), "values"), Some(vec![1, 2, 3]));
+    assert_eq!(
+      Vec::<ScString>::read_result(&map.immutable(), "names"),
+      Some(Vec::new())
+    );
+    assert_eq!(Vec::<i64>::read_result(&map.immutable(), "missing"), None);
+  }
+
+  #[test]
+  fn test_int64_results() {
+    let _host: MockHost = MockHost::new("test");
+    let map: ScMutableMap = ScMutableMap::new();
+
+    true.write_result(&map, "flag");
+    7_u8.write_result(&map, "small");
+
+    assert_eq!(bool::read_result(&map.immutable(), "flag"), Some(true));
+    assert_eq!(u8::read_result(&map.immutable(), "small"), Some(7));
+    assert_eq!(u8::read_result(&map.immutable(), "missing"), None);
+  }
+
+  #[test]
+  fn test_result_schema() {
+    assert_eq!(u8::schema("small"), FieldSchema::new("small", ScTag::Int64));
+    assert_eq!(Vec::<U256>::schema("ids"), FieldSchema::array("ids", ScTag::Bytes));
+    assert!(!U256::schema("id").is_array());
+  }
+}
@@ -0,0 +1,410 @@
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt::Debug;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::ops::Add;
+use core::ops::AddAssign;
+use core::ops::Neg;
+use core::ops::Sub;
+use core::ops::SubAssign;
+use core::str::FromStr;
+
+use crate::panic::fail;
+use crate::types::IntError;
+use crate::types::I256;
+use crate::types::U256;
+
+/// An error returned when parsing a decimal number fails.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum DecimalError {
+  /// The string to parse was empty.
+  Empty,
+  /// The string to parse contained an unexpected character.
+  InvalidDigit,
+  /// The string to parse has more fractional digits than the scale.
+  Precision,
+  /// The value does not fit in the target type.
+  Overflow,
+}
+
+impl Display for DecimalError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    match self {
+      Self::Empty => f.write_str("cannot parse decimal from empty string"),
+      Self::InvalidDigit => f.write_str("invalid digit found in string"),
+      Self::Precision => f.write_str("too many fractional digits for the scale"),
+      Self::Overflow => f.write_str("decimal out of range for the target type"),
+    }
+  }
+}
+
+impl From<IntError> for DecimalError {
+  fn from(other: IntError) -> Self {
+    match other {
+      IntError::Empty => Self::Empty,
+      IntError::InvalidDigit => Self::InvalidDigit,
+      IntError::Overflow => Self::Overflow,
+    }
+  }
+}
+
+/// The rounding mode of an inexact [Decimal] operation.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Rounding {
+  /// Round towards negative infinity.
+  Floor,
+  /// Round towards positive infinity.
+  Ceil,
+  /// Round to the nearest value; ties are rounded to the even neighbour.
+  HalfEven,
+}
+
+// =============================================================================
+// Decimal
+// =============================================================================
+
+/// A signed fixed-point number with `SCALE` fractional decimal digits.
+///
+/// The value is stored as an [I256] holding `value * 10^SCALE`, so sums and
+/// differences are exact and only multiplication, division and rescaling
+/// require a [Rounding] mode.
+///
+/// Values are encoded in the same format as their raw [I256].
+#[derive(Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Decimal<const SCALE: u32> {
+  raw: I256,
+}
+
+impl<const SCALE: u32> Decimal<SCALE> {
+  /// The number of fractional decimal digits.
+  pub const SCALE: u32 = SCALE;
+
+  /// The value `0`.
+  pub const ZERO: Self = Self { raw: I256::ZERO };
+
+  /// The smallest value that can be represented by this type.
+  pub const MIN: Self = Self { raw: I256::MIN };
+
+  /// The largest value that can be represented by this type.
+  pub const MAX: Self = Self { raw: I256::MAX };
+
+  /// Creates a new `Decimal` from its raw value, `value * 10^SCALE`.
+  pub const fn from_raw(raw: I256) -> Self {
+    Self { raw }
+  }
+
+  /// Returns the raw value, `value * 10^SCALE`.
+  pub const fn raw(self) -> I256 {
+    self.raw
+  }
+
+  /// Creates a new `Decimal` from an integer, or `None` if it doesn't fit.
+  pub fn from_integer(value: impl Into<I256>) -> Option<Self> {
+    value.into().checked_mul(pow10(SCALE)?).map(Self::from_raw)
+  }
+
+  /// Returns the value rounded to an integer, or `None` if the scale is too
+  /// large to be represented.
+  pub fn to_integer(self, rounding: Rounding) -> Option<I256> {
+    rescale(self.raw, SCALE, 0, rounding)
+  }
+
+  /// Returns `true` if the value is `0`.
+  pub fn is_zero(self) -> bool {
+    self.raw == I256::ZERO
+  }
+
+  /// Returns `true` if the value is less than `0`.
+  pub fn is_negative(self) -> bool {
+    self.raw.is_negative()
+  }
+
+  /// Returns `self + rhs`, or `None` if overflow occurred.
+  pub fn checked_add(self, rhs: Self) -> Option<Self> {
+    self.raw.checked_add(rhs.raw).map(Self::from_raw)
+  }
+
+  /// Returns `self - rhs`, or `None` if overflow occurred.
+  pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+    self.raw.checked_sub(rhs.raw).map(Self::from_raw)
+  }
+
+  /// Returns `-self`, or `None` if overflow occurred.
+  pub fn checked_neg(self) -> Option<Self> {
+    self.raw.checked_neg().map(Self::from_raw)
+  }
+
+  /// Returns `self * rhs` rounded with `rounding`, or `None` if overflow
+  /// occurred.
+  pub fn checked_mul(self, rhs: Self, rounding: Rounding) -> Option<Self> {
+    div_round(self.raw.checked_mul(rhs.raw)?, pow10(SCALE)?, rounding).map(Self::from_raw)
+  }
+
+  /// Returns `self / rhs` rounded with `rounding`, or `None` if `rhs == 0` or
+  /// overflow occurred.
+  pub fn checked_div(self, rhs: Self, rounding: Rounding) -> Option<Self> {
+    div_round(self.raw.checked_mul(pow10(SCALE)?)?, rhs.raw, rounding).map(Self::from_raw)
+  }
+
+  /// Converts the value to a different scale, or `None` if overflow occurred.
+  ///
+  /// The value is rounded with `rounding` if the new scale is smaller.
+  pub fn checked_rescale<const OTHER: u32>(self, rounding: Rounding) -> Option<Decimal<OTHER>> {
+    rescale(self.raw, SCALE, OTHER, rounding).map(Decimal::from_raw)
+  }
+
+  /// Creates a new `Decimal` from an amount of token base units, where one
+  /// token is `10^decimals` base units (see `IERC20::decimals`).
+  ///
+  /// Returns `None` if the value doesn't fit.
+  pub fn from_base_units(amount: U256, decimals: u8, rounding: Rounding) -> Option<Self> {
+    let amount: I256 = I256::try_from(amount).ok()?;
+
+    rescale(amount, u32::from(decimals), SCALE, rounding).map(Self::from_raw)
+  }
+
+  /// Returns the value as an amount of token base units, where one token is
+  /// `10^decimals` base units (see `IERC20::decimals`).
+  ///
+  /// Returns `None` if the value is negative or doesn't fit.
+  pub fn to_base_units(self, decimals: u8, rounding: Rounding) -> Option<U256> {
+    let amount: I256 = rescale(self.raw, SCALE, u32::from(decimals), rounding)?;
+
+    U256::try_from(amount).ok()
+  }
+}
+
+impl<const SCALE: u32> Display for Decimal<SCALE> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    let digits: String = self.raw.unsigned_abs().to_string();
+    let scale: usize = SCALE as usize;
+
+    if scale == 0 {
+      return f.pad_integral(!self.is_negative(), "", &digits);
+    }
+
+    // Pad with leading zeros so there is at least one integer digit
+    let digits: String = format!("{:0>width$}", digits, width = scale + 1);
+    let (integer, fraction): (&str, &str) = digits.split_at(digits.len() - scale);
+
+    f.pad_integral(!self.is_negative(), "", &format!("{}.{}", integer, fraction))
+  }
+}
+
+impl<const SCALE: u32> Debug for Decimal<SCALE> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    Display::fmt(self, f)
+  }
+}
+
+impl<const SCALE: u32> FromStr for Decimal<SCALE> {
+  type Err = DecimalError;
+
+  fn from_str(string: &str) -> Result<Self, Self::Err> {
+    let (sign, digits): (&str, &str) = match string.strip_prefix('-') {
+      Some(digits) => ("-", digits),
+      None => ("", string.strip_prefix('+').unwrap_or(string)),
+    };
+
+    let (integer, fraction): (&str, &str) = match digits.find('.') {
+      Some(index) => (&digits[..index], &digits[index + 1..]),
+      None => (digits, ""),
+    };
+
+    if integer.is_empty() && fraction.is_empty() {
+      return Err(DecimalError::Empty);
+    }
+
+    if !integer
+      .bytes()
+      .chain(fraction.bytes())
+      .all(|byte| byte.is_ascii_digit())
+    {
+      return Err(DecimalError::InvalidDigit);
+    }
+
+    if fraction.len() > SCALE as usize {
+      return Err(DecimalError::Precision);
+    }
+
+    let raw: String = format!(
+      "{}{}{}{}",
+      sign,
+      integer,
+      fraction,
+      "0".repeat(SCALE as usize - fraction.len()),
+    );
+
+    raw.parse().map(Self::from_raw).map_err(Into::into)
+  }
+}
+
+impl<const SCALE: u32> Add for Decimal<SCALE> {
+  type Output = Self;
+
+  fn add(self, rhs: Self) -> Self::Output {
+    match self.checked_add(rhs) {
+      Some(output) => output,
+      None => fail("attempt to add with overflow"),
+    }
+  }
+}
+
+impl<const SCALE: u32> AddAssign for Decimal<SCALE> {
+  fn add_assign(&mut self, rhs: Self) {
+    *self = *self + rhs;
+  }
+}
+
+impl<const SCALE: u32> Sub for Decimal<SCALE> {
+  type Output = Self;
+
+  fn sub(self, rhs: Self) -> Self::Output {
+    match self.checked_sub(rhs) {
+      Some(output) => output,
+      None => fail("attempt to subtract with overflow"),
+    }
+  }
+}
+
+impl<const SCALE: u32> SubAssign for Decimal<SCALE> {
+  fn sub_assign(&mut self, rhs: Self) {
+    *self = *self - rhs;
+  }
+}
+
+impl<const SCALE: u32> Neg for Decimal<SCALE> {
+  type Output = Self;
+
+  fn neg(self) -> Self::Output {
+    match self.checked_neg() {
+      Some(output) => output,
+      None => fail("attempt to negate with overflow"),
+    }
+  }
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+fn pow10(exp: u32) -> Option<I256> {
+  I256::from(10).checked_pow(exp)
+}
+
+// Converts a raw value between scales
+fn rescale(value: I256, from: u32, to: u32, rounding: Rounding) -> Option<I256> {
+  match from.cmp(&to) {
+    Ordering::Less => value.checked_mul(pow10(to - from)?),
+    Ordering::Equal => Some(value),
+    Ordering::Greater => div_round(value, pow10(from - to)?, rounding),
+  }
+}
+
+// Divides `lhs` by `rhs` and rounds the quotient with `rounding`
+fn div_round(lhs: I256, rhs: I256, rounding: Rounding) -> Option<I256> {
+  let (quotient, remainder): (I256, I256) = lhs.checked_div_rem(rhs)?;
+
+  if remainder == I256::ZERO {
+    return Some(quotient);
+  }
+
+  // The quotient is truncated; `step` moves it away from zero
+  let negative: bool = lhs.is_negative() != rhs.is_negative();
+  let step: I256 = if negative { I256::from(-1) } else { I256::ONE };
+
+  let away: bool = match rounding {
+    Rounding::Floor => negative,
+    Rounding::Ceil => !negative,
+    Rounding::HalfEven => {
+      let remainder: U256 = remainder.unsigned_abs();
+      let divisor: U256 = rhs.unsigned_abs();
+
+      match remainder.cmp(&(divisor - remainder)) {
+        Ordering::Less => false,
+        Ordering::Equal => quotient.checked_rem(I256::from(2))? != I256::ZERO,
+        Ordering::Greater => true,
+      }
+    }
+  };
+
+  if away {
+    quotient.checked_add(step)
+  } else {
+    Some(quotient)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  type Fixed2 = Decimal<2>;
+
+  fn fixed(string: &str) -> Fixed2 {
+    string.parse().unwrap()
+  }
+
+  #[test]
+  fn test_parse_display() {
+    assert_eq!(fixed("1.5").to_string(), "1.50");
+    assert_eq!(fixed("-0.05").to_string(), "-0.05");
+    assert_eq!(fixed(".5").to_string(), "0.50");
+    assert_eq!(fixed("+12").to_string(), "12.00");
+    assert_eq!(Decimal::<0>::from_integer(-7).unwrap().to_string(), "-7");
+    assert_eq!("".parse::<Fixed2>(), Err(DecimalError::Empty));
+    assert_eq!(".".parse::<Fixed2>(), Err(DecimalError::Empty));
+    assert_eq!("1.2.3".parse::<Fixed2>(), Err(DecimalError::InvalidDigit));
+    assert_eq!("--1".parse::<Fixed2>(), Err(DecimalError::InvalidDigit));
+    assert_eq!("1.234".parse::<Fixed2>(), Err(DecimalError::Precision));
+    assert_eq!(Fixed2::MIN.to_string().parse::<Fixed2>(), Ok(Fixed2::MIN));
+  }
+
+  #[test]
+  fn test_rounding() {
+    let value: Fixed2 = fixed("1.25");
+    let half: Fixed2 = fixed("0.5");
+
+    assert_eq!(value.checked_mul(half, Rounding::Floor), Some(fixed("0.62")));
+    assert_eq!(value.checked_mul(half, Rounding::Ceil), Some(fixed("0.63")));
+    assert_eq!(value.checked_mul(half, Rounding::HalfEven), Some(fixed("0.62")));
+    assert_eq!((-value).checked_mul(half, Rounding::Floor), Some(fixed("-0.63")));
+    assert_eq!((-value).checked_mul(half, Rounding::Ceil), Some(fixed("-0.62")));
+    assert_eq!((-value).checked_mul(half, Rounding::HalfEven), Some(fixed("-0.62")));
+    assert_eq!(
+      fixed("1").checked_div(fixed("3"), Rounding::HalfEven),
+      Some(fixed("0.33"))
+    );
+    assert_eq!(
+      fixed("2").checked_div(fixed("3"), Rounding::HalfEven),
+      Some(fixed("0.67"))
+    );
+    assert_eq!(fixed("1").checked_div(Fixed2::ZERO, Rounding::Floor), None);
+    assert_eq!(fixed("2.5").to_integer(Rounding::HalfEven), Some(I256::from(2)));
+    assert_eq!(fixed("3.5").to_integer(Rounding::HalfEven), Some(I256::from(4)));
+    assert_eq!(fixed("2.51").to_integer(Rounding::HalfEven), Some(I256::from(3)));
+    assert_eq!(Fixed2::MAX.checked_add(fixed("0.01")), None);
+    assert_eq!(Fixed2::MAX.checked_mul(fixed("2"), Rounding::Floor), None);
+  }
+
+  #[test]
+  fn test_base_units() {
+    let amount: U256 = U256::from(1_234_567_u32);
+    let value: Fixed2 = Fixed2::from_base_units(amount, 6, Rounding::Floor).unwrap();
+
+    assert_eq!(value, fixed("1.23"));
+    assert_eq!(Fixed2::from_base_units(amount, 6, Rounding::Ceil), Some(fixed("1.24")));
+    assert_eq!(value.to_base_units(6, Rounding::Floor), Some(U256::from(1_230_000_u32)));
+    assert_eq!(
+      fixed("1.25").to_base_units(1, Rounding::HalfEven),
+      Some(U256::from(12_u8))
+    );
+    assert_eq!(fixed("-1").to_base_units(6, Rounding::Floor), None);
+    assert_eq!(
+      value.checked_rescale::<4>(Rounding::Floor),
+      Some("1.23".parse::<Decimal<4>>().unwrap())
+    );
+  }
+}
@@ -0,0 +1,555 @@
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::str;
+use wasmlib::ScAddress;
+use wasmlib::ScAgentId;
+use wasmlib::ScChainId;
+use wasmlib::ScColor;
+use wasmlib::ScHash;
+use wasmlib::ScHname;
+use wasmlib::ScRequestId;
+
+use crate::panic::fail;
+use crate::types::I256;
+use crate::types::U256;
+
+/// A decoder for the byte format produced by [Encoder][crate::types::Encoder].
+///
+/// Unlike [BytesDecoder][wasmlib::BytesDecoder], this keeps track of the
+/// position in the input which allows callers to detect the end of a record
+/// and to recover from malformed input with the `try_*` methods.
+#[derive(Clone, Debug)]
+pub struct Decoder<'a> {
+  data: &'a [u8],
+  offset: usize,
+}
+
+impl<'a> Decoder<'a> {
+  /// Creates a new `Decoder` for the given slice of bytes.
+  pub const fn new(data: &'a [u8]) -> Self {
+    Self { data, offset: 0 }
+  }
+
+  /// Returns the number of bytes consumed so far.
+  pub const fn offset(&self) -> usize {
+    self.offset
+  }
+
+  /// Returns the number of bytes left to decode.
+  pub const fn remaining(&self) -> usize {
+    self.data.len() - self.offset
+  }
+
+  /// Returns `true` if all bytes have been consumed.
+  pub const fn is_empty(&self) -> bool {
+    self.remaining() == 0
+  }
+
+  /// Returns a [DecodeError] at the current offset.
+  pub const fn error(&self, expected: &'static str, kind: DecodeErrorKind) -> DecodeError {
+    DecodeError::new(self.offset, expected, kind)
+  }
+
+  /// Decodes an [address][ScAddress].
+  pub fn address(&mut self) -> ScAddress {
+    check(self.try_address())
+  }
+
+  /// Decodes an [agent id][ScAgentId].
+  pub fn agent_id(&mut self) -> ScAgentId {
+    check(self.try_agent_id())
+  }
+
+  /// Decodes a boolean flag.
+  pub fn bool(&mut self) -> bool {
+    check(self.try_bool())
+  }
+
+  /// Decodes a length-prefixed slice of bytes.
+  pub fn bytes(&mut self) -> &'a [u8] {
+    check(self.try_bytes())
+  }
+
+  /// Decodes a [chain id][ScChainId].
+  pub fn chain_id(&mut self) -> ScChainId {
+    check(self.try_chain_id())
+  }
+
+  /// Decodes a [color][ScColor].
+  pub fn color(&mut self) -> ScColor {
+    check(self.try_color())
+  }
+
+  /// Decodes a [hash][ScHash].
+  pub fn hash(&mut self) -> ScHash {
+    check(self.try_hash())
+  }
+
+  /// Decodes a [hash name][ScHname].
+  pub fn hname(&mut self) -> ScHname {
+    check(self.try_hname())
+  }
+
+  /// Decodes a [256-bit signed integer][I256].
+  pub fn i256(&mut self) -> I256 {
+    check(self.try_i256())
+  }
+
+  /// Decodes a signed LEB128 integer.
+  pub fn int64(&mut self) -> i64 {
+    check(self.try_int64())
+  }
+
+  /// Decodes the length prefix of a sequence.
+  pub fn length(&mut self, expected: &'static str) -> usize {
+    check(self.try_length(expected))
+  }
+
+  /// Decodes the contents of a length-prefixed record.
+  pub fn record(&mut self, expected: &'static str) -> Decoder<'a> {
+    check(self.try_record(expected))
+  }
+
+  /// Decodes a [request id][ScRequestId].
+  pub fn request_id(&mut self) -> ScRequestId {
+    check(self.try_request_id())
+  }
+
+  /// Decodes a UTF-8 string.
+  pub fn string(&mut self) -> String {
+    check(self.try_string())
+  }
+
+  /// Decodes a [256-bit unsigned integer][U256].
+  pub fn u256(&mut self) -> U256 {
+    check(self.try_u256())
+  }
+
+  /// Decodes the version of a record, which must not exceed `current`.
+  pub fn version(&mut self, expected: &'static str, current: i64) -> i64 {
+    check(self.try_version(expected, current))
+  }
+
+  /// Checks that all bytes have been consumed.
+  pub fn finish(&self, expected: &'static str) {
+    check(self.try_finish(expected))
+  }
+
+  /// Decodes an [address][ScAddress].
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the input is truncated or malformed.
+  pub fn try_address(&mut self) -> Result<ScAddress, DecodeError> {
+    self.try_fixed("ScAddress", 33).map(ScAddress::from_bytes)
+  }
+
+  /// Decodes an [agent id][ScAgentId].
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the input is truncated or malformed.
+  pub fn try_agent_id(&mut self) -> Result<ScAgentId, DecodeError> {
+    self.try_fixed("ScAgentId", 37).map(ScAgentId::from_bytes)
+  }
+
+  /// Decodes a boolean flag.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the input is truncated or the flag is not `0` or `1`.
+  pub fn try_bool(&mut self) -> Result<bool, DecodeError> {
+    let start: usize = self.offset;
+
+    match self.try_int64()? {
+      0 => Ok(false),
+      1 => Ok(true),
+      flag => Err(DecodeError::new(start, "bool", DecodeErrorKind::Tag(flag))),
+    }
+  }
+
+  /// Decodes a length-prefixed slice of bytes.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the input is truncated or malformed.
+  pub fn try_bytes(&mut self) -> Result<&'a [u8], DecodeError> {
+    self.try_sized("ScBytes")
+  }
+
+  /// Decodes a [chain id][ScChainId].
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the input is truncated or malformed.
+  pub fn try_chain_id(&mut self) -> Result<ScChainId, DecodeError> {
+    self.try_fixed("ScChainId", 33).map(ScChainId::from_bytes)
+  }
+
+  /// Decodes a [color][ScColor].
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the input is truncated or malformed.
+  pub fn try_color(&mut self) -> Result<ScColor, DecodeError> {
+    self.try_fixed("ScColor", 32).map(ScColor::from_bytes)
+  }
+
+  /// Decodes a [hash][ScHash].
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the input is truncated or malformed.
+  pub fn try_hash(&mut self) -> Result<ScHash, DecodeError> {
+    self.try_fixed("ScHash", 32).map(ScHash::from_bytes)
+  }
+
+  /// Decodes a [hash name][ScHname].
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the input is truncated or malformed.
+  pub fn try_hname(&mut self) -> Result<ScHname, DecodeError> {
+    self.try_fixed("ScHname", 4).map(ScHname::from_bytes)
+  }
+
+  /// Decodes a [256-bit signed integer][I256].
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the input is truncated or malformed.
+  pub fn try_i256(&mut self) -> Result<I256, DecodeError> {
+    self.try_take("I256", 32).map(|bytes| I256::from_le_bytes(array(bytes)))
+  }
+
+  /// Decodes a signed LEB128 integer.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the input is truncated or malformed.
+  pub fn try_int64(&mut self) -> Result<i64, DecodeError> {
+    let start: usize = self.offset;
+    let mut value: i64 = 0;
+    let mut shift: u32 = 0;
+
+    loop {
+      let byte: u8 = self.try_take("ScInt64", 1)?[0];
+
+      // The 10th byte only holds the sign bit and must be the final byte
+      if shift == 63 && byte != 0x00 && byte != 0x7f {
+        return Err(DecodeError::new(start, "ScInt64", DecodeErrorKind::Overflow));
+      }
+
+      value |= i64::from(byte & 0x7f) << shift;
+      shift += 7;
+
+      if byte & 0x80 == 0 {
+        if shift < 64 && byte & 0x40 != 0 {
+          value |= -1 << shift;
+        }
+
+        return Ok(value);
+      }
+    }
+  }
+
+  /// Decodes the length prefix of a sequence.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the input is truncated or the length is negative.
+  pub fn try_length(&mut self, expected: &'static str) -> Result<usize, DecodeError> {
+    let start: usize = self.offset;
+    let size: i64 = self.try_int64()?;
+
+    if size < 0 {
+      return Err(DecodeError::new(start, expected, DecodeErrorKind::Length(size)));
+    }
+
+    Ok(size as usize)
+  }
+
+  /// Decodes the contents of a length-prefixed record.
+  ///
+  /// The returned decoder is limited to the contents of the record but
+  /// reports offsets relative to the whole input.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the input is truncated or malformed.
+  pub fn try_record(&mut self, expected: &'static str) -> Result<Decoder<'a>, DecodeError> {
+    let size: usize = self.try_length(expected)?;
+    let start: usize = self.offset;
+
+    self.try_take(expected, size)?;
+
+    Ok(Self {
+      data: &self.data[..self.offset],
+      offset: start,
+    })
+  }
+
+  /// Decodes a [request id][ScRequestId].
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the input is truncated or malformed.
+  pub fn try_request_id(&mut self) -> Result<ScRequestId, DecodeError> {
+    self.try_fixed("ScRequestId", 34).map(ScRequestId::from_bytes)
+  }
+
+  /// Decodes a UTF-8 string.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the input is truncated or not valid UTF-8.
+  pub fn try_string(&mut self) -> Result<String, DecodeError> {
+    let bytes: &'a [u8] = self.try_sized("ScString")?;
+    let start: usize = self.offset - bytes.len();
+
+    match str::from_utf8(bytes) {
+      Ok(string) => Ok(string.to_owned()),
+      Err(error) => Err(DecodeError::new(
+        start + error.valid_up_to(),
+        "ScString",
+        DecodeErrorKind::Utf8,
+      )),
+    }
+  }
+
+  /// Consumes `size` bytes from the input.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if fewer than `size` bytes remain.
+  pub fn try_take(&mut self, expected: &'static str, size: usize) -> Result<&'a [u8], DecodeError> {
+    if size > self.remaining() {
+      return Err(self.error(expected, DecodeErrorKind::Insufficient(size)));
+    }
+
+    let slice: &'a [u8] = &self.data[self.offset..self.offset + size];
+
+    self.offset += size;
+
+    Ok(slice)
+  }
+
+  /// Decodes a [256-bit unsigned integer][U256].
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the input is truncated or malformed.
+  pub fn try_u256(&mut self) -> Result<U256, DecodeError> {
+    self.try_take("U256", 32).map(|bytes| U256::from_le_bytes(array(bytes)))
+  }
+
+  /// Decodes the version of a record, which must not exceed `current`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the input is truncated or the version is negative or
+  /// newer than `current`.
+  pub fn try_version(&mut self, expected: &'static str, current: i64) -> Result<i64, DecodeError> {
+    let start: usize = self.offset;
+    let version: i64 = self.try_int64()?;
+
+    if version < 0 || version > current {
+      return Err(DecodeError::new(start, expected, DecodeErrorKind::Version(version)));
+    }
+
+    Ok(version)
+  }
+
+  /// Checks that all bytes have been consumed.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if any bytes remain.
+  pub fn try_finish(&self, expected: &'static str) -> Result<(), DecodeError> {
+    if self.is_empty() {
+      Ok(())
+    } else {
+      Err(self.error(expected, DecodeErrorKind::Trailing(self.remaining())))
+    }
+  }
+
+  fn try_sized(&mut self, expected: &'static str) -> Result<&'a [u8], DecodeError> {
+    let size: usize = self.try_length(expected)?;
+
+    self.try_take(expected, size)
+  }
+
+  fn try_fixed(&mut self, expected: &'static str, size: usize) -> Result<&'a [u8], DecodeError> {
+    let start: usize = self.offset;
+    let bytes: &'a [u8] = self.try_sized(expected)?;
+
+    if bytes.len() != size {
+      return Err(DecodeError::new(
+        start,
+        expected,
+        DecodeErrorKind::Length(bytes.len() as i64),
+      ));
+    }
+
+    Ok(bytes)
+  }
+}
+
+/// Copies a slice with a checked length into an array.
+fn array(bytes: &[u8]) -> [u8; 32] {
+  let mut output: [u8; 32] = [0; 32];
+  output.copy_from_slice(bytes);
+  output
+}
+
+/// Unwraps a decoding result, panicking with a descriptive message on error.
+pub(crate) fn check<T>(result: Result<T, DecodeError>) -> T {
+  match result {
+    Ok(value) => value,
+    Err(error) => fail(&error.to_string()),
+  }
+}
+
+// =============================================================================
+// =============================================================================
+
+/// The reason a value could not be decoded.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum DecodeErrorKind {
+  /// The input ended before the given number of bytes could be read.
+  Insufficient(usize),
+  /// A length prefix did not match the size of the type.
+  Length(i64),
+  /// An integer was encoded with too many bytes.
+  Overflow,
+  /// An enum tag did not match any variant.
+  Tag(i64),
+  /// The given number of bytes were left at the end of a record.
+  Trailing(usize),
+  /// The keys of a map or set were not in strictly ascending order.
+  Unordered,
+  /// A string was not valid UTF-8.
+  Utf8,
+  /// A record was written by an unsupported version.
+  Version(i64),
+}
+
+/// An error encountered while decoding a value.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct DecodeError {
+  offset: usize,
+  expected: &'static str,
+  kind: DecodeErrorKind,
+}
+
+impl DecodeError {
+  /// Creates a new `DecodeError`.
+  pub const fn new(offset: usize, expected: &'static str, kind: DecodeErrorKind) -> Self {
+    Self { offset, expected, kind }
+  }
+
+  /// Returns the offset in the input where the error occurred.
+  pub const fn offset(&self) -> usize {
+    self.offset
+  }
+
+  /// Returns the name of the type that was being decoded.
+  pub const fn expected(&self) -> &'static str {
+    self.expected
+  }
+
+  /// Returns the reason for the error.
+  pub const fn kind(&self) -> DecodeErrorKind {
+    self.kind
+  }
+}
+
+impl Display for DecodeError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    write!(f, "failed to decode `{}` at offset {}: ", self.expected, self.offset)?;
+
+    match self.kind {
+      DecodeErrorKind::Insufficient(size) => write!(f, "insufficient bytes (need {})", size),
+      DecodeErrorKind::Length(size) => write!(f, "invalid length ({})", size),
+      DecodeErrorKind::Overflow => f.write_str("integer representation too long"),
+      DecodeErrorKind::Tag(tag) => write!(f, "invalid tag ({})", tag),
+      DecodeErrorKind::Trailing(size) => write!(f, "trailing bytes ({})", size),
+      DecodeErrorKind::Unordered => f.write_str("keys not in ascending order"),
+      DecodeErrorKind::Utf8 => f.write_str("invalid UTF-8"),
+      DecodeErrorKind::Version(version) => write!(f, "unsupported version ({})", version),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use wasmlib::BytesEncoder;
+
+  use super::*;
+
+  fn encode_int64(value: i64) -> Vec<u8> {
+    BytesEncoder::new().int64(value).data()
+  }
+
+  #[test]
+  fn test_int64() {
+    let values: [i64; 9] = [0, 1, -1, 63, 64, -64, -65, i64::MAX, i64::MIN];
+
+    for value in values {
+      let bytes: Vec<u8> = encode_int64(value);
+      let mut decoder: Decoder<'_> = Decoder::new(&bytes);
+
+      assert_eq!(decoder.try_int64(), Ok(value));
+      assert!(decoder.is_empty());
+    }
+
+    assert_eq!(encode_int64(i64::MAX)[9], 0x00);
+    assert_eq!(encode_int64(i64::MIN)[9], 0x7f);
+  }
+
+  #[test]
+  fn test_int64_overflow() {
+    let mut bytes: Vec<u8> = encode_int64(i64::MAX);
+
+    // Bits beyond the sign bit in the 10th byte
+    for last in [0x01, 0x3f, 0x40, 0x7e] {
+      bytes[9] = last;
+
+      let error: DecodeError = Decoder::new(&bytes).try_int64().unwrap_err();
+
+      assert_eq!((error.offset(), error.kind()), (0, DecodeErrorKind::Overflow));
+    }
+
+    bytes[9] = 0x80;
+    bytes.push(0x00);
+
+    assert_eq!(
+      Decoder::new(&bytes).try_int64().map_err(|error| error.kind()),
+      Err(DecodeErrorKind::Overflow)
+    );
+
+    assert_eq!(
+      Decoder::new(&[0x80, 0x80]).try_int64().map_err(|error| error.kind()),
+      Err(DecodeErrorKind::Insufficient(1))
+    );
+  }
+
+  #[test]
+  fn test_string() {
+    let bytes: Vec<u8> = BytesEncoder::new().string("héllo").data();
+
+    assert_eq!(Decoder::new(&bytes).try_string(), Ok("héllo".to_string()));
+
+    let bytes: Vec<u8> = BytesEncoder::new().int64(0).bytes(&[b'o', b'k', 0xff]).data();
+    let mut decoder: Decoder<'_> = Decoder::new(&bytes);
+
+    assert_eq!(decoder.try_int64(), Ok(0));
+
+    let error: DecodeError = decoder.try_string().unwrap_err();
+
+    assert_eq!(error.expected(), "ScString");
+    assert_eq!(error.offset(), 4);
+    assert_eq!(error.kind(), DecodeErrorKind::Utf8);
+  }
+}
@@ -0,0 +1,143 @@
+use wasmlib::ScAddress;
+use wasmlib::ScAgentId;
+use wasmlib::ScChainId;
+use wasmlib::ScColor;
+use wasmlib::ScHash;
+use wasmlib::ScHname;
+use wasmlib::ScRequestId;
+
+use crate::types::I256;
+use crate::types::U256;
+
+/// An encoder for the byte format read by [Decoder][crate::types::Decoder].
+///
+/// This writes the same format as [BytesEncoder][wasmlib::BytesEncoder] and
+/// additionally supports values without a length prefix, such as [U256] and
+/// [I256].
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Encoder {
+  data: Vec<u8>,
+}
+
+impl Encoder {
+  /// Creates a new, empty `Encoder`.
+  pub const fn new() -> Self {
+    Self { data: Vec::new() }
+  }
+
+  /// Returns the encoded bytes.
+  pub fn data(&self) -> Vec<u8> {
+    self.data.clone()
+  }
+
+  /// Encodes an [address][ScAddress].
+  pub fn address(&mut self, value: &ScAddress) -> &mut Self {
+    self.bytes(value.to_bytes())
+  }
+
+  /// Encodes an [agent id][ScAgentId].
+  pub fn agent_id(&mut self, value: &ScAgentId) -> &mut Self {
+    self.bytes(value.to_bytes())
+  }
+
+  /// Encodes a slice of bytes with an `Int64` length prefix.
+  pub fn bytes(&mut self, value: &[u8]) -> &mut Self {
+    self.int64(value.len() as i64);
+    self.fixed(value)
+  }
+
+  /// Encodes a [chain id][ScChainId].
+  pub fn chain_id(&mut self, value: &ScChainId) -> &mut Self {
+    self.bytes(value.to_bytes())
+  }
+
+  /// Encodes a [color][ScColor].
+  pub fn color(&mut self, value: &ScColor) -> &mut Self {
+    self.bytes(value.to_bytes())
+  }
+
+  /// Encodes a slice of bytes as-is, without a length prefix.
+  ///
+  /// The size of the value must be known to the decoder.
+  pub fn fixed(&mut self, value: &[u8]) -> &mut Self {
+    self.data.extend_from_slice(value);
+    self
+  }
+
+  /// Encodes a [hash][ScHash].
+  pub fn hash(&mut self, value: &ScHash) -> &mut Self {
+    self.bytes(value.to_bytes())
+  }
+
+  /// Encodes a [hash name][ScHname].
+  pub fn hname(&mut self, value: &ScHname) -> &mut Self {
+    self.bytes(&value.to_bytes())
+  }
+
+  /// Encodes a [256-bit signed integer][I256] as 32 little-endian bytes.
+  pub fn i256(&mut self, value: &I256) -> &mut Self {
+    self.fixed(&value.to_le_bytes())
+  }
+
+  /// Encodes a signed LEB128 integer.
+  pub fn int64(&mut self, mut value: i64) -> &mut Self {
+    loop {
+      let byte: u8 = value as u8;
+      let sign: u8 = byte & 0x40;
+
+      value >>= 7;
+
+      if (value == 0 && sign == 0) || (value == -1 && sign != 0) {
+        self.data.push(byte & 0x7f);
+        return self;
+      }
+
+      self.data.push(byte | 0x80);
+    }
+  }
+
+  /// Encodes a [request id][ScRequestId].
+  pub fn request_id(&mut self, value: &ScRequestId) -> &mut Self {
+    self.bytes(value.to_bytes())
+  }
+
+  /// Encodes a string.
+  pub fn string(&mut self, value: &str) -> &mut Self {
+    self.bytes(value.as_bytes())
+  }
+
+  /// Encodes a [256-bit unsigned integer][U256] as 32 little-endian bytes.
+  pub fn u256(&mut self, value: &U256) -> &mut Self {
+    self.fixed(&value.to_le_bytes())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use wasmlib::BytesEncoder;
+
+  use super::*;
+
+  #[test]
+  fn test_wasmlib_format() {
+    for value in [0, 1, -1, 63, 64, -64, -65, i64::MIN, i64::MAX] {
+      assert_eq!(
+        Encoder::new().int64(value).data(),
+        BytesEncoder::new().int64(value).data()
+      );
+    }
+
+    assert_eq!(
+      Encoder::new().string("scarab").bytes(&[1, 2]).data(),
+      BytesEncoder::new().string("scarab").bytes(&[1, 2]).data()
+    );
+  }
+
+  #[test]
+  fn test_int256() {
+    let value: U256 = U256::from(0x0102_u32);
+
+    assert_eq!(Encoder::new().u256(&value).data(), value.to_le_bytes());
+    assert_eq!(Encoder::new().i256(&I256::from(-1_i8)).data(), [0xff; 32]);
+  }
+}
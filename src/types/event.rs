@@ -0,0 +1,182 @@
+use core::convert::TryInto;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::str;
+
+use crate::traits::utility::Event;
+use crate::traits::utility::TryDecode;
+use crate::types::DecodeError;
+use crate::types::DecodeErrorKind;
+
+/// An error returned when decoding an event fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EventError {
+  /// The record is too short to contain a timestamp.
+  Record,
+  /// The event text is not in the `{name} {hex}` format.
+  Format,
+  /// The event has a different name than the expected event.
+  Name(String),
+  /// The event data could not be decoded.
+  Decode(DecodeError),
+}
+
+impl Display for EventError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    match self {
+      Self::Record => f.write_str("event record too short"),
+      Self::Format => f.write_str("invalid event format"),
+      Self::Name(name) => write!(f, "unexpected event: {}", name),
+      Self::Decode(error) => Display::fmt(error, f),
+    }
+  }
+}
+
+impl From<DecodeError> for EventError {
+  fn from(other: DecodeError) -> Self {
+    Self::Decode(other)
+  }
+}
+
+/// A record of the event log, as returned by
+/// [EventLog::search][crate::contracts::core::EventLog::search].
+///
+/// Records consist of the `Int64` timestamp of the event followed by the
+/// event text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EventRecord {
+  timestamp: i64,
+  text: String,
+}
+
+impl EventRecord {
+  /// Parses an event log record.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the record is too short to contain a timestamp or
+  /// the event text is not valid UTF-8.
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self, EventError> {
+    if bytes.len() < 8 {
+      return Err(EventError::Record);
+    }
+
+    let (timestamp, text): (&[u8], &[u8]) = bytes.split_at(8);
+
+    let text: &str = str::from_utf8(text)
+      .map_err(|error| DecodeError::new(8 + error.valid_up_to(), "EventRecord", DecodeErrorKind::Utf8))?;
+
+    Ok(Self {
+      timestamp: i64::from_le_bytes(timestamp.try_into().map_err(|_| EventError::Record)?),
+      text: text.to_string(),
+    })
+  }
+
+  /// Returns the timestamp of the event.
+  pub const fn timestamp(&self) -> i64 {
+    self.timestamp
+  }
+
+  /// Returns the text of the event.
+  pub fn text(&self) -> &str {
+    &self.text
+  }
+
+  /// Returns the name of the event, if it was emitted by [Event::emit].
+  pub fn name(&self) -> Option<&str> {
+    split(&self.text).map(|(name, _)| name)
+  }
+
+  /// Returns `true` if the record contains an event of type `E`.
+  pub fn is<E: Event>(&self) -> bool {
+    self.name() == Some(E::NAME)
+  }
+
+  /// Decodes the event.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the record does not contain an event of type `E`.
+  pub fn decode<E: Event + TryDecode>(&self) -> Result<E, EventError> {
+    E::from_text(&self.text)
+  }
+}
+
+/// Returns the text an event is emitted as: its name, followed by a space and
+/// the hex-encoded bytes of the event.
+pub(crate) fn encode_text(name: &str, bytes: &[u8]) -> String {
+  let mut output: String = String::with_capacity(name.len() + 1 + bytes.len() * 2);
+
+  output.push_str(name);
+  output.push(' ');
+
+  for byte in bytes {
+    output.push_str(&format!("{:02x}", byte));
+  }
+
+  output
+}
+
+/// Decodes an event from the text it was emitted as.
+pub(crate) fn decode_text<E: Event + TryDecode>(text: &str) -> Result<E, EventError> {
+  let (name, data): (&str, &str) = split(text).ok_or(EventError::Format)?;
+
+  if name != E::NAME {
+    return Err(EventError::Name(name.to_string()));
+  }
+
+  if data.len() % 2 != 0 || !data.is_ascii() {
+    return Err(EventError::Format);
+  }
+
+  let bytes: Vec<u8> = (0..data.len())
+    .step_by(2)
+    .map(|index| u8::from_str_radix(&data[index..index + 2], 16))
+    .collect::<Result<_, _>>()
+    .map_err(|_| EventError::Format)?;
+
+  E::try_from_bytes(&bytes).map_err(Into::into)
+}
+
+fn split(text: &str) -> Option<(&str, &str)> {
+  let index: usize = text.find(' ')?;
+
+  Some((&text[..index], &text[index + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn record(timestamp: i64, text: &[u8]) -> Vec<u8> {
+    let mut output: Vec<u8> = timestamp.to_le_bytes().to_vec();
+
+    output.extend_from_slice(text);
+    output
+  }
+
+  #[test]
+  fn test_record_from_bytes() {
+    let record: EventRecord = EventRecord::from_bytes(&record(-5, "Pinged 01 ✓".as_bytes())).unwrap();
+
+    assert_eq!(record.timestamp(), -5);
+    assert_eq!(record.text(), "Pinged 01 ✓");
+    assert_eq!(record.name(), Some("Pinged"));
+    assert_eq!(EventRecord::from_bytes(&[0; 7]), Err(EventError::Record));
+  }
+
+  #[test]
+  fn test_record_invalid_utf8() {
+    let error: EventError = EventRecord::from_bytes(&record(1, b"Pinged \xff")).unwrap_err();
+
+    assert_eq!(
+      error,
+      EventError::Decode(DecodeError::new(15, "EventRecord", DecodeErrorKind::Utf8))
+    );
+    assert_eq!(
+      error.to_string(),
+      "failed to decode `EventRecord` at offset 15: invalid UTF-8"
+    );
+  }
+}
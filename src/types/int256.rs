@@ -0,0 +1,961 @@
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt::Debug;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::ops::Add;
+use core::ops::AddAssign;
+use core::ops::Div;
+use core::ops::DivAssign;
+use core::ops::Mul;
+use core::ops::MulAssign;
+use core::ops::Neg;
+use core::ops::Rem;
+use core::ops::RemAssign;
+use core::ops::Sub;
+use core::ops::SubAssign;
+use core::str::FromStr;
+
+use crate::panic::fail;
+
+/// The largest power of ten that fits in a `u64`.
+const TEN19: u64 = 10_000_000_000_000_000_000;
+
+/// An error returned when converting or parsing a 256-bit integer fails.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum IntError {
+  /// The string to parse was empty.
+  Empty,
+  /// The string to parse contained a character that is not a decimal digit.
+  InvalidDigit,
+  /// The value does not fit in the target type.
+  Overflow,
+}
+
+impl Display for IntError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    match self {
+      Self::Empty => f.write_str("cannot parse integer from empty string"),
+      Self::InvalidDigit => f.write_str("invalid digit found in string"),
+      Self::Overflow => f.write_str("integer out of range for the target type"),
+    }
+  }
+}
+
+// =============================================================================
+// U256
+// =============================================================================
+
+/// A 256-bit unsigned integer.
+///
+/// Values are stored in WasmLib maps and encoded as 32 little-endian bytes.
+#[derive(Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub struct U256 {
+  limbs: [u64; 4],
+}
+
+impl U256 {
+  /// The size of this integer type in bits.
+  pub const BITS: u32 = 256;
+
+  /// The value `0`.
+  pub const ZERO: Self = Self { limbs: [0; 4] };
+
+  /// The value `1`.
+  pub const ONE: Self = Self { limbs: [1, 0, 0, 0] };
+
+  /// The smallest value that can be represented by this integer type.
+  pub const MIN: Self = Self::ZERO;
+
+  /// The largest value that can be represented by this integer type.
+  pub const MAX: Self = Self { limbs: [u64::MAX; 4] };
+
+  /// Creates a new `U256` from a `u128`.
+  pub const fn from_u128(value: u128) -> Self {
+    Self {
+      limbs: [value as u64, (value >> 64) as u64, 0, 0],
+    }
+  }
+
+  /// Creates a new `U256` from its little-endian byte representation.
+  pub fn from_le_bytes(bytes: [u8; 32]) -> Self {
+    let mut limbs: [u64; 4] = [0; 4];
+
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks_exact(8)) {
+      let mut buffer: [u8; 8] = [0; 8];
+      buffer.copy_from_slice(chunk);
+      *limb = u64::from_le_bytes(buffer);
+    }
+
+    Self { limbs }
+  }
+
+  /// Creates a new `U256` from its big-endian byte representation.
+  pub fn from_be_bytes(mut bytes: [u8; 32]) -> Self {
+    bytes.reverse();
+    Self::from_le_bytes(bytes)
+  }
+
+  /// Returns the memory representation of the integer in little-endian byte order.
+  pub fn to_le_bytes(self) -> [u8; 32] {
+    let mut bytes: [u8; 32] = [0; 32];
+
+    for (chunk, limb) in bytes.chunks_exact_mut(8).zip(self.limbs.iter()) {
+      chunk.copy_from_slice(&limb.to_le_bytes());
+    }
+
+    bytes
+  }
+
+  /// Returns the memory representation of the integer in big-endian byte order.
+  pub fn to_be_bytes(self) -> [u8; 32] {
+    let mut bytes: [u8; 32] = self.to_le_bytes();
+    bytes.reverse();
+    bytes
+  }
+
+  /// Returns `true` if the value is `0`.
+  pub const fn is_zero(&self) -> bool {
+    self.limbs[0] == 0 && self.limbs[1] == 0 && self.limbs[2] == 0 && self.limbs[3] == 0
+  }
+
+  /// Returns the number of leading zeros in the binary representation.
+  pub fn leading_zeros(self) -> u32 {
+    let mut zeros: u32 = 0;
+
+    for limb in self.limbs.iter().rev() {
+      zeros += limb.leading_zeros();
+
+      if *limb != 0 {
+        break;
+      }
+    }
+
+    zeros
+  }
+
+  /// Calculates `self + rhs`, returning whether an arithmetic overflow occurred.
+  pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+    let mut limbs: [u64; 4] = [0; 4];
+    let mut carry: bool = false;
+
+    for (index, limb) in limbs.iter_mut().enumerate() {
+      let (sum, a): (u64, bool) = self.limbs[index].overflowing_add(rhs.limbs[index]);
+      let (sum, b): (u64, bool) = sum.overflowing_add(carry as u64);
+
+      *limb = sum;
+      carry = a || b;
+    }
+
+    (Self { limbs }, carry)
+  }
+
+  /// Calculates `self - rhs`, returning whether an arithmetic overflow occurred.
+  pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+    let mut limbs: [u64; 4] = [0; 4];
+    let mut borrow: bool = false;
+
+    for (index, limb) in limbs.iter_mut().enumerate() {
+      let (diff, a): (u64, bool) = self.limbs[index].overflowing_sub(rhs.limbs[index]);
+      let (diff, b): (u64, bool) = diff.overflowing_sub(borrow as u64);
+
+      *limb = diff;
+      borrow = a || b;
+    }
+
+    (Self { limbs }, borrow)
+  }
+
+  /// Calculates `self * rhs`, returning whether an arithmetic overflow occurred.
+  pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+    let mut output: [u64; 8] = [0; 8];
+
+    for i in 0..4 {
+      let mut carry: u128 = 0;
+
+      for j in 0..4 {
+        let value: u128 = u128::from(self.limbs[i]) * u128::from(rhs.limbs[j]) + u128::from(output[i + j]) + carry;
+
+        output[i + j] = value as u64;
+        carry = value >> 64;
+      }
+
+      output[i + 4] = carry as u64;
+    }
+
+    let limbs: [u64; 4] = [output[0], output[1], output[2], output[3]];
+
+    (Self { limbs }, output[4..].iter().any(|limb| *limb != 0))
+  }
+
+  /// Checked integer addition. Returns `None` if overflow occurred.
+  pub fn checked_add(self, rhs: Self) -> Option<Self> {
+    checked(self.overflowing_add(rhs))
+  }
+
+  /// Checked integer subtraction. Returns `None` if overflow occurred.
+  pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+    checked(self.overflowing_sub(rhs))
+  }
+
+  /// Checked integer multiplication. Returns `None` if overflow occurred.
+  pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+    checked(self.overflowing_mul(rhs))
+  }
+
+  /// Checked integer division. Returns `None` if `rhs == 0`.
+  pub fn checked_div(self, rhs: Self) -> Option<Self> {
+    self.checked_div_rem(rhs).map(|(quotient, _)| quotient)
+  }
+
+  /// Checked integer remainder. Returns `None` if `rhs == 0`.
+  pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+    self.checked_div_rem(rhs).map(|(_, remainder)| remainder)
+  }
+
+  /// Checked exponentiation. Returns `None` if overflow occurred.
+  pub fn checked_pow(self, mut exp: u32) -> Option<Self> {
+    let mut base: Self = self;
+    let mut output: Self = Self::ONE;
+
+    while exp > 0 {
+      if exp & 1 == 1 {
+        output = output.checked_mul(base)?;
+      }
+
+      exp >>= 1;
+
+      if exp > 0 {
+        base = base.checked_mul(base)?;
+      }
+    }
+
+    Some(output)
+  }
+
+  /// Wrapping (modular) addition.
+  pub fn wrapping_add(self, rhs: Self) -> Self {
+    self.overflowing_add(rhs).0
+  }
+
+  /// Wrapping (modular) subtraction.
+  pub fn wrapping_sub(self, rhs: Self) -> Self {
+    self.overflowing_sub(rhs).0
+  }
+
+  /// Wrapping (modular) multiplication.
+  pub fn wrapping_mul(self, rhs: Self) -> Self {
+    self.overflowing_mul(rhs).0
+  }
+
+  /// Saturating integer addition.
+  pub fn saturating_add(self, rhs: Self) -> Self {
+    self.checked_add(rhs).unwrap_or(Self::MAX)
+  }
+
+  /// Saturating integer subtraction.
+  pub fn saturating_sub(self, rhs: Self) -> Self {
+    self.checked_sub(rhs).unwrap_or(Self::MIN)
+  }
+
+  /// Saturating integer multiplication.
+  pub fn saturating_mul(self, rhs: Self) -> Self {
+    self.checked_mul(rhs).unwrap_or(Self::MAX)
+  }
+
+  /// Saturating exponentiation.
+  pub fn saturating_pow(self, exp: u32) -> Self {
+    self.checked_pow(exp).unwrap_or(Self::MAX)
+  }
+
+  /// Returns the quotient and remainder of `self / rhs`, or `None` if `rhs == 0`.
+  pub fn checked_div_rem(self, rhs: Self) -> Option<(Self, Self)> {
+    if rhs.is_zero() {
+      return None;
+    }
+
+    if self < rhs {
+      return Some((Self::ZERO, self));
+    }
+
+    if rhs.limbs[1..].iter().all(|limb| *limb == 0) {
+      let (quotient, remainder): (Self, u64) = self.div_rem_u64(rhs.limbs[0]);
+      return Some((quotient, Self::from(remainder)));
+    }
+
+    let mut quotient: Self = Self::ZERO;
+    let mut remainder: Self = Self::ZERO;
+
+    for index in (0..Self::BITS - self.leading_zeros()).rev() {
+      // The shifted-out bit means the remainder exceeds `rhs`
+      let carry: bool = remainder.bit(255);
+
+      remainder = remainder.shl1();
+      remainder.limbs[0] |= self.bit(index) as u64;
+
+      if carry || remainder >= rhs {
+        remainder = remainder.wrapping_sub(rhs);
+        quotient.limbs[(index / 64) as usize] |= 1 << (index % 64);
+      }
+    }
+
+    Some((quotient, remainder))
+  }
+
+  /// Returns the value as a `u128` if it fits.
+  pub(crate) fn to_u128(self) -> Option<u128> {
+    if self.limbs[2] == 0 && self.limbs[3] == 0 {
+      Some(u128::from(self.limbs[0]) | (u128::from(self.limbs[1]) << 64))
+    } else {
+      None
+    }
+  }
+
+  fn div_rem_u64(self, rhs: u64) -> (Self, u64) {
+    let mut limbs: [u64; 4] = [0; 4];
+    let mut remainder: u128 = 0;
+
+    for index in (0..4).rev() {
+      let value: u128 = (remainder << 64) | u128::from(self.limbs[index]);
+
+      limbs[index] = (value / u128::from(rhs)) as u64;
+      remainder = value % u128::from(rhs);
+    }
+
+    (Self { limbs }, remainder as u64)
+  }
+
+  fn bit(&self, index: u32) -> bool {
+    (self.limbs[(index / 64) as usize] >> (index % 64)) & 1 == 1
+  }
+
+  fn shl1(self) -> Self {
+    let mut limbs: [u64; 4] = [0; 4];
+
+    for (index, limb) in limbs.iter_mut().enumerate() {
+      *limb = self.limbs[index] << 1;
+
+      if index > 0 {
+        *limb |= self.limbs[index - 1] >> 63;
+      }
+    }
+
+    Self { limbs }
+  }
+}
+
+impl Ord for U256 {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.limbs.iter().rev().cmp(other.limbs.iter().rev())
+  }
+}
+
+impl PartialOrd for U256 {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Display for U256 {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    let mut chunks: Vec<u64> = Vec::new();
+    let mut value: Self = *self;
+
+    while !value.is_zero() {
+      let (quotient, remainder): (Self, u64) = value.div_rem_u64(TEN19);
+
+      chunks.push(remainder);
+      value = quotient;
+    }
+
+    let mut output: String = chunks.pop().unwrap_or_default().to_string();
+
+    for chunk in chunks.iter().rev() {
+      output.push_str(&format!("{:019}", chunk));
+    }
+
+    f.pad_integral(true, "", &output)
+  }
+}
+
+impl Debug for U256 {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    Display::fmt(self, f)
+  }
+}
+
+impl FromStr for U256 {
+  type Err = IntError;
+
+  fn from_str(string: &str) -> Result<Self, Self::Err> {
+    let digits: &str = string.strip_prefix('+').unwrap_or(string);
+
+    if digits.is_empty() {
+      return Err(IntError::Empty);
+    }
+
+    let mut output: Self = Self::ZERO;
+
+    for char in digits.chars() {
+      let digit: u32 = char.to_digit(10).ok_or(IntError::InvalidDigit)?;
+
+      output = output
+        .checked_mul(Self::from(10_u8))
+        .and_then(|output| output.checked_add(Self::from(digit)))
+        .ok_or(IntError::Overflow)?;
+    }
+
+    Ok(output)
+  }
+}
+
+impl TryFrom<I256> for U256 {
+  type Error = IntError;
+
+  fn try_from(other: I256) -> Result<Self, Self::Error> {
+    if other.is_negative() {
+      Err(IntError::Overflow)
+    } else {
+      Ok(other.bits)
+    }
+  }
+}
+
+// =============================================================================
+// I256
+// =============================================================================
+
+/// A 256-bit signed integer in two's complement representation.
+///
+/// Values are stored in WasmLib maps and encoded as 32 little-endian bytes.
+#[derive(Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub struct I256 {
+  bits: U256,
+}
+
+impl I256 {
+  /// The size of this integer type in bits.
+  pub const BITS: u32 = 256;
+
+  /// The value `0`.
+  pub const ZERO: Self = Self { bits: U256::ZERO };
+
+  /// The value `1`.
+  pub const ONE: Self = Self { bits: U256::ONE };
+
+  /// The smallest value that can be represented by this integer type.
+  pub const MIN: Self = Self {
+    bits: U256 {
+      limbs: [0, 0, 0, 1 << 63],
+    },
+  };
+
+  /// The largest value that can be represented by this integer type.
+  pub const MAX: Self = Self {
+    bits: U256 {
+      limbs: [u64::MAX, u64::MAX, u64::MAX, u64::MAX >> 1],
+    },
+  };
+
+  /// Creates a new `I256` from an `i128`.
+  pub const fn from_i128(value: i128) -> Self {
+    let extend: u64 = if value < 0 { u64::MAX } else { 0 };
+
+    Self {
+      bits: U256 {
+        limbs: [value as u64, (value >> 64) as u64, extend, extend],
+      },
+    }
+  }
+
+  /// Creates a new `I256` from its little-endian byte representation.
+  pub fn from_le_bytes(bytes: [u8; 32]) -> Self {
+    Self {
+      bits: U256::from_le_bytes(bytes),
+    }
+  }
+
+  /// Creates a new `I256` from its big-endian byte representation.
+  pub fn from_be_bytes(bytes: [u8; 32]) -> Self {
+    Self {
+      bits: U256::from_be_bytes(bytes),
+    }
+  }
+
+  /// Returns the memory representation of the integer in little-endian byte order.
+  pub fn to_le_bytes(self) -> [u8; 32] {
+    self.bits.to_le_bytes()
+  }
+
+  /// Returns the memory representation of the integer in big-endian byte order.
+  pub fn to_be_bytes(self) -> [u8; 32] {
+    self.bits.to_be_bytes()
+  }
+
+  /// Returns `true` if the value is `0`.
+  pub const fn is_zero(&self) -> bool {
+    self.bits.is_zero()
+  }
+
+  /// Returns `true` if the value is negative.
+  pub const fn is_negative(&self) -> bool {
+    self.bits.limbs[3] >> 63 == 1
+  }
+
+  /// Returns `true` if the value is positive.
+  pub const fn is_positive(&self) -> bool {
+    !self.is_negative() && !self.is_zero()
+  }
+
+  /// Returns the absolute value of `self` as an unsigned integer.
+  pub fn unsigned_abs(self) -> U256 {
+    if self.is_negative() {
+      self.wrapping_neg().bits
+    } else {
+      self.bits
+    }
+  }
+
+  /// Calculates `self + rhs`, returning whether an arithmetic overflow occurred.
+  pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+    let output: Self = Self {
+      bits: self.bits.wrapping_add(rhs.bits),
+    };
+
+    let overflow: bool = self.is_negative() == rhs.is_negative() && output.is_negative() != self.is_negative();
+
+    (output, overflow)
+  }
+
+  /// Calculates `self - rhs`, returning whether an arithmetic overflow occurred.
+  pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+    let output: Self = Self {
+      bits: self.bits.wrapping_sub(rhs.bits),
+    };
+
+    let overflow: bool = self.is_negative() != rhs.is_negative() && output.is_negative() != self.is_negative();
+
+    (output, overflow)
+  }
+
+  /// Calculates `self * rhs`, returning whether an arithmetic overflow occurred.
+  pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+    let output: Self = Self {
+      bits: self.bits.wrapping_mul(rhs.bits),
+    };
+
+    let negative: bool = self.is_negative() != rhs.is_negative();
+    let overflow: bool = match self.unsigned_abs().checked_mul(rhs.unsigned_abs()) {
+      Some(magnitude) => Self::from_magnitude(negative, magnitude).is_none(),
+      None => true,
+    };
+
+    (output, overflow)
+  }
+
+  /// Checked integer addition. Returns `None` if overflow occurred.
+  pub fn checked_add(self, rhs: Self) -> Option<Self> {
+    checked(self.overflowing_add(rhs))
+  }
+
+  /// Checked integer subtraction. Returns `None` if overflow occurred.
+  pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+    checked(self.overflowing_sub(rhs))
+  }
+
+  /// Checked integer multiplication. Returns `None` if overflow occurred.
+  pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+    checked(self.overflowing_mul(rhs))
+  }
+
+  /// Checked integer division. Returns `None` if `rhs == 0` or the division
+  /// results in overflow.
+  pub fn checked_div(self, rhs: Self) -> Option<Self> {
+    self.checked_div_rem(rhs).map(|(quotient, _)| quotient)
+  }
+
+  /// Checked integer remainder. Returns `None` if `rhs == 0` or the division
+  /// results in overflow.
+  pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+    self.checked_div_rem(rhs).map(|(_, remainder)| remainder)
+  }
+
+  /// Checked exponentiation. Returns `None` if overflow occurred.
+  pub fn checked_pow(self, exp: u32) -> Option<Self> {
+    let magnitude: U256 = self.unsigned_abs().checked_pow(exp)?;
+
+    Self::from_magnitude(self.is_negative() && exp % 2 == 1, magnitude)
+  }
+
+  /// Checked negation. Returns `None` if `self == MIN`.
+  pub fn checked_neg(self) -> Option<Self> {
+    if self == Self::MIN {
+      None
+    } else {
+      Some(self.wrapping_neg())
+    }
+  }
+
+  /// Checked absolute value. Returns `None` if `self == MIN`.
+  pub fn checked_abs(self) -> Option<Self> {
+    if self.is_negative() {
+      self.checked_neg()
+    } else {
+      Some(self)
+    }
+  }
+
+  /// Wrapping (modular) addition.
+  pub fn wrapping_add(self, rhs: Self) -> Self {
+    self.overflowing_add(rhs).0
+  }
+
+  /// Wrapping (modular) subtraction.
+  pub fn wrapping_sub(self, rhs: Self) -> Self {
+    self.overflowing_sub(rhs).0
+  }
+
+  /// Wrapping (modular) multiplication.
+  pub fn wrapping_mul(self, rhs: Self) -> Self {
+    self.overflowing_mul(rhs).0
+  }
+
+  /// Wrapping (modular) negation.
+  pub fn wrapping_neg(self) -> Self {
+    Self {
+      bits: U256::ZERO.wrapping_sub(self.bits),
+    }
+  }
+
+  /// Saturating integer addition.
+  pub fn saturating_add(self, rhs: Self) -> Self {
+    match self.checked_add(rhs) {
+      Some(output) => output,
+      None if rhs.is_negative() => Self::MIN,
+      None => Self::MAX,
+    }
+  }
+
+  /// Saturating integer subtraction.
+  pub fn saturating_sub(self, rhs: Self) -> Self {
+    match self.checked_sub(rhs) {
+      Some(output) => output,
+      None if rhs.is_negative() => Self::MAX,
+      None => Self::MIN,
+    }
+  }
+
+  /// Saturating integer multiplication.
+  pub fn saturating_mul(self, rhs: Self) -> Self {
+    match self.checked_mul(rhs) {
+      Some(output) => output,
+      None if self.is_negative() != rhs.is_negative() => Self::MIN,
+      None => Self::MAX,
+    }
+  }
+
+  /// Saturating exponentiation.
+  pub fn saturating_pow(self, exp: u32) -> Self {
+    match self.checked_pow(exp) {
+      Some(output) => output,
+      None if self.is_negative() && exp % 2 == 1 => Self::MIN,
+      None => Self::MAX,
+    }
+  }
+
+  /// Returns the quotient and remainder of `self / rhs`, or `None` if
+  /// `rhs == 0` or the division results in overflow.
+  ///
+  /// The quotient is rounded towards zero.
+  pub fn checked_div_rem(self, rhs: Self) -> Option<(Self, Self)> {
+    let (quotient, remainder): (U256, U256) = self.unsigned_abs().checked_div_rem(rhs.unsigned_abs())?;
+
+    let quotient: Self = Self::from_magnitude(self.is_negative() != rhs.is_negative(), quotient)?;
+    let remainder: Self = Self::from_magnitude(self.is_negative(), remainder)?;
+
+    Some((quotient, remainder))
+  }
+
+  /// Returns the value as an `i128` if it fits.
+  pub(crate) fn to_i128(self) -> Option<i128> {
+    let value: i128 = (u128::from(self.bits.limbs[0]) | (u128::from(self.bits.limbs[1]) << 64)) as i128;
+
+    if Self::from_i128(value) == self {
+      Some(value)
+    } else {
+      None
+    }
+  }
+
+  /// Creates a new `I256` from a sign and magnitude, or `None` if it doesn't fit.
+  fn from_magnitude(negative: bool, magnitude: U256) -> Option<Self> {
+    let value: Self = Self { bits: magnitude };
+
+    match (negative, value.is_negative()) {
+      (false, false) => Some(value),
+      (true, false) => Some(value.wrapping_neg()),
+      (true, true) if value == Self::MIN => Some(value),
+      (_, true) => None,
+    }
+  }
+}
+
+impl Ord for I256 {
+  fn cmp(&self, other: &Self) -> Ordering {
+    match (self.is_negative(), other.is_negative()) {
+      (true, false) => Ordering::Less,
+      (false, true) => Ordering::Greater,
+      _ => self.bits.cmp(&other.bits),
+    }
+  }
+}
+
+impl PartialOrd for I256 {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Display for I256 {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.pad_integral(!self.is_negative(), "", &self.unsigned_abs().to_string())
+  }
+}
+
+impl Debug for I256 {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    Display::fmt(self, f)
+  }
+}
+
+impl FromStr for I256 {
+  type Err = IntError;
+
+  fn from_str(string: &str) -> Result<Self, Self::Err> {
+    let (negative, digits): (bool, &str) = match string.strip_prefix('-') {
+      Some(digits) => (true, digits),
+      None => (false, string),
+    };
+
+    // Reject a second sign after the first
+    if negative && digits.starts_with('+') {
+      return Err(IntError::InvalidDigit);
+    }
+
+    Self::from_magnitude(negative, digits.parse()?).ok_or(IntError::Overflow)
+  }
+}
+
+impl TryFrom<U256> for I256 {
+  type Error = IntError;
+
+  fn try_from(other: U256) -> Result<Self, Self::Error> {
+    Self::from_magnitude(false, other).ok_or(IntError::Overflow)
+  }
+}
+
+impl Neg for I256 {
+  type Output = Self;
+
+  fn neg(self) -> Self::Output {
+    match self.checked_neg() {
+      Some(output) => output,
+      None => fail("attempt to negate with overflow"),
+    }
+  }
+}
+
+// =============================================================================
+// Conversions
+// =============================================================================
+
+macro_rules! impl_Convert {
+  (U256, $($primitive:ty),+ $(,)*) => {
+    $(
+      impl TryFrom<U256> for $primitive {
+        type Error = IntError;
+
+        fn try_from(other: U256) -> Result<Self, Self::Error> {
+          other
+            .to_u128()
+            .and_then(|value| <$primitive>::try_from(value).ok())
+            .ok_or(IntError::Overflow)
+        }
+      }
+    )+
+  };
+  (I256, $($primitive:ty),+ $(,)*) => {
+    $(
+      impl TryFrom<I256> for $primitive {
+        type Error = IntError;
+
+        fn try_from(other: I256) -> Result<Self, Self::Error> {
+          let value: Option<$primitive> = if other.is_negative() {
+            other.to_i128().and_then(|value| <$primitive>::try_from(value).ok())
+          } else {
+            other.bits.to_u128().and_then(|value| <$primitive>::try_from(value).ok())
+          };
+
+          value.ok_or(IntError::Overflow)
+        }
+      }
+    )+
+  };
+  (@from, $ident:ident, $via:ident, $($primitive:ty),+ $(,)*) => {
+    paste::paste! {
+      $(
+        impl From<$primitive> for $ident {
+          fn from(other: $primitive) -> Self {
+            Self::[<from_ $via>](<$via>::from(other))
+          }
+        }
+      )+
+    }
+  };
+  (@try_from, $ident:ident, $via:ident, $($primitive:ty),+ $(,)*) => {
+    paste::paste! {
+      $(
+        impl TryFrom<$primitive> for $ident {
+          type Error = IntError;
+
+          fn try_from(other: $primitive) -> Result<Self, Self::Error> {
+            <$via>::try_from(other)
+              .map(Self::[<from_ $via>])
+              .map_err(|_| IntError::Overflow)
+          }
+        }
+      )+
+    }
+  };
+}
+
+impl_Convert!(@from, U256, u128, u8, u16, u32, u64, u128);
+impl_Convert!(@try_from, U256, u128, i8, i16, i32, i64, i128, usize, isize);
+impl_Convert!(U256, u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, usize, isize);
+
+impl_Convert!(@from, I256, i128, i8, i16, i32, i64, i128, u8, u16, u32, u64);
+impl_Convert!(@try_from, I256, i128, usize, isize);
+impl_Convert!(I256, u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, usize, isize);
+
+impl From<u128> for I256 {
+  fn from(other: u128) -> Self {
+    Self {
+      bits: U256::from_u128(other),
+    }
+  }
+}
+
+// =============================================================================
+// Operators
+// =============================================================================
+
+macro_rules! impl_Ops {
+  ($ident:ident) => {
+    impl_Ops!(@op, $ident, Add, add, AddAssign, add_assign, checked_add, "attempt to add with overflow");
+    impl_Ops!(@op, $ident, Sub, sub, SubAssign, sub_assign, checked_sub, "attempt to subtract with overflow");
+    impl_Ops!(@op, $ident, Mul, mul, MulAssign, mul_assign, checked_mul, "attempt to multiply with overflow");
+    impl_Ops!(@op, $ident, Div, div, DivAssign, div_assign, checked_div, "attempt to divide by zero or with overflow");
+    impl_Ops!(@op, $ident, Rem, rem, RemAssign, rem_assign, checked_rem, "attempt to calculate the remainder with a divisor of zero or with overflow");
+  };
+  (@op, $ident:ident, $trait:ident, $fn:ident, $assign:ident, $assign_fn:ident, $checked:ident, $message:expr) => {
+    impl $trait for $ident {
+      type Output = Self;
+
+      fn $fn(self, rhs: Self) -> Self::Output {
+        match self.$checked(rhs) {
+          Some(output) => output,
+          None => fail($message),
+        }
+      }
+    }
+
+    impl $assign for $ident {
+      fn $assign_fn(&mut self, rhs: Self) {
+        *self = $trait::$fn(*self, rhs);
+      }
+    }
+  };
+}
+
+impl_Ops!(U256);
+impl_Ops!(I256);
+
+fn checked<T>((value, overflow): (T, bool)) -> Option<T> {
+  if overflow {
+    None
+  } else {
+    Some(value)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_u256_parse_display() {
+    let max: &str = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+
+    assert_eq!(U256::MAX.to_string(), max);
+    assert_eq!(max.parse::<U256>(), Ok(U256::MAX));
+    assert_eq!("".parse::<U256>(), Err(IntError::Empty));
+    assert_eq!("-1".parse::<U256>(), Err(IntError::InvalidDigit));
+    assert_eq!(
+      "115792089237316195423570985008687907853269984665640564039457584007913129639936".parse::<U256>(),
+      Err(IntError::Overflow)
+    );
+  }
+
+  #[test]
+  fn test_u256_arithmetic() {
+    assert_eq!(U256::MAX.checked_add(U256::ONE), None);
+    assert_eq!(U256::MAX.wrapping_add(U256::ONE), U256::ZERO);
+    assert_eq!(U256::MAX.saturating_add(U256::ONE), U256::MAX);
+    assert_eq!(U256::ZERO.checked_sub(U256::ONE), None);
+    assert_eq!(U256::ZERO.wrapping_sub(U256::ONE), U256::MAX);
+    assert_eq!(U256::ZERO.saturating_sub(U256::ONE), U256::ZERO);
+    assert_eq!(
+      U256::from(10_u8).checked_pow(77).map(|value| value.to_string().len()),
+      Some(78)
+    );
+    assert_eq!(U256::from(10_u8).checked_pow(78), None);
+
+    let value: U256 = "98765432109876543210987654321098765432109876543210".parse().unwrap();
+    let divisor: U256 = "1234567890123456789012345678901".parse().unwrap();
+    let (quotient, remainder): (U256, U256) = value.checked_div_rem(divisor).unwrap();
+
+    assert_eq!(quotient * divisor + remainder, value);
+    assert!(remainder < divisor);
+    assert_eq!(U256::MAX % (U256::MAX - U256::ONE), U256::ONE);
+    assert_eq!(value.checked_div(U256::ZERO), None);
+  }
+
+  #[test]
+  fn test_i256_arithmetic() {
+    assert_eq!(I256::MAX.checked_add(I256::ONE), None);
+    assert_eq!(I256::MAX.wrapping_add(I256::ONE), I256::MIN);
+    assert_eq!(I256::MIN.saturating_sub(I256::ONE), I256::MIN);
+    assert_eq!(I256::MAX.saturating_mul(I256::from(-2)), I256::MIN);
+    assert_eq!(I256::MIN.checked_neg(), None);
+    assert_eq!(I256::MIN.checked_div(I256::from(-1)), None);
+    assert_eq!(I256::from(-7) / I256::from(2), I256::from(-3));
+    assert_eq!(I256::from(-7) % I256::from(2), I256::from(-1));
+    assert_eq!(I256::from(-2).checked_pow(255), Some(I256::MIN));
+    assert_eq!(I256::from(2).checked_pow(255), None);
+    assert!(I256::MIN < I256::from(-1));
+    assert!(I256::from(-1) < I256::ONE);
+  }
+
+  #[test]
+  fn test_conversions() {
+    assert_eq!(u64::try_from(U256::from(5_u64)), Ok(5));
+    assert_eq!(u8::try_from(U256::from(256_u16)), Err(IntError::Overflow));
+    assert_eq!(U256::try_from(-1_i64), Err(IntError::Overflow));
+    assert_eq!(i128::try_from(I256::from(i128::MIN)), Ok(i128::MIN));
+    assert_eq!(u64::try_from(I256::from(-5)), Err(IntError::Overflow));
+    assert_eq!(U256::try_from(I256::from(-1)), Err(IntError::Overflow));
+    assert_eq!(I256::try_from(U256::MAX), Err(IntError::Overflow));
+    assert_eq!(I256::from_le_bytes(I256::from(-3).to_le_bytes()), I256::from(-3));
+  }
+}
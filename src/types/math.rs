@@ -0,0 +1,48 @@
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+
+/// An error returned by checked math on value proxies.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum MathError {
+  /// The result is greater than the largest value of the type.
+  Overflow,
+  /// The result is less than the smallest value of the type.
+  Underflow,
+  /// The source of a transfer holds less than the transferred amount.
+  Insufficient,
+  /// The transferred amount is negative.
+  Negative,
+}
+
+impl Display for MathError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    match self {
+      Self::Overflow => f.write_str("arithmetic overflow"),
+      Self::Underflow => f.write_str("arithmetic underflow"),
+      Self::Insufficient => f.write_str("insufficient balance"),
+      Self::Negative => f.write_str("negative amount"),
+    }
+  }
+}
+
+/// An error returned when decoding a LEB128 varint fails.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum VarintError {
+  /// The input ended before the last byte of the varint.
+  Truncated,
+  /// The varint does not fit in the target type.
+  Overflow,
+  /// The input contains bytes after the end of the varint.
+  Trailing,
+}
+
+impl Display for VarintError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    match self {
+      Self::Truncated => f.write_str("truncated varint"),
+      Self::Overflow => f.write_str("varint out of range for the target type"),
+      Self::Trailing => f.write_str("trailing bytes after varint"),
+    }
+  }
+}
@@ -1,9 +1,27 @@
 //! Commonly used types.
 
 mod alias;
+mod decimal;
+mod decoder;
+mod encoder;
+mod event;
+mod int256;
+mod math;
+mod params;
+mod proxy;
+mod schema;
 mod tag;
 mod value;
 
 pub use self::alias::*;
+pub use self::decimal::*;
+pub use self::decoder::*;
+pub use self::encoder::*;
+pub use self::event::*;
+pub use self::int256::*;
+pub use self::math::*;
+pub use self::params::*;
+pub use self::proxy::*;
+pub use self::schema::*;
 pub use self::tag::*;
 pub use self::value::*;
@@ -0,0 +1,80 @@
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+
+/// An error encountered while reading request parameters.
+///
+/// Every missing or invalid parameter is collected so the caller can be told
+/// about all of them at once.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct ParamsError {
+  missing: Vec<&'static str>,
+  invalid: Vec<(&'static str, String)>,
+  message: Option<String>,
+}
+
+impl ParamsError {
+  /// Creates a new, empty `ParamsError`.
+  pub const fn new() -> Self {
+    Self {
+      missing: Vec::new(),
+      invalid: Vec::new(),
+      message: None,
+    }
+  }
+
+  /// Creates a new `ParamsError` for parameters that are invalid as a whole.
+  pub fn custom(message: String) -> Self {
+    Self {
+      message: Some(message),
+      ..Self::new()
+    }
+  }
+
+  /// Records a missing required parameter.
+  pub fn add_missing(&mut self, key: &'static str) {
+    self.missing.push(key);
+  }
+
+  /// Records a parameter that failed validation.
+  pub fn add_invalid(&mut self, key: &'static str, message: String) {
+    self.invalid.push((key, message));
+  }
+
+  /// Returns the keys of all missing required parameters.
+  pub fn missing(&self) -> &[&'static str] {
+    &self.missing
+  }
+
+  /// Returns the keys and messages of all invalid parameters.
+  pub fn invalid(&self) -> &[(&'static str, String)] {
+    &self.invalid
+  }
+
+  /// Returns `true` if no errors were recorded.
+  pub fn is_empty(&self) -> bool {
+    self.missing.is_empty() && self.invalid.is_empty() && self.message.is_none()
+  }
+}
+
+impl Display for ParamsError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    let mut separator: &str = "";
+
+    if !self.missing.is_empty() {
+      write!(f, "missing required params: {}", self.missing.join(", "))?;
+      separator = "; ";
+    }
+
+    for (key, message) in self.invalid.iter() {
+      write!(f, "{}invalid param `{}`: {}", separator, key, message)?;
+      separator = "; ";
+    }
+
+    if let Some(ref message) = self.message {
+      write!(f, "{}invalid params: {}", separator, message)?;
+    }
+
+    Ok(())
+  }
+}
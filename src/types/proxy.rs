@@ -0,0 +1,137 @@
+use wasmlib::Key32;
+use wasmlib::ScImmutableBytes;
+use wasmlib::ScImmutableBytesArray;
+use wasmlib::ScMutableBytes;
+use wasmlib::ScMutableBytesArray;
+
+use crate::panic::fail;
+use crate::traits::math::Integer;
+use crate::types::I256;
+use crate::types::U256;
+
+// Proxies for integers that WasmLib has no native type for; values are
+// stored as little-endian bytes.
+macro_rules! impl_IntProxy {
+  ($($ident:ident),+ $(,)*) => {
+    paste::paste! {
+      $(
+        #[doc = "An immutable proxy for a [`" $ident "`] stored as bytes."]
+        pub struct [<ScImmutable $ident>] {
+          bytes: ScImmutableBytes,
+        }
+
+        impl [<ScImmutable $ident>] {
+          /// Creates a new proxy for `key_id` of the object `obj_id`.
+          pub fn new(obj_id: i32, key_id: Key32) -> Self {
+            Self::from_bytes(ScImmutableBytes::new(obj_id, key_id))
+          }
+
+          pub(crate) const fn from_bytes(bytes: ScImmutableBytes) -> Self {
+            Self { bytes }
+          }
+
+          /// Returns `true` if the value exists.
+          pub fn exists(&self) -> bool {
+            self.bytes.exists()
+          }
+
+          /// Returns the stored value, or `0` if the value does not exist.
+          pub fn value(&self) -> $ident {
+            decode(&self.bytes.value())
+          }
+        }
+
+        #[doc = "A mutable proxy for a [`" $ident "`] stored as bytes."]
+        pub struct [<ScMutable $ident>] {
+          bytes: ScMutableBytes,
+        }
+
+        impl [<ScMutable $ident>] {
+          /// Creates a new proxy for `key_id` of the object `obj_id`.
+          pub fn new(obj_id: i32, key_id: Key32) -> Self {
+            Self::from_bytes(ScMutableBytes::new(obj_id, key_id))
+          }
+
+          pub(crate) const fn from_bytes(bytes: ScMutableBytes) -> Self {
+            Self { bytes }
+          }
+
+          /// Returns `true` if the value exists.
+          pub fn exists(&self) -> bool {
+            self.bytes.exists()
+          }
+
+          /// Returns the stored value, or `0` if the value does not exist.
+          pub fn value(&self) -> $ident {
+            decode(&self.bytes.value())
+          }
+
+          /// Sets the stored value.
+          pub fn set_value(&self, value: $ident) {
+            self.bytes.set_value(&value.to_le_bytes());
+          }
+        }
+
+        #[doc = "An immutable array proxy for [`" $ident "`] values."]
+        pub struct [<ScImmutable $ident Array>] {
+          array: ScImmutableBytesArray,
+        }
+
+        impl [<ScImmutable $ident Array>] {
+          pub(crate) const fn from_bytes(array: ScImmutableBytesArray) -> Self {
+            Self { array }
+          }
+
+          /// Returns the proxy at `index`.
+          pub fn [<get_ $ident:lower>](&self, index: i32) -> [<ScImmutable $ident>] {
+            [<ScImmutable $ident>]::from_bytes(self.array.get_bytes(index))
+          }
+
+          /// Returns the number of values in the array.
+          pub fn length(&self) -> i32 {
+            self.array.length()
+          }
+        }
+
+        #[doc = "A mutable array proxy for [`" $ident "`] values."]
+        pub struct [<ScMutable $ident Array>] {
+          array: ScMutableBytesArray,
+        }
+
+        impl [<ScMutable $ident Array>] {
+          pub(crate) const fn from_bytes(array: ScMutableBytesArray) -> Self {
+            Self { array }
+          }
+
+          /// Returns the proxy at `index`.
+          pub fn [<get_ $ident:lower>](&self, index: i32) -> [<ScMutable $ident>] {
+            [<ScMutable $ident>]::from_bytes(self.array.get_bytes(index))
+          }
+
+          /// Returns the number of values in the array.
+          pub fn length(&self) -> i32 {
+            self.array.length()
+          }
+
+          /// Removes all values from the array.
+          pub fn clear(&self) {
+            self.array.clear();
+          }
+        }
+      )+
+    }
+  };
+}
+
+impl_IntProxy!(U256, I256);
+
+fn decode<T: Integer + Default>(bytes: &[u8]) -> T {
+  if bytes.is_empty() {
+    return T::default();
+  }
+
+  match T::decode(bytes) {
+    Some(value) => value,
+    None => fail(&format!("invalid integer length: {}", bytes.len())),
+  }
+}
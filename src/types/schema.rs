@@ -0,0 +1,324 @@
+use core::fmt::Write;
+use wasmlib::ScHname;
+
+use crate::types::ScTag;
+
+/// A machine-readable description of a contract interface.
+///
+/// Schemas are usually built by [`Export::schema`][crate::traits::utility::Export::schema]
+/// and rendered with [`to_json`][Self::to_json].
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Schema {
+  name: &'static str,
+  description: &'static str,
+  funcs: Vec<FuncSchema>,
+  views: Vec<FuncSchema>,
+  events: Vec<EventSchema>,
+}
+
+impl Schema {
+  /// Creates a new, empty `Schema` for the contract `name`.
+  pub const fn new(name: &'static str) -> Self {
+    Self {
+      name,
+      description: "",
+      funcs: Vec::new(),
+      views: Vec::new(),
+      events: Vec::new(),
+    }
+  }
+
+  /// Returns the name of the contract.
+  pub const fn name(&self) -> &'static str {
+    self.name
+  }
+
+  /// Returns the description of the contract.
+  pub const fn description(&self) -> &'static str {
+    self.description
+  }
+
+  /// Returns the exported funcs of the contract.
+  pub fn funcs(&self) -> &[FuncSchema] {
+    &self.funcs
+  }
+
+  /// Returns the exported views of the contract.
+  pub fn views(&self) -> &[FuncSchema] {
+    &self.views
+  }
+
+  /// Returns the events emitted by the contract.
+  pub fn events(&self) -> &[EventSchema] {
+    &self.events
+  }
+
+  /// Sets the description of the contract.
+  pub fn set_description(&mut self, description: &'static str) {
+    self.description = description;
+  }
+
+  /// Adds an exported func to the schema.
+  pub fn add_func(&mut self, func: FuncSchema) {
+    self.funcs.push(func);
+  }
+
+  /// Adds an exported view to the schema.
+  pub fn add_view(&mut self, view: FuncSchema) {
+    self.views.push(view);
+  }
+
+  /// Adds an event to the schema.
+  pub fn add_event(&mut self, event: EventSchema) {
+    self.events.push(event);
+  }
+
+  /// Renders the schema as a JSON document.
+  ///
+  /// Function hnames are computed by the host, so this must be called from
+  /// within a contract or with the `testing` host.
+  pub fn to_json(&self) -> String {
+    let mut output: String = String::new();
+
+    output.push_str("{\"name\":");
+    write_str(&mut output, self.name);
+    output.push_str(",\"description\":");
+    write_str(&mut output, self.description);
+    output.push_str(",\"funcs\":");
+    write_list(&mut output, &self.funcs, FuncSchema::write_json);
+    output.push_str(",\"views\":");
+    write_list(&mut output, &self.views, FuncSchema::write_json);
+    output.push_str(",\"events\":");
+    write_list(&mut output, &self.events, EventSchema::write_json);
+    output.push('}');
+
+    output
+  }
+}
+
+// =============================================================================
+// =============================================================================
+
+/// A description of an exported func or view.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct FuncSchema {
+  name: &'static str,
+  params: Vec<FieldSchema>,
+  results: Vec<FieldSchema>,
+}
+
+impl FuncSchema {
+  /// Creates a new `FuncSchema` for the function `name`.
+  pub const fn new(name: &'static str) -> Self {
+    Self {
+      name,
+      params: Vec::new(),
+      results: Vec::new(),
+    }
+  }
+
+  /// Adds a parameter stored at `key` as a value of type `tag`.
+  pub fn param(mut self, key: &'static str, tag: ScTag) -> Self {
+    self.params.push(FieldSchema::new(key, tag));
+    self
+  }
+
+  /// Adds a parameter stored at `key` as a native array of type `tag`.
+  pub fn param_array(mut self, key: &'static str, tag: ScTag) -> Self {
+    self.params.push(FieldSchema::array(key, tag));
+    self
+  }
+
+  /// Adds a parameter described by `field`.
+  pub fn param_field(mut self, field: FieldSchema) -> Self {
+    self.params.push(field);
+    self
+  }
+
+  /// Adds a result stored at `key` as a value of type `tag`.
+  pub fn result(mut self, key: &'static str, tag: ScTag) -> Self {
+    self.results.push(FieldSchema::new(key, tag));
+    self
+  }
+
+  /// Adds a result stored at `key` as a native array of type `tag`.
+  pub fn result_array(mut self, key: &'static str, tag: ScTag) -> Self {
+    self.results.push(FieldSchema::array(key, tag));
+    self
+  }
+
+  /// Adds a result described by `field`.
+  pub fn result_field(mut self, field: FieldSchema) -> Self {
+    self.results.push(field);
+    self
+  }
+
+  /// Returns the name of the function.
+  pub const fn name(&self) -> &'static str {
+    self.name
+  }
+
+  /// Returns the hash name of the function.
+  pub fn hname(&self) -> ScHname {
+    ScHname::new(self.name)
+  }
+
+  /// Returns the parameters of the function.
+  pub fn params(&self) -> &[FieldSchema] {
+    &self.params
+  }
+
+  /// Returns the results of the function.
+  pub fn results(&self) -> &[FieldSchema] {
+    &self.results
+  }
+
+  fn write_json(&self, output: &mut String) {
+    let hname: Vec<u8> = self.hname().to_bytes();
+    let hname: u32 = u32::from_le_bytes([hname[0], hname[1], hname[2], hname[3]]);
+
+    output.push_str("{\"name\":");
+    write_str(output, self.name);
+    let _ = write!(output, ",\"hname\":\"{:08x}\"", hname);
+    output.push_str(",\"params\":");
+    write_list(output, &self.params, FieldSchema::write_json);
+    output.push_str(",\"results\":");
+    write_list(output, &self.results, FieldSchema::write_json);
+    output.push('}');
+  }
+}
+
+// =============================================================================
+// =============================================================================
+
+/// A description of an event emitted by a contract.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct EventSchema {
+  name: &'static str,
+  fields: Vec<FieldSchema>,
+}
+
+impl EventSchema {
+  /// Creates a new `EventSchema` for the event `name`.
+  pub const fn new(name: &'static str) -> Self {
+    Self {
+      name,
+      fields: Vec::new(),
+    }
+  }
+
+  /// Adds a field of type `tag` to the event.
+  pub fn field(mut self, key: &'static str, tag: ScTag) -> Self {
+    self.fields.push(FieldSchema::new(key, tag));
+    self
+  }
+
+  /// Returns the name of the event.
+  pub const fn name(&self) -> &'static str {
+    self.name
+  }
+
+  /// Returns the fields of the event, in encoding order.
+  pub fn fields(&self) -> &[FieldSchema] {
+    &self.fields
+  }
+
+  fn write_json(&self, output: &mut String) {
+    output.push_str("{\"name\":");
+    write_str(output, self.name);
+    output.push_str(",\"fields\":");
+    write_list(output, &self.fields, FieldSchema::write_json);
+    output.push('}');
+  }
+}
+
+// =============================================================================
+// =============================================================================
+
+/// A description of a single typed value in a [`Schema`].
+///
+/// Fields stored as a native array are rendered with `"array":true`; the
+/// type is the type of the elements.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct FieldSchema {
+  key: &'static str,
+  tag: ScTag,
+  array: bool,
+}
+
+impl FieldSchema {
+  /// Creates a new `FieldSchema` for a single value.
+  pub const fn new(key: &'static str, tag: ScTag) -> Self {
+    Self { key, tag, array: false }
+  }
+
+  /// Creates a new `FieldSchema` for a native array of values.
+  pub const fn array(key: &'static str, tag: ScTag) -> Self {
+    Self { key, tag, array: true }
+  }
+
+  /// Returns the key of the value.
+  pub const fn key(&self) -> &'static str {
+    self.key
+  }
+
+  /// Returns the type of the value, or of its elements if it is an array.
+  pub const fn tag(&self) -> ScTag {
+    self.tag
+  }
+
+  /// Returns `true` if the value is stored as a native array.
+  pub const fn is_array(&self) -> bool {
+    self.array
+  }
+
+  fn write_json(&self, output: &mut String) {
+    output.push_str("{\"key\":");
+    write_str(output, self.key);
+    output.push_str(",\"type\":");
+    write_str(output, self.tag.name());
+
+    if self.array {
+      output.push_str(",\"array\":true");
+    }
+
+    output.push('}');
+  }
+}
+
+// =============================================================================
+// =============================================================================
+
+fn write_list<T>(output: &mut String, items: &[T], write: fn(&T, &mut String)) {
+  output.push('[');
+
+  for (index, item) in items.iter().enumerate() {
+    if index > 0 {
+      output.push(',');
+    }
+
+    write(item, output);
+  }
+
+  output.push(']');
+}
+
+fn write_str(output: &mut String, value: &str) {
+  output.push('"');
+
+  for char in value.chars() {
+    match char {
+      '"' => output.push_str("\\\""),
+      '\\' => output.push_str("\\\\"),
+      '\n' => output.push_str("\\n"),
+      '\r' => output.push_str("\\r"),
+      '\t' => output.push_str("\\t"),
+      char if char.is_control() => {
+        let _ = write!(output, "\\u{:04x}", char as u32);
+      }
+      char => output.push(char),
+    }
+  }
+
+  output.push('"');
+}
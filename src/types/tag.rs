@@ -96,6 +96,22 @@ impl ScTag {
   pub const fn type_id(self) -> i32 {
     self as i32
   }
+
+  /// Returns the name of the type, as used in contract schemas.
+  pub const fn name(self) -> &'static str {
+    match self {
+      Self::Address => "Address",
+      Self::AgentId => "AgentId",
+      Self::Bytes => "Bytes",
+      Self::ChainId => "ChainId",
+      Self::Color => "Color",
+      Self::Hash => "Hash",
+      Self::Hname => "Hname",
+      Self::Int64 => "Int64",
+      Self::RequestId => "RequestId",
+      Self::String => "String",
+    }
+  }
 }
 
 #[cfg(test)]
@@ -148,4 +164,18 @@ mod tests {
     assert_eq!(ScTag::RequestId.type_id(), host::TYPE_REQUEST_ID);
     assert_eq!(ScTag::String.type_id(), host::TYPE_STRING);
   }
+
+  #[test]
+  fn test_name() {
+    assert_eq!(ScTag::Address.name(), "Address");
+    assert_eq!(ScTag::AgentId.name(), "AgentId");
+    assert_eq!(ScTag::Bytes.name(), "Bytes");
+    assert_eq!(ScTag::ChainId.name(), "ChainId");
+    assert_eq!(ScTag::Color.name(), "Color");
+    assert_eq!(ScTag::Hash.name(), "Hash");
+    assert_eq!(ScTag::Hname.name(), "Hname");
+    assert_eq!(ScTag::Int64.name(), "Int64");
+    assert_eq!(ScTag::RequestId.name(), "RequestId");
+    assert_eq!(ScTag::String.name(), "String");
+  }
 }
@@ -3,6 +3,8 @@ use core::fmt::Formatter;
 use core::fmt::Result;
 use wasmlib::*;
 
+use crate::types::DecodeError;
+use crate::types::Decoder;
 use crate::types::ScBytes;
 use crate::types::ScInt64;
 use crate::types::ScString;
@@ -36,7 +38,7 @@ pub enum ScValue {
 impl ScValue {
   /// Decodes an `ScValue` from the given type tag and slice of bytes.
   pub fn from_bytes(tag: ScTag, bytes: &[u8]) -> Self {
-    let mut decoder: BytesDecoder<'_> = BytesDecoder::new(bytes);
+    let mut decoder: Decoder<'_> = Decoder::new(bytes);
 
     match tag {
       ScTag::Address => Self::Address(decoder.address()),
@@ -52,6 +54,28 @@ impl ScValue {
     }
   }
 
+  /// Decodes an `ScValue` from the given type tag and slice of bytes.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `bytes` is not a valid encoding of `tag`.
+  pub fn try_from_bytes(tag: ScTag, bytes: &[u8]) -> core::result::Result<Self, DecodeError> {
+    let mut decoder: Decoder<'_> = Decoder::new(bytes);
+
+    match tag {
+      ScTag::Address => decoder.try_address().map(Self::Address),
+      ScTag::AgentId => decoder.try_agent_id().map(Self::AgentId),
+      ScTag::Bytes => decoder.try_bytes().map(|bytes| Self::Bytes(bytes.to_vec())),
+      ScTag::ChainId => decoder.try_chain_id().map(Self::ChainId),
+      ScTag::Color => decoder.try_color().map(Self::Color),
+      ScTag::Hash => decoder.try_hash().map(Self::Hash),
+      ScTag::Hname => decoder.try_hname().map(Self::Hname),
+      ScTag::Int64 => decoder.try_int64().map(Self::Int64),
+      ScTag::RequestId => decoder.try_request_id().map(Self::RequestId),
+      ScTag::String => decoder.try_string().map(Self::String),
+    }
+  }
+
   /// Returns the value tag and encoded vector of bytes.
   pub fn to_bytes(&self) -> (ScTag, Vec<u8>) {
     (self.tag(), self.to_untagged_bytes())
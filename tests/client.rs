@@ -0,0 +1,495 @@
+#![cfg(feature = "testing")]
+#![allow(non_snake_case)]
+
+use scarab::contracts::erc1155::ERC1155Client;
+use scarab::contracts::erc1155::IERC1155__Bridge;
+use scarab::contracts::erc1155::IERC1155;
+use scarab::contracts::erc20::ERC20Client;
+use scarab::contracts::erc20::IERC20__Bridge;
+use scarab::contracts::erc20::IERC20;
+use scarab::contracts::erc721::ERC721Client;
+use scarab::contracts::erc721::IERC721__Bridge;
+use scarab::contracts::erc721::IERC721;
+use scarab::export::ResultValue;
+use scarab::prelude::*;
+use scarab::testing::MockChain;
+use scarab::testing::MockHost;
+use scarab::testing::Program;
+use scarab::types::FieldSchema;
+use scarab::types::FuncSchema;
+use scarab::types::ScTag;
+use scarab::types::Schema;
+use scarab::types::U256;
+use scarab::wasmlib::*;
+
+type Funcs = &'static [(&'static str, fn(&ScFuncContext))];
+type Views = &'static [(&'static str, fn(&ScViewContext))];
+
+// =============================================================================
+// ERC-20
+// =============================================================================
+
+struct Token;
+
+impl IERC20 for Token {
+  fn name(_: &ScViewContext) -> String {
+    "Token".to_string()
+  }
+
+  fn symbol(_: &ScViewContext) -> String {
+    "TKN".to_string()
+  }
+
+  fn decimals(_: &ScViewContext) -> u8 {
+    6
+  }
+
+  fn totalSupply(_: &ScViewContext) -> U256 {
+    U256::from(1000_u32)
+  }
+
+  fn balanceOf(ctx: &ScViewContext, owner: &ScAgentId) -> U256 {
+    ctx.state().get_value(owner)
+  }
+
+  fn allowance(ctx: &ScViewContext, owner: &ScAgentId, spender: &ScAgentId) -> U256 {
+    ctx.state().get_map(owner).get_value(spender)
+  }
+
+  fn approve(ctx: &ScFuncContext, spender: &ScAgentId, value: &U256) -> bool {
+    ctx.state().get_map(&ctx.caller()).set(spender, value);
+    true
+  }
+
+  fn transfer(ctx: &ScFuncContext, to: &ScAgentId, value: &U256) -> bool {
+    ctx.state().set(to, value);
+    true
+  }
+
+  fn transferFrom(_: &ScFuncContext, _: &ScAgentId, _: &ScAgentId, _: &U256) -> bool {
+    false
+  }
+}
+
+const ERC20_FUNCS: Funcs = &[
+  ("approve", <Token as IERC20__Bridge>::func_approve),
+  ("transfer", <Token as IERC20__Bridge>::func_transfer),
+  ("transferFrom", <Token as IERC20__Bridge>::func_transferFrom),
+];
+
+const ERC20_VIEWS: Views = &[
+  ("name", <Token as IERC20__Bridge>::view_name),
+  ("symbol", <Token as IERC20__Bridge>::view_symbol),
+  ("decimals", <Token as IERC20__Bridge>::view_decimals),
+  ("totalSupply", <Token as IERC20__Bridge>::view_totalSupply),
+  ("balanceOf", <Token as IERC20__Bridge>::view_balanceOf),
+  ("allowance", <Token as IERC20__Bridge>::view_allowance),
+];
+
+fn erc20_send(ctx: &ScFuncContext) {
+  let client: ERC20Client = ERC20Client::new(ScHname::new("token"));
+  let to: ScAgentId = ctx.params().get_agent_id("to").value();
+
+  let approved: bool = client.approve(ctx, &to, &U256::from(7_u32), ScTransfers::new_transfers());
+  let transferred: bool = client.transfer(ctx, &to, &U256::from(5_u32), ScTransfers::new_transfers());
+  let failed: bool = client.transfer_from(ctx, &to, &to, &U256::ONE, ScTransfers::new_transfers());
+
+  ctx.require(approved && transferred && !failed, "unexpected success");
+}
+
+fn erc20_read(ctx: &ScViewContext) {
+  let client: ERC20Client = ERC20Client::new(ScHname::new("token"));
+  let to: ScAgentId = ctx.params().get_agent_id("to").value();
+
+  ctx.result("name", client.name(ctx));
+  ctx.result("symbol", client.symbol(ctx));
+  ctx.result("decimals", i64::from(client.decimals(ctx)));
+  ctx.result("totalSupply", client.total_supply(ctx));
+  ctx.result("balance", client.balance_of(ctx, &to));
+  ctx.result("remaining", client.allowance(ctx, &ctx.contract_id(), &to));
+}
+
+#[test]
+fn test_erc20_client() {
+  let chain: MockChain = deploy(ERC20_FUNCS, ERC20_VIEWS, erc20_send, erc20_read);
+  let alice: ScAddress = chain.address("alice");
+  let bob: ScAgentId = chain.address("bob").as_agent_id();
+
+  chain.params().get_agent_id("to").set_value(&bob);
+  chain.post(&alice, "caller", "send", &[]).unwrap();
+  chain.params().get_agent_id("to").set_value(&bob);
+
+  let results: ScImmutableMap = chain.call_view("caller", "read").unwrap();
+
+  assert_eq!(results.get_string("name").value(), "Token");
+  assert_eq!(results.get_string("symbol").value(), "TKN");
+  assert_eq!(results.get_int64("decimals").value(), 6);
+  assert_eq!(results.get_value::<_, U256>("totalSupply"), U256::from(1000_u32));
+  assert_eq!(results.get_value::<_, U256>("balance"), U256::from(5_u32));
+  assert_eq!(results.get_value::<_, U256>("remaining"), U256::from(7_u32));
+}
+
+#[test]
+fn test_erc20_schema() {
+  let mut schema: Schema = Schema::new("token");
+
+  <Token as IERC20__Bridge>::describe(&mut schema);
+
+  check_schema(&schema, ERC20_FUNCS, ERC20_VIEWS);
+}
+
+// =============================================================================
+// ERC-721
+// =============================================================================
+
+struct Nft;
+
+impl IERC721 for Nft {
+  fn balanceOf(ctx: &ScViewContext, owner: &ScAgentId) -> U256 {
+    ctx.state().get_map("balances").get_value(owner)
+  }
+
+  fn ownerOf(ctx: &ScViewContext, tokenId: &U256) -> ScAgentId {
+    ctx.state().get_map("owners").get_value(tokenId)
+  }
+
+  fn getApproved(ctx: &ScViewContext, tokenId: &U256) -> ScAgentId {
+    ctx.state().get_map("approvals").get_value(tokenId)
+  }
+
+  fn isApprovedForAll(ctx: &ScViewContext, owner: &ScAgentId, operator: &ScAgentId) -> bool {
+    ctx.state().get_map(owner).get_int64(operator).value() != 0
+  }
+
+  fn approve(ctx: &ScFuncContext, approved: &ScAgentId, tokenId: &U256) {
+    ctx.state().get_map("approvals").set(tokenId, approved);
+  }
+
+  fn setApprovalForAll(ctx: &ScFuncContext, operator: &ScAgentId, approved: bool) {
+    ctx
+      .state()
+      .get_map(&ctx.caller())
+      .get_int64(operator)
+      .set_value(approved as i64);
+  }
+
+  fn transferFrom(ctx: &ScFuncContext, _: &ScAgentId, to: &ScAgentId, tokenId: &U256) {
+    ctx.state().get_map("owners").set(tokenId, to);
+    ctx.state().get_map("balances").set(to, &U256::ONE);
+  }
+}
+
+const ERC721_FUNCS: Funcs = &[
+  ("approve", <Nft as IERC721__Bridge>::func_approve),
+  ("setApprovalForAll", <Nft as IERC721__Bridge>::func_setApprovalForAll),
+  ("transferFrom", <Nft as IERC721__Bridge>::func_transferFrom),
+];
+
+const ERC721_VIEWS: Views = &[
+  ("balanceOf", <Nft as IERC721__Bridge>::view_balanceOf),
+  ("ownerOf", <Nft as IERC721__Bridge>::view_ownerOf),
+  ("getApproved", <Nft as IERC721__Bridge>::view_getApproved),
+  ("isApprovedForAll", <Nft as IERC721__Bridge>::view_isApprovedForAll),
+];
+
+fn erc721_send(ctx: &ScFuncContext) {
+  let client: ERC721Client = ERC721Client::new(ScHname::new("token"));
+  let to: ScAgentId = ctx.params().get_agent_id("to").value();
+  let token_id: U256 = U256::from(42_u32);
+
+  client.approve(ctx, &to, &token_id, ScTransfers::new_transfers());
+  client.set_approval_for_all(ctx, &to, true, ScTransfers::new_transfers());
+  client.transfer_from(ctx, &ctx.contract_id(), &to, &token_id, ScTransfers::new_transfers());
+}
+
+fn erc721_read(ctx: &ScViewContext) {
+  let client: ERC721Client = ERC721Client::new(ScHname::new("token"));
+  let to: ScAgentId = ctx.params().get_agent_id("to").value();
+  let token_id: U256 = U256::from(42_u32);
+
+  ctx.result("balance", client.balance_of(ctx, &to));
+  ctx.result("owner", client.owner_of(ctx, &token_id));
+  ctx.result("approved", client.get_approved(ctx, &token_id));
+  ctx.result("operator", client.is_approved_for_all(ctx, &ctx.contract_id(), &to));
+}
+
+#[test]
+fn test_erc721_client() {
+  let chain: MockChain = deploy(ERC721_FUNCS, ERC721_VIEWS, erc721_send, erc721_read);
+  let alice: ScAddress = chain.address("alice");
+  let bob: ScAgentId = chain.address("bob").as_agent_id();
+
+  chain.params().get_agent_id("to").set_value(&bob);
+  chain.post(&alice, "caller", "send", &[]).unwrap();
+  chain.params().get_agent_id("to").set_value(&bob);
+
+  let results: ScImmutableMap = chain.call_view("caller", "read").unwrap();
+
+  assert_eq!(results.get_value::<_, U256>("balance"), U256::ONE);
+  assert_eq!(results.get_agent_id("owner").value().to_bytes(), bob.to_bytes());
+  assert_eq!(results.get_agent_id("approved").value().to_bytes(), bob.to_bytes());
+  assert_eq!(results.get_int64("operator").value(), 1);
+}
+
+#[test]
+fn test_erc721_schema() {
+  let mut schema: Schema = Schema::new("token");
+
+  <Nft as IERC721__Bridge>::describe(&mut schema);
+
+  check_schema(&schema, ERC721_FUNCS, ERC721_VIEWS);
+}
+
+// =============================================================================
+// ERC-1155
+// =============================================================================
+
+struct Items;
+
+impl IERC1155 for Items {
+  fn balanceOf(ctx: &ScViewContext, owner: &ScAgentId, id: &U256) -> U256 {
+    ctx.state().get_map(owner).get_value(id)
+  }
+
+  fn balanceOfBatch(ctx: &ScViewContext, owners: &[ScAgentId], ids: &[U256]) -> Vec<U256> {
+    owners
+      .iter()
+      .zip(ids)
+      .map(|(owner, id)| Self::balanceOf(ctx, owner, id))
+      .collect()
+  }
+
+  fn isApprovedForAll(ctx: &ScViewContext, owner: &ScAgentId, operator: &ScAgentId) -> bool {
+    ctx
+      .state()
+      .get_map("operators")
+      .get_map(owner)
+      .get_int64(operator)
+      .value()
+      != 0
+  }
+
+  fn setApprovalForAll(ctx: &ScFuncContext, operator: &ScAgentId, approved: bool) {
+    ctx
+      .state()
+      .get_map("operators")
+      .get_map(&ctx.caller())
+      .get_int64(operator)
+      .set_value(approved as i64);
+  }
+
+  fn safeTransferFrom(ctx: &ScFuncContext, _: &ScAgentId, to: &ScAgentId, id: &U256, value: &U256) {
+    ctx.state().get_map(to).set(id, value);
+  }
+
+  fn safeBatchTransferFrom(ctx: &ScFuncContext, from: &ScAgentId, to: &ScAgentId, ids: &[U256], values: &[U256]) {
+    for (id, value) in ids.iter().zip(values) {
+      Self::safeTransferFrom(ctx, from, to, id, value);
+    }
+  }
+}
+
+const ERC1155_FUNCS: Funcs = &[
+  ("setApprovalForAll", <Items as IERC1155__Bridge>::func_setApprovalForAll),
+  ("safeTransferFrom", <Items as IERC1155__Bridge>::func_safeTransferFrom),
+  (
+    "safeBatchTransferFrom",
+    <Items as IERC1155__Bridge>::func_safeBatchTransferFrom,
+  ),
+];
+
+const ERC1155_VIEWS: Views = &[
+  ("balanceOf", <Items as IERC1155__Bridge>::view_balanceOf),
+  ("balanceOfBatch", <Items as IERC1155__Bridge>::view_balanceOfBatch),
+  ("isApprovedForAll", <Items as IERC1155__Bridge>::view_isApprovedForAll),
+];
+
+fn erc1155_send(ctx: &ScFuncContext) {
+  let client: ERC1155Client = ERC1155Client::new(ScHname::new("token"));
+  let to: ScAgentId = ctx.params().get_agent_id("to").value();
+  let from: ScAgentId = ctx.contract_id();
+
+  client.set_approval_for_all(ctx, &to, true, ScTransfers::new_transfers());
+  client.safe_transfer_from(
+    ctx,
+    &from,
+    &to,
+    &U256::ONE,
+    &U256::from(10_u32),
+    ScTransfers::new_transfers(),
+  );
+  client.safe_batch_transfer_from(
+    ctx,
+    &from,
+    &to,
+    &[U256::from(2_u32), U256::from(3_u32)],
+    &[U256::from(20_u32), U256::from(30_u32)],
+    ScTransfers::new_transfers(),
+  );
+}
+
+fn erc1155_read(ctx: &ScViewContext) {
+  let client: ERC1155Client = ERC1155Client::new(ScHname::new("token"));
+  let to: ScAgentId = ctx.params().get_agent_id("to").value();
+  let owners: [ScAgentId; 3] = [to.clone(), to.clone(), ctx.contract_id()];
+  let ids: [U256; 3] = [U256::from(3_u32), U256::ONE, U256::ONE];
+
+  ctx.result("balance", client.balance_of(ctx, &to, &U256::from(2_u32)));
+  ctx.result("balances", client.balance_of_batch(ctx, &owners, &ids));
+  ctx.result("approved", client.is_approved_for_all(ctx, &ctx.contract_id(), &to));
+}
+
+#[test]
+fn test_erc1155_client() {
+  let chain: MockChain = deploy(ERC1155_FUNCS, ERC1155_VIEWS, erc1155_send, erc1155_read);
+  let alice: ScAddress = chain.address("alice");
+  let bob: ScAgentId = chain.address("bob").as_agent_id();
+
+  chain.params().get_agent_id("to").set_value(&bob);
+  chain.post(&alice, "caller", "send", &[]).unwrap();
+  chain.params().get_agent_id("to").set_value(&bob);
+
+  let results: ScImmutableMap = chain.call_view("caller", "read").unwrap();
+  let balances: Vec<U256> = Vec::read_result(&results, "balances").unwrap();
+
+  assert_eq!(results.get_value::<_, U256>("balance"), U256::from(20_u32));
+  assert_eq!(balances, [U256::from(30_u32), U256::from(10_u32), U256::ZERO]);
+  assert_eq!(results.get_int64("approved").value(), 1);
+}
+
+#[test]
+fn test_erc1155_schema() {
+  let mut schema: Schema = Schema::new("token");
+
+  <Items as IERC1155__Bridge>::describe(&mut schema);
+
+  check_schema(&schema, ERC1155_FUNCS, ERC1155_VIEWS);
+
+  let batch: &FuncSchema = schema
+    .views()
+    .iter()
+    .find(|view| view.name() == "balanceOfBatch")
+    .unwrap();
+
+  assert_eq!(
+    batch.params(),
+    [
+      FieldSchema::array("owners", ScTag::AgentId),
+      FieldSchema::array("ids", ScTag::Bytes),
+    ]
+  );
+  assert_eq!(batch.results(), [FieldSchema::array("balances", ScTag::Bytes)]);
+  assert!(schema
+    .to_json()
+    .contains(r#"{"key":"balances","type":"Bytes","array":true}"#));
+}
+
+#[test]
+fn test_erc1155_missing_arrays() {
+  let host: MockHost = MockHost::new("token");
+  let missing =
+    |key: &str| -> Result<(), String> { Err(format!("missing required param: Key32({})", key.get_key_id().0)) };
+
+  host.params().set("ids", &[U256::ONE][..]);
+
+  assert_eq!(
+    host.call_view(<Items as IERC1155__Bridge>::view_balanceOfBatch),
+    missing("owners")
+  );
+
+  let params: ScMutableMap = host.params();
+
+  params.set("from", &host.agent_id("alice"));
+  params.set("to", &host.agent_id("bob"));
+  params.set("ids", &[U256::ONE][..]);
+
+  assert_eq!(
+    host.call_func(<Items as IERC1155__Bridge>::func_safeBatchTransferFrom),
+    missing("values")
+  );
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+// Deploys the bridge exports as `token` and a contract calling them through
+// a client as `caller`
+fn deploy(funcs: Funcs, views: Views, send: fn(&ScFuncContext), read: fn(&ScViewContext)) -> MockChain {
+  let chain: MockChain = MockChain::new();
+  let mut token: Program = Program::new("token");
+
+  for (name, func) in funcs {
+    token = token.func(name, *func);
+  }
+
+  for (name, view) in views {
+    token = token.view(name, *view);
+  }
+
+  let caller: Program = Program::new("caller").func("send", send).view("read", read);
+  let token: ScHash = chain.upload(&token).unwrap();
+  let caller: ScHash = chain.upload(&caller).unwrap();
+
+  chain.deploy(&token, "token").unwrap();
+  chain.deploy(&caller, "caller").unwrap();
+  chain
+}
+
+// Calls every exported function with the params listed in the schema and
+// checks that the bridge writes every listed result
+fn check_schema(schema: &Schema, funcs: Funcs, views: Views) {
+  let names = |exports: &[FuncSchema]| -> Vec<&str> { exports.iter().map(FuncSchema::name).collect() };
+
+  assert_eq!(
+    names(schema.funcs()),
+    funcs.iter().map(|func| func.0).collect::<Vec<_>>()
+  );
+  assert_eq!(
+    names(schema.views()),
+    views.iter().map(|view| view.0).collect::<Vec<_>>()
+  );
+
+  for (export, (_, func)) in schema.funcs().iter().zip(funcs) {
+    let host: MockHost = MockHost::new("token");
+
+    set_params(&host, export.params());
+    host.call_func(*func).unwrap();
+    check_results(&host, export);
+  }
+
+  for (export, (_, view)) in schema.views().iter().zip(views) {
+    let host: MockHost = MockHost::new("token");
+
+    set_params(&host, export.params());
+    host.call_view(*view).unwrap();
+    check_results(&host, export);
+  }
+}
+
+fn set_params(host: &MockHost, fields: &[FieldSchema]) {
+  let params: ScMutableMap = host.params();
+
+  for field in fields {
+    match (field.tag(), field.is_array()) {
+      (ScTag::AgentId, false) => params.set(field.key(), &host.agent_id("alice")),
+      (ScTag::AgentId, true) => vec![host.agent_id("alice")].write_result(&params, field.key()),
+      (ScTag::Bytes, false) => params.set(field.key(), &U256::ONE),
+      (ScTag::Bytes, true) => vec![U256::ONE].write_result(&params, field.key()),
+      (ScTag::Int64, false) => params.get_int64(field.key()).set_value(1),
+      (tag, array) => panic!("unexpected param type: {:?} (array: {})", tag, array),
+    }
+  }
+}
+
+fn check_results(host: &MockHost, export: &FuncSchema) {
+  for field in export.results() {
+    let exists: bool = if field.is_array() {
+      host.results().get_bytes_array(field.key()).length() > 0
+    } else {
+      host.results().get_bytes(field.key()).exists()
+    };
+
+    assert!(exists, "{}: missing result `{}`", export.name(), field.key());
+  }
+}
@@ -0,0 +1,35 @@
+//! Fixtures shared by the token tests.
+
+use scarab::prelude::*;
+use scarab::testing::MockHost;
+use scarab::types::U256;
+use scarab::wasmlib::*;
+
+pub struct Accounts {
+  pub alice: ScAgentId,
+  pub bob: ScAgentId,
+  pub carol: ScAgentId,
+}
+
+impl Accounts {
+  /// Returns the accounts of `host`, with alice as the caller of the next
+  /// request.
+  pub fn new(host: &MockHost) -> Self {
+    let accounts: Self = Self {
+      alice: host.agent_id("alice"),
+      bob: host.agent_id("bob"),
+      carol: host.agent_id("carol"),
+    };
+
+    host.set_caller(&accounts.alice);
+
+    accounts
+  }
+}
+
+/// Calls the `balanceOf` view `view` and returns the balance of `owner`.
+pub fn balance_of(host: &MockHost, view: fn(&ScViewContext), owner: &ScAgentId) -> U256 {
+  host.params().set("owner", owner);
+  host.call_view(view).unwrap();
+  host.results().get_value("balance")
+}
@@ -0,0 +1,234 @@
+#![cfg(feature = "testing")]
+
+use scarab::contracts::erc20::*;
+use scarab::prelude::*;
+use scarab::testing::MockChain;
+use scarab::testing::Program;
+use scarab::traits::utility::Event;
+use scarab::types::EventSchema;
+use scarab::types::FieldSchema;
+use scarab::types::FuncSchema;
+use scarab::types::ScTag;
+use scarab::types::Schema;
+use scarab::wasmlib::*;
+use scarab::Decode;
+use scarab::Encode;
+
+#[derive(Encode, Decode)]
+struct Counted {
+  value: i64,
+}
+
+impl Event for Counted {
+  const NAME: &'static str = "Counted";
+
+  fn schema() -> EventSchema {
+    EventSchema::new(Self::NAME).field("value", ScTag::Int64)
+  }
+}
+
+struct Counter;
+
+#[scarab::contract(events(Counted))]
+impl Counter {
+  #[func]
+  fn init(ctx: &ScFuncContext, #[scarab(key = "start")] value: i64) {
+    ctx.state().get_int64("counter").set_value(value);
+  }
+
+  #[func(result = "counter")]
+  fn add_value(ctx: &ScFuncContext, amount: i64, owner: &ScAgentId) -> i64 {
+    let counter: ScMutableInt64 = ctx.state().get_int64("counter");
+
+    counter.set_value(counter.value() + amount);
+    ctx.state().get_agent_id("owner").set_value(owner);
+
+    Counted { value: counter.value() }.emit(ctx);
+
+    counter.value()
+  }
+
+  #[view(name = "get", result = "value")]
+  fn get_value(ctx: &ScViewContext) -> i64 {
+    ctx.state().get_int64("counter").value()
+  }
+
+  #[view]
+  fn history(ctx: &ScViewContext) -> Vec<i64> {
+    vec![ctx.state().get_int64("counter").value()]
+  }
+}
+
+// A token exporting the ERC-20 bridges along with its own `init`
+struct Token;
+
+type Standard = StandardERC20<Token>;
+
+impl ERC20Config for Token {
+  const NAME: &'static str = "Token";
+  const SYMBOL: &'static str = "TKN";
+}
+
+#[scarab::contract(bridges(IERC20__Bridge, IERC20Mintable__Bridge), no_on_load)]
+impl Token {
+  #[func]
+  fn init(ctx: &ScFuncContext, owner: &ScAgentId, supply: &U256) {
+    Standard::init(ctx, owner, supply);
+  }
+}
+
+impl IERC20 for Token {
+  fn name(ctx: &ScViewContext) -> String {
+    <Standard as IERC20>::name(ctx)
+  }
+
+  fn symbol(ctx: &ScViewContext) -> String {
+    <Standard as IERC20>::symbol(ctx)
+  }
+
+  fn decimals(ctx: &ScViewContext) -> u8 {
+    <Standard as IERC20>::decimals(ctx)
+  }
+
+  fn totalSupply(ctx: &ScViewContext) -> U256 {
+    <Standard as IERC20>::totalSupply(ctx)
+  }
+
+  fn balanceOf(ctx: &ScViewContext, owner: &ScAgentId) -> U256 {
+    <Standard as IERC20>::balanceOf(ctx, owner)
+  }
+
+  fn allowance(ctx: &ScViewContext, owner: &ScAgentId, spender: &ScAgentId) -> U256 {
+    <Standard as IERC20>::allowance(ctx, owner, spender)
+  }
+
+  fn approve(ctx: &ScFuncContext, spender: &ScAgentId, value: &U256) -> bool {
+    <Standard as IERC20>::approve(ctx, spender, value)
+  }
+
+  fn transfer(ctx: &ScFuncContext, to: &ScAgentId, value: &U256) -> bool {
+    <Standard as IERC20>::transfer(ctx, to, value)
+  }
+
+  fn transferFrom(ctx: &ScFuncContext, from: &ScAgentId, to: &ScAgentId, value: &U256) -> bool {
+    <Standard as IERC20>::transferFrom(ctx, from, to, value)
+  }
+}
+
+impl IERC20Mintable for Token {
+  fn mint(ctx: &ScFuncContext, to: &ScAgentId, value: &U256) {
+    <Standard as IERC20Mintable>::mint(ctx, to, value);
+  }
+}
+
+fn upload<T: Export>(chain: &MockChain, name: &str) -> ScHash {
+  chain.upload(&Program::new(name).export::<T>()).unwrap()
+}
+
+#[test]
+fn test_contract_exports() {
+  let chain: MockChain = MockChain::new();
+  let alice: ScAddress = chain.address("alice");
+  let program: ScHash = upload::<Counter>(&chain, "counter");
+
+  chain.params().get_int64("start").set_value(5);
+  chain.deploy(&program, "counter").unwrap();
+  chain.fund(&alice, &ScColor::IOTA, 10);
+
+  let params: ScMutableMap = chain.params();
+
+  params.get_int64("amount").set_value(3);
+  params.get_agent_id("owner").set_value(&alice.as_agent_id());
+
+  let results: ScImmutableMap = chain.post(&alice, "counter", "addValue", &[]).unwrap();
+
+  assert_eq!(results.get_int64("counter").value(), 8);
+  assert_eq!(chain.call_view("counter", "get").unwrap().get_int64("value").value(), 8);
+  assert_eq!(
+    chain
+      .call_view("counter", "history")
+      .unwrap()
+      .get_int64_array("history")
+      .get_int64(0)
+      .value(),
+    8
+  );
+
+  // Params are required and read from their camelCase keys
+  chain.params().get_int64("amount").set_value(1);
+
+  assert_eq!(
+    chain.post(&alice, "counter", "addValue", &[]).err(),
+    Some(format!("missing required param: Key32({})", "owner".get_key_id().0))
+  );
+  assert_eq!(chain.call_view("counter", "get").unwrap().get_int64("value").value(), 8);
+}
+
+#[test]
+fn test_contract_schema() {
+  let _chain: MockChain = MockChain::new();
+  let schema: Schema = Counter::schema("counter");
+
+  assert_eq!(schema.funcs()[0].name(), "init");
+  assert_eq!(schema.funcs()[0].params(), [FieldSchema::new("start", ScTag::Int64)]);
+  assert_eq!(schema.funcs()[1].name(), "addValue");
+  assert_eq!(
+    schema.funcs()[1].params(),
+    [
+      FieldSchema::new("amount", ScTag::Int64),
+      FieldSchema::new("owner", ScTag::AgentId),
+    ]
+  );
+
+  let json: String = schema.to_json();
+
+  assert!(json.starts_with(r#"{"name":"counter","#));
+  assert!(json.contains(r#""results":[{"key":"counter","type":"Int64"}]"#));
+  assert!(json.contains(r#"{"name":"get","hname":""#));
+  assert!(json.contains(r#""results":[{"key":"value","type":"Int64"}]"#));
+  assert!(json.contains(r#""results":[{"key":"history","type":"Int64","array":true}]"#));
+  assert!(json.ends_with(r#""events":[{"name":"Counted","fields":[{"key":"value","type":"Int64"}]}]}"#));
+}
+
+#[test]
+fn test_contract_bridges() {
+  let chain: MockChain = MockChain::new();
+  let alice: ScAddress = chain.address("alice");
+  let bob: ScAgentId = chain.address("bob").as_agent_id();
+  let program: ScHash = upload::<Token>(&chain, "token");
+  let params: ScMutableMap = chain.params();
+
+  params.get_agent_id("owner").set_value(&alice.as_agent_id());
+  params.set("supply", &U256::from(100_u32));
+  chain.deploy(&program, "token").unwrap();
+  chain.fund(&alice, &ScColor::IOTA, 10);
+
+  let params: ScMutableMap = chain.params();
+
+  params.get_agent_id("to").set_value(&bob);
+  params.set("value", &U256::from(5_u32));
+  chain.post(&alice, "token", "mint", &[]).unwrap();
+
+  let params: ScMutableMap = chain.params();
+
+  params.get_agent_id("to").set_value(&bob);
+  params.set("value", &U256::from(10_u32));
+  chain.post(&alice, "token", "transfer", &[]).unwrap();
+  chain.params().get_agent_id("owner").set_value(&bob);
+
+  let results: ScImmutableMap = chain.call_view("token", "balanceOf").unwrap();
+
+  assert_eq!(results.get_value::<_, U256>("balance"), U256::from(15_u32));
+  assert_eq!(
+    chain.call_view("token", "name").unwrap().get_string("name").value(),
+    "Token"
+  );
+
+  // The bridges are described along with the methods of the contract
+  let schema: Schema = Token::schema("token");
+  let names: Vec<&str> = schema.funcs().iter().map(FuncSchema::name).collect();
+
+  assert_eq!(names, ["init", "approve", "transfer", "transferFrom", "mint"]);
+  assert_eq!(schema.views().len(), 6);
+  assert_eq!(schema.events().len(), 2);
+}
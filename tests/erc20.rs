@@ -0,0 +1,381 @@
+#![cfg(feature = "testing")]
+
+use scarab::contracts::erc20::*;
+use scarab::prelude::*;
+use scarab::testing::MockChain;
+use scarab::testing::MockHost;
+use scarab::testing::Program;
+use scarab::traits::utility::Event;
+use scarab::wasmlib::*;
+
+use self::common::balance_of;
+use self::common::Accounts;
+
+mod common;
+
+struct Token;
+
+impl ERC20Config for Token {
+  const NAME: &'static str = "Token";
+  const SYMBOL: &'static str = "TKN";
+  const DECIMALS: u8 = 6;
+  const CAP: Option<U256> = Some(U256::from_u128(1000));
+}
+
+type T = StandardERC20<Token>;
+
+// Initializes the token with a supply of 600 owned by alice
+fn setup(host: &MockHost) -> Accounts {
+  let accounts: Accounts = Accounts::new(host);
+  let params: ScMutableMap = host.params();
+
+  params.set("owner", &accounts.alice);
+  params.set("supply", &U256::from(600_u32));
+  host.call_func(T::func_init).unwrap();
+
+  accounts
+}
+
+fn balance(host: &MockHost, owner: &ScAgentId) -> U256 {
+  balance_of(host, <T as IERC20__Bridge>::view_balanceOf, owner)
+}
+
+fn allowance(host: &MockHost, owner: &ScAgentId, spender: &ScAgentId) -> U256 {
+  let params: ScMutableMap = host.params();
+
+  params.set("owner", owner);
+  params.set("spender", spender);
+  host.call_view(<T as IERC20__Bridge>::view_allowance).unwrap();
+  host.results().get_value("remaining")
+}
+
+fn total_supply(host: &MockHost) -> U256 {
+  host.call_view(<T as IERC20__Bridge>::view_totalSupply).unwrap();
+  host.results().get_value("totalSupply")
+}
+
+fn transfer(host: &MockHost, to: &ScAgentId, value: u32) -> Result<(), String> {
+  let params: ScMutableMap = host.params();
+
+  params.set("to", to);
+  params.set("value", &U256::from(value));
+  host.call_func(<T as IERC20__Bridge>::func_transfer)
+}
+
+fn transfer_from(host: &MockHost, from: &ScAgentId, to: &ScAgentId, value: u32) -> Result<(), String> {
+  let params: ScMutableMap = host.params();
+
+  params.set("from", from);
+  params.set("to", to);
+  params.set("value", &U256::from(value));
+  host.call_func(<T as IERC20__Bridge>::func_transferFrom)
+}
+
+fn approve(host: &MockHost, spender: &ScAgentId, value: &U256) {
+  let params: ScMutableMap = host.params();
+
+  params.set("spender", spender);
+  params.set("value", value);
+  host.call_func(<T as IERC20__Bridge>::func_approve).unwrap();
+}
+
+fn mint(host: &MockHost, to: &ScAgentId, value: u32) -> Result<(), String> {
+  let params: ScMutableMap = host.params();
+
+  params.set("to", to);
+  params.set("value", &U256::from(value));
+  host.call_func(<T as IERC20Mintable__Bridge>::func_mint)
+}
+
+#[test]
+fn test_init() {
+  let host: MockHost = MockHost::new("token");
+  let accounts: Accounts = setup(&host);
+
+  assert_eq!(balance(&host, &accounts.alice), U256::from(600_u32));
+  assert_eq!(total_supply(&host), U256::from(600_u32));
+
+  host.call_view(<T as IERC20__Bridge>::view_name).unwrap();
+
+  assert_eq!(host.results().get_string("name").value(), "Token");
+
+  host.call_view(<T as IERC20__Bridge>::view_decimals).unwrap();
+
+  assert_eq!(host.results().get_int64("decimals").value(), 6);
+
+  let params: ScMutableMap = host.params();
+
+  params.set("owner", &accounts.bob);
+  params.set("supply", &U256::ONE);
+
+  assert_eq!(
+    host.call_func(T::func_init),
+    Err("ERC20: already initialized".to_string())
+  );
+
+  let mint: Transfer = Transfer::from_text(&host.events()[0]).unwrap();
+
+  assert_eq!(mint.from.to_bytes(), [0; 37]);
+  assert_eq!(mint.to.to_bytes(), accounts.alice.to_bytes());
+  assert_eq!(mint.value, U256::from(600_u32));
+}
+
+#[test]
+fn test_init_owner() {
+  let host: MockHost = MockHost::new("token");
+  let bob: ScAgentId = host.agent_id("bob");
+  let params: ScMutableMap = host.params();
+
+  params.set("owner", &bob);
+  params.set("supply", &U256::from(1001_u32));
+
+  assert_eq!(host.call_func(T::func_init), Err("ERC20: cap exceeded".to_string()));
+
+  let params: ScMutableMap = host.params();
+
+  params.set("owner", &bob);
+  params.set("supply", &U256::from(1000_u32));
+  host.call_func(T::func_init).unwrap();
+
+  assert_eq!(balance(&host, &bob), U256::from(1000_u32));
+}
+
+#[test]
+fn test_deploy() {
+  let chain: MockChain = MockChain::new();
+  let alice: ScAgentId = chain.address("alice").as_agent_id();
+  let program: Program = Program::new("token")
+    .func("init", T::func_init)
+    .view("balanceOf", <T as IERC20__Bridge>::view_balanceOf);
+  let program: ScHash = chain.upload(&program).unwrap();
+
+  let owner: Key32 = "owner".get_key_id();
+
+  // `init` is called by the root contract, so the owner can't be the caller
+  chain.params().set("supply", &U256::from(600_u32));
+
+  assert_eq!(
+    chain.deploy(&program, "token"),
+    Err(format!("missing required param: Key32({})", owner.0))
+  );
+
+  let params: ScMutableMap = chain.params();
+
+  params.set("owner", &alice);
+  params.set("supply", &U256::from(600_u32));
+  chain.deploy(&program, "token").unwrap();
+  chain.params().set("owner", &alice);
+
+  let results: ScImmutableMap = chain.call_view("token", "balanceOf").unwrap();
+
+  assert_eq!(results.get_value::<_, U256>("balance"), U256::from(600_u32));
+}
+
+#[test]
+fn test_transfer() {
+  let host: MockHost = MockHost::new("token");
+  let accounts: Accounts = setup(&host);
+
+  transfer(&host, &accounts.bob, 250).unwrap();
+
+  assert_eq!(balance(&host, &accounts.alice), U256::from(350_u32));
+  assert_eq!(balance(&host, &accounts.bob), U256::from(250_u32));
+  assert_eq!(
+    transfer(&host, &accounts.bob, 351),
+    Err("ERC20: insufficient balance".to_string())
+  );
+
+  // Transfers to the sender leave the balance unchanged
+  transfer(&host, &accounts.alice, 350).unwrap();
+
+  assert_eq!(balance(&host, &accounts.alice), U256::from(350_u32));
+  assert_eq!(total_supply(&host), U256::from(600_u32));
+
+  let sent: Transfer = Transfer::from_text(&host.events()[1]).unwrap();
+
+  assert_eq!(sent.to.to_bytes(), accounts.bob.to_bytes());
+  assert_eq!(sent.value, U256::from(250_u32));
+}
+
+#[test]
+fn test_transfer_from() {
+  let host: MockHost = MockHost::new("token");
+  let accounts: Accounts = setup(&host);
+
+  approve(&host, &accounts.carol, &U256::from(100_u32));
+  host.set_caller(&accounts.carol);
+
+  assert_eq!(
+    transfer_from(&host, &accounts.alice, &accounts.bob, 101),
+    Err("ERC20: insufficient allowance".to_string())
+  );
+
+  transfer_from(&host, &accounts.alice, &accounts.bob, 60).unwrap();
+
+  assert_eq!(balance(&host, &accounts.alice), U256::from(540_u32));
+  assert_eq!(balance(&host, &accounts.bob), U256::from(60_u32));
+  assert_eq!(allowance(&host, &accounts.alice, &accounts.carol), U256::from(40_u32));
+
+  // The allowance is only spent by the approved spender
+  host.set_caller(&accounts.bob);
+
+  assert_eq!(
+    transfer_from(&host, &accounts.alice, &accounts.bob, 1),
+    Err("ERC20: insufficient allowance".to_string())
+  );
+
+  let approval: Approval = Approval::from_text(&host.events()[1]).unwrap();
+
+  assert_eq!(approval.owner.to_bytes(), accounts.alice.to_bytes());
+  assert_eq!(approval.spender.to_bytes(), accounts.carol.to_bytes());
+  assert_eq!(approval.value, U256::from(100_u32));
+}
+
+#[test]
+fn test_transfer_from_insufficient_balance() {
+  let host: MockHost = MockHost::new("token");
+  let accounts: Accounts = setup(&host);
+
+  approve(&host, &accounts.carol, &U256::from(1000_u32));
+  host.set_caller(&accounts.carol);
+
+  assert_eq!(
+    transfer_from(&host, &accounts.alice, &accounts.bob, 601),
+    Err("ERC20: insufficient balance".to_string())
+  );
+
+  // The allowance is not spent by a failed transfer
+  assert_eq!(allowance(&host, &accounts.alice, &accounts.carol), U256::from(1000_u32));
+}
+
+#[test]
+fn test_transfer_from_unlimited() {
+  let host: MockHost = MockHost::new("token");
+  let accounts: Accounts = setup(&host);
+
+  approve(&host, &accounts.carol, &U256::MAX);
+  host.set_caller(&accounts.carol);
+  transfer_from(&host, &accounts.alice, &accounts.bob, 100).unwrap();
+  transfer_from(&host, &accounts.alice, &accounts.carol, 500).unwrap();
+
+  assert_eq!(balance(&host, &accounts.alice), U256::ZERO);
+  assert_eq!(allowance(&host, &accounts.alice, &accounts.carol), U256::MAX);
+}
+
+#[test]
+fn test_mint() {
+  let host: MockHost = MockHost::new("token");
+  let accounts: Accounts = setup(&host);
+
+  mint(&host, &accounts.bob, 400).unwrap();
+
+  assert_eq!(balance(&host, &accounts.bob), U256::from(400_u32));
+  assert_eq!(total_supply(&host), U256::from(1000_u32));
+  assert_eq!(mint(&host, &accounts.bob, 1), Err("ERC20: cap exceeded".to_string()));
+  assert_eq!(total_supply(&host), U256::from(1000_u32));
+
+  host.call_view(<T as IERC20Capped__Bridge>::view_cap).unwrap();
+
+  assert_eq!(host.results().get_value::<_, U256>("cap"), U256::from(1000_u32));
+
+  host.set_caller(&accounts.bob);
+
+  assert_eq!(
+    mint(&host, &accounts.bob, 0),
+    Err("ERC20: caller is not the owner".to_string())
+  );
+}
+
+#[test]
+fn test_burn() {
+  let host: MockHost = MockHost::new("token");
+  let accounts: Accounts = setup(&host);
+
+  host.params().set("value", &U256::from(150_u32));
+  host.call_func(<T as IERC20Burnable__Bridge>::func_burn).unwrap();
+
+  assert_eq!(balance(&host, &accounts.alice), U256::from(450_u32));
+  assert_eq!(total_supply(&host), U256::from(450_u32));
+
+  host.params().set("value", &U256::from(451_u32));
+
+  assert_eq!(
+    host.call_func(<T as IERC20Burnable__Bridge>::func_burn),
+    Err("ERC20: insufficient balance".to_string())
+  );
+
+  approve(&host, &accounts.carol, &U256::from(50_u32));
+  host.set_caller(&accounts.carol);
+
+  let params: ScMutableMap = host.params();
+
+  params.set("from", &accounts.alice);
+  params.set("value", &U256::from(51_u32));
+
+  assert_eq!(
+    host.call_func(<T as IERC20Burnable__Bridge>::func_burnFrom),
+    Err("ERC20: insufficient allowance".to_string())
+  );
+
+  let params: ScMutableMap = host.params();
+
+  params.set("from", &accounts.alice);
+  params.set("value", &U256::from(50_u32));
+  host.call_func(<T as IERC20Burnable__Bridge>::func_burnFrom).unwrap();
+
+  assert_eq!(balance(&host, &accounts.alice), U256::from(400_u32));
+  assert_eq!(total_supply(&host), U256::from(400_u32));
+  assert_eq!(allowance(&host, &accounts.alice, &accounts.carol), U256::ZERO);
+
+  let burned: Transfer = Transfer::from_text(host.events().last().unwrap()).unwrap();
+
+  assert_eq!(burned.to.to_bytes(), [0; 37]);
+  assert_eq!(burned.value, U256::from(50_u32));
+}
+
+#[test]
+fn test_pause() {
+  let host: MockHost = MockHost::new("token");
+  let accounts: Accounts = setup(&host);
+
+  host.set_caller(&accounts.bob);
+
+  assert_eq!(
+    host.call_func(<T as IERC20Pausable__Bridge>::func_pause),
+    Err("ERC20: caller is not the owner".to_string())
+  );
+
+  host.set_caller(&accounts.alice);
+  host.call_func(<T as IERC20Pausable__Bridge>::func_pause).unwrap();
+  host.call_view(<T as IERC20Pausable__Bridge>::view_paused).unwrap();
+
+  assert_eq!(host.results().get_int64("paused").value(), 1);
+  assert_eq!(
+    host.call_func(<T as IERC20Pausable__Bridge>::func_pause),
+    Err("ERC20: paused".to_string())
+  );
+  assert_eq!(transfer(&host, &accounts.bob, 1), Err("ERC20: paused".to_string()));
+  assert_eq!(mint(&host, &accounts.bob, 1), Err("ERC20: paused".to_string()));
+
+  host.params().set("value", &U256::ONE);
+
+  assert_eq!(
+    host.call_func(<T as IERC20Burnable__Bridge>::func_burn),
+    Err("ERC20: paused".to_string())
+  );
+
+  let paused: Paused = Paused::from_text(host.events().last().unwrap()).unwrap();
+
+  assert_eq!(paused.account.to_bytes(), accounts.alice.to_bytes());
+
+  host.call_func(<T as IERC20Pausable__Bridge>::func_unpause).unwrap();
+
+  assert_eq!(
+    host.call_func(<T as IERC20Pausable__Bridge>::func_unpause),
+    Err("ERC20: not paused".to_string())
+  );
+
+  transfer(&host, &accounts.bob, 1).unwrap();
+
+  assert_eq!(balance(&host, &accounts.bob), U256::ONE);
+}
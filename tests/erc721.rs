@@ -0,0 +1,297 @@
+#![cfg(feature = "testing")]
+
+use scarab::contracts::erc721::*;
+use scarab::prelude::*;
+use scarab::testing::MockHost;
+use scarab::traits::utility::Event;
+use scarab::wasmlib::*;
+
+use self::common::balance_of;
+use self::common::Accounts;
+
+mod common;
+
+type T = StandardERC721;
+
+fn mint(ctx: &ScFuncContext) {
+  let to: ScAgentId = ctx.get_required_param("to");
+  let token_id: U256 = ctx.get_required_param("tokenId");
+
+  T::mint(ctx, &to, &token_id);
+}
+
+fn burn(ctx: &ScFuncContext) {
+  let token_id: U256 = ctx.get_required_param("tokenId");
+
+  T::burn(ctx, &token_id);
+}
+
+// Mints the tokens 1 and 2 to alice
+fn setup(host: &MockHost) -> Accounts {
+  let accounts: Accounts = Accounts::new(host);
+
+  for token_id in [1_u32, 2] {
+    let params: ScMutableMap = host.params();
+
+    params.set("to", &accounts.alice);
+    params.set("tokenId", &U256::from(token_id));
+    host.call_func(mint).unwrap();
+  }
+
+  accounts
+}
+
+fn owner_of(host: &MockHost, token_id: u32) -> Result<Vec<u8>, String> {
+  host.params().set("tokenId", &U256::from(token_id));
+  host.call_view(<T as IERC721__Bridge>::view_ownerOf)?;
+
+  Ok(host.results().get_agent_id("owner").value().to_bytes().to_vec())
+}
+
+fn get_approved(host: &MockHost, token_id: u32) -> Result<Vec<u8>, String> {
+  host.params().set("tokenId", &U256::from(token_id));
+  host.call_view(<T as IERC721__Bridge>::view_getApproved)?;
+
+  Ok(host.results().get_agent_id("approved").value().to_bytes().to_vec())
+}
+
+fn is_approved_for_all(host: &MockHost, owner: &ScAgentId, operator: &ScAgentId) -> bool {
+  let params: ScMutableMap = host.params();
+
+  params.set("owner", owner);
+  params.set("operator", operator);
+  host.call_view(<T as IERC721__Bridge>::view_isApprovedForAll).unwrap();
+  host.results().get_int64("approved").value() != 0
+}
+
+fn balance(host: &MockHost, owner: &ScAgentId) -> U256 {
+  balance_of(host, <T as IERC721__Bridge>::view_balanceOf, owner)
+}
+
+fn approve(host: &MockHost, approved: &ScAgentId, token_id: u32) -> Result<(), String> {
+  let params: ScMutableMap = host.params();
+
+  params.set("approved", approved);
+  params.set("tokenId", &U256::from(token_id));
+  host.call_func(<T as IERC721__Bridge>::func_approve)
+}
+
+fn set_approval_for_all(host: &MockHost, operator: &ScAgentId, approved: bool) -> Result<(), String> {
+  let params: ScMutableMap = host.params();
+
+  params.set("operator", operator);
+  params.set("approved", &approved);
+  host.call_func(<T as IERC721__Bridge>::func_setApprovalForAll)
+}
+
+fn transfer(host: &MockHost, from: &ScAgentId, to: &ScAgentId, token_id: u32) -> Result<(), String> {
+  let params: ScMutableMap = host.params();
+
+  params.set("from", from);
+  params.set("to", to);
+  params.set("tokenId", &U256::from(token_id));
+  host.call_func(<T as IERC721__Bridge>::func_transferFrom)
+}
+
+#[test]
+fn test_mint() {
+  let host: MockHost = MockHost::new("nft");
+  let accounts: Accounts = setup(&host);
+
+  assert_eq!(owner_of(&host, 1), Ok(accounts.alice.to_bytes().to_vec()));
+  assert_eq!(owner_of(&host, 3), Err("ERC721: invalid token id".to_string()));
+  assert_eq!(balance(&host, &accounts.alice), U256::from(2_u32));
+  assert_eq!(balance(&host, &accounts.bob), U256::ZERO);
+
+  let params: ScMutableMap = host.params();
+
+  params.set("to", &accounts.bob);
+  params.set("tokenId", &U256::ONE);
+
+  assert_eq!(host.call_func(mint), Err("ERC721: token already minted".to_string()));
+
+  let params: ScMutableMap = host.params();
+
+  params.set("to", &ScAgentId::from_bytes(&[0; 37]));
+  params.set("tokenId", &U256::from(3_u32));
+
+  assert_eq!(host.call_func(mint), Err("ERC721: mint to the zero agent".to_string()));
+
+  let minted: Transfer = Transfer::from_text(&host.events()[1]).unwrap();
+
+  assert_eq!(minted.from.to_bytes(), [0; 37]);
+  assert_eq!(minted.to.to_bytes(), accounts.alice.to_bytes());
+  assert_eq!(minted.token_id, U256::from(2_u32));
+}
+
+#[test]
+fn test_approve() {
+  let host: MockHost = MockHost::new("nft");
+  let accounts: Accounts = setup(&host);
+
+  assert_eq!(get_approved(&host, 1), Ok(vec![0; 37]));
+  assert_eq!(get_approved(&host, 3), Err("ERC721: invalid token id".to_string()));
+  assert_eq!(
+    approve(&host, &accounts.alice, 1),
+    Err("ERC721: approval to current owner".to_string())
+  );
+  assert_eq!(
+    approve(&host, &accounts.bob, 3),
+    Err("ERC721: invalid token id".to_string())
+  );
+
+  approve(&host, &accounts.bob, 1).unwrap();
+
+  assert_eq!(get_approved(&host, 1), Ok(accounts.bob.to_bytes().to_vec()));
+
+  // Only the owner or an operator can approve
+  host.set_caller(&accounts.bob);
+
+  assert_eq!(
+    approve(&host, &accounts.carol, 1),
+    Err("ERC721: caller is not the owner or an operator".to_string())
+  );
+
+  // The approved agent can transfer the token once
+  transfer(&host, &accounts.alice, &accounts.carol, 1).unwrap();
+
+  assert_eq!(owner_of(&host, 1), Ok(accounts.carol.to_bytes().to_vec()));
+  assert_eq!(get_approved(&host, 1), Ok(vec![0; 37]));
+  assert_eq!(
+    transfer(&host, &accounts.carol, &accounts.bob, 1),
+    Err("ERC721: caller is not the owner or approved".to_string())
+  );
+
+  let approval: Approval = Approval::from_text(&host.events()[2]).unwrap();
+
+  assert_eq!(approval.owner.to_bytes(), accounts.alice.to_bytes());
+  assert_eq!(approval.approved.to_bytes(), accounts.bob.to_bytes());
+  assert_eq!(approval.token_id, U256::ONE);
+}
+
+#[test]
+fn test_operator() {
+  let host: MockHost = MockHost::new("nft");
+  let accounts: Accounts = setup(&host);
+
+  assert_eq!(
+    set_approval_for_all(&host, &accounts.alice, true),
+    Err("ERC721: approve to caller".to_string())
+  );
+
+  set_approval_for_all(&host, &accounts.bob, true).unwrap();
+
+  assert!(is_approved_for_all(&host, &accounts.alice, &accounts.bob));
+
+  let params: ScMutableMap = host.params();
+
+  params.set("operator", &accounts.carol);
+  params.get_int64("approved").set_value(2);
+
+  assert_eq!(
+    host.call_func(<T as IERC721__Bridge>::func_setApprovalForAll),
+    Err(format!(
+      "invalid param: Key32({}): `bool` out of range: 2",
+      "approved".get_key_id().0
+    ))
+  );
+  assert!(!is_approved_for_all(&host, &accounts.bob, &accounts.alice));
+
+  // Operators can approve and transfer all tokens of the owner
+  host.set_caller(&accounts.bob);
+  approve(&host, &accounts.carol, 1).unwrap();
+  transfer(&host, &accounts.alice, &accounts.bob, 2).unwrap();
+
+  assert_eq!(get_approved(&host, 1), Ok(accounts.carol.to_bytes().to_vec()));
+  assert_eq!(owner_of(&host, 2), Ok(accounts.bob.to_bytes().to_vec()));
+
+  host.set_caller(&accounts.alice);
+  set_approval_for_all(&host, &accounts.bob, false).unwrap();
+
+  assert!(!is_approved_for_all(&host, &accounts.alice, &accounts.bob));
+
+  host.set_caller(&accounts.bob);
+
+  assert_eq!(
+    transfer(&host, &accounts.alice, &accounts.bob, 1),
+    Err("ERC721: caller is not the owner or approved".to_string())
+  );
+
+  let revoked: ApprovalForAll = ApprovalForAll::from_text(host.events().last().unwrap()).unwrap();
+
+  assert_eq!(revoked.owner.to_bytes(), accounts.alice.to_bytes());
+  assert_eq!(revoked.operator.to_bytes(), accounts.bob.to_bytes());
+  assert!(!revoked.approved);
+}
+
+#[test]
+fn test_transfer_checks() {
+  let host: MockHost = MockHost::new("nft");
+  let accounts: Accounts = setup(&host);
+
+  assert_eq!(
+    transfer(&host, &accounts.bob, &accounts.carol, 1),
+    Err("ERC721: transfer from incorrect owner".to_string())
+  );
+  assert_eq!(
+    transfer(&host, &accounts.alice, &ScAgentId::from_bytes(&[0; 37]), 1),
+    Err("ERC721: transfer to the zero agent".to_string())
+  );
+  assert_eq!(
+    transfer(&host, &accounts.alice, &accounts.bob, 3),
+    Err("ERC721: invalid token id".to_string())
+  );
+
+  host.set_caller(&accounts.bob);
+
+  assert_eq!(
+    transfer(&host, &accounts.alice, &accounts.bob, 1),
+    Err("ERC721: caller is not the owner or approved".to_string())
+  );
+
+  host.set_caller(&accounts.alice);
+  transfer(&host, &accounts.alice, &accounts.bob, 1).unwrap();
+
+  assert_eq!(owner_of(&host, 1), Ok(accounts.bob.to_bytes().to_vec()));
+  assert_eq!(balance(&host, &accounts.alice), U256::ONE);
+  assert_eq!(balance(&host, &accounts.bob), U256::ONE);
+
+  let sent: Transfer = Transfer::from_text(host.events().last().unwrap()).unwrap();
+
+  assert_eq!(sent.from.to_bytes(), accounts.alice.to_bytes());
+  assert_eq!(sent.to.to_bytes(), accounts.bob.to_bytes());
+  assert_eq!(sent.token_id, U256::ONE);
+}
+
+#[test]
+fn test_burn() {
+  let host: MockHost = MockHost::new("nft");
+  let accounts: Accounts = setup(&host);
+
+  approve(&host, &accounts.bob, 2).unwrap();
+  host.params().set("tokenId", &U256::from(2_u32));
+  host.call_func(burn).unwrap();
+
+  assert_eq!(owner_of(&host, 2), Err("ERC721: invalid token id".to_string()));
+  assert_eq!(balance(&host, &accounts.alice), U256::ONE);
+
+  host.params().set("tokenId", &U256::from(2_u32));
+
+  assert_eq!(host.call_func(burn), Err("ERC721: invalid token id".to_string()));
+
+  let burned: Transfer = Transfer::from_text(host.events().last().unwrap()).unwrap();
+
+  assert_eq!(burned.from.to_bytes(), accounts.alice.to_bytes());
+  assert_eq!(burned.to.to_bytes(), [0; 37]);
+  assert_eq!(burned.token_id, U256::from(2_u32));
+
+  // Burned tokens can be minted again without their previous approval
+  let params: ScMutableMap = host.params();
+
+  params.set("to", &accounts.carol);
+  params.set("tokenId", &U256::from(2_u32));
+  host.call_func(mint).unwrap();
+
+  assert_eq!(owner_of(&host, 2), Ok(accounts.carol.to_bytes().to_vec()));
+  assert_eq!(get_approved(&host, 2), Ok(vec![0; 37]));
+}
@@ -0,0 +1,143 @@
+#![cfg(feature = "testing")]
+
+use scarab::contracts::core::*;
+use scarab::testing::MockChain;
+use scarab::testing::Program;
+use scarab::traits::utility::Event;
+use scarab::types::EventError;
+use scarab::types::EventRecord;
+use scarab::types::EventSchema;
+use scarab::types::ScTag;
+use scarab::types::U256;
+use scarab::wasmlib::*;
+use scarab::Decode;
+use scarab::Encode;
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Deposited {
+  memo: String,
+  amount: U256,
+  tags: Vec<i64>,
+}
+
+impl Event for Deposited {
+  const NAME: &'static str = "Deposited";
+
+  fn schema() -> EventSchema {
+    EventSchema::new(Self::NAME)
+      .field("memo", ScTag::String)
+      .field("amount", ScTag::Bytes)
+      .field("tags", ScTag::Bytes)
+  }
+}
+
+fn deposited(memo: &str, amount: u32) -> Deposited {
+  Deposited {
+    memo: memo.to_string(),
+    amount: U256::from(amount),
+    tags: vec![-1, amount as i64],
+  }
+}
+
+fn deposit(ctx: &ScFuncContext) {
+  deposited("first", 1).emit(ctx);
+  deposited("second", 2).emit(ctx);
+}
+
+fn noise(ctx: &ScFuncContext) {
+  ctx.event("Withdrawn 00");
+  ctx.event("Deposited zz");
+  ctx.event("unnamed");
+}
+
+fn records(ctx: &ScViewContext) {
+  let records: Vec<Vec<u8>> = EventLog::new("bank").search(ctx, EventFilter::new());
+  let output: ScMutableBytesArray = ctx.results().get_bytes_array("records");
+
+  for (index, record) in records.iter().enumerate() {
+    output.get_bytes(index as i32).set_value(record);
+  }
+}
+
+fn deposits(ctx: &ScViewContext) {
+  let to: i64 = ctx.params().get_int64("to").value();
+
+  match EventLog::new("bank").search_events::<Deposited>(ctx, EventFilter::new().to(to)) {
+    Ok(events) => ctx.results().get_int64("count").set_value(events.len() as i64),
+    Err(error) => ctx.results().get_string("error").set_value(&error.to_string()),
+  }
+}
+
+fn setup() -> MockChain {
+  let chain: MockChain = MockChain::new();
+  let program: Program = Program::new("bank")
+    .func("deposit", deposit)
+    .func("noise", noise)
+    .view("records", records)
+    .view("deposits", deposits);
+  let program: ScHash = chain.upload(&program).unwrap();
+  let alice: ScAddress = chain.address("alice");
+
+  chain.deploy(&program, "bank").unwrap();
+  chain.set_timestamp(10);
+  chain.post(&alice, "bank", "deposit", &[]).unwrap();
+  chain.set_timestamp(20);
+  chain.post(&alice, "bank", "noise", &[]).unwrap();
+  chain
+}
+
+fn decode_records(chain: &MockChain) -> Vec<EventRecord> {
+  let results: ScImmutableMap = chain.call_view("bank", "records").unwrap();
+  let records: ScImmutableBytesArray = results.get_bytes_array("records");
+
+  (0..records.length())
+    .map(|index| EventRecord::from_bytes(&records.get_bytes(index).value()).unwrap())
+    .collect()
+}
+
+#[test]
+fn test_event_roundtrip() {
+  let chain: MockChain = setup();
+  let records: Vec<EventRecord> = decode_records(&chain);
+
+  assert_eq!(records.len(), 5);
+  assert_eq!(records[0].timestamp(), 10);
+  assert!(records[0].is::<Deposited>());
+  assert_eq!(records[0].decode::<Deposited>(), Ok(deposited("first", 1)));
+  assert_eq!(records[1].decode::<Deposited>(), Ok(deposited("second", 2)));
+  assert_eq!(records[1].text(), deposited("second", 2).to_text());
+}
+
+#[test]
+fn test_event_errors() {
+  let chain: MockChain = setup();
+  let records: Vec<EventRecord> = decode_records(&chain);
+
+  assert_eq!(records[2].timestamp(), 20);
+  assert!(!records[2].is::<Deposited>());
+  assert_eq!(
+    records[2].decode::<Deposited>(),
+    Err(EventError::Name("Withdrawn".to_string()))
+  );
+  assert_eq!(records[3].decode::<Deposited>(), Err(EventError::Format));
+  assert_eq!(records[4].name(), None);
+  assert_eq!(records[4].decode::<Deposited>(), Err(EventError::Format));
+}
+
+#[test]
+fn test_search_events() {
+  let chain: MockChain = setup();
+
+  chain.params().get_int64("to").set_value(10);
+
+  let results: ScImmutableMap = chain.call_view("bank", "deposits").unwrap();
+
+  assert_eq!(results.get_int64("count").value(), 2);
+
+  // Records of other events are skipped, malformed events fail the search
+  chain.params().get_int64("to").set_value(20);
+
+  let results: ScImmutableMap = chain.call_view("bank", "deposits").unwrap();
+
+  assert_eq!(results.get_string("error").value(), "invalid event format");
+}
@@ -0,0 +1,446 @@
+#![cfg(feature = "testing")]
+
+use scarab::contracts::core::*;
+use scarab::testing::MockChain;
+use scarab::testing::Program;
+use scarab::wasmlib::*;
+
+fn forward(ctx: &ScFuncContext) {
+  let amount: i64 = ctx.params().get_int64("amount").value();
+
+  ctx.call(
+    ScHname::new("receiver"),
+    ScHname::new("receive"),
+    None,
+    Some(ScTransfers::iotas(amount)),
+  );
+}
+
+fn receive(ctx: &ScFuncContext) {
+  ctx.event(&format!("receive {}", ctx.incoming().balance(&ScColor::IOTA)));
+}
+
+fn balance(ctx: &ScViewContext) {
+  let agent: ScAgentId = ctx.params().get_agent_id("agent").value();
+  let balance: u64 = Accounts::balance(ctx, &agent).get(&ScColor::IOTA).unwrap_or_default();
+
+  ctx.results().get_int64("balance").set_value(balance as i64);
+}
+
+fn deposit(ctx: &ScFuncContext) {
+  let agent: ScAgentId = ctx.params().get_agent_id("agent").value();
+  let amount: i64 = ctx.params().get_int64("amount").value();
+
+  Accounts::deposit_account(ctx, ScTransfers::iotas(amount), &agent);
+}
+
+fn assets(ctx: &ScViewContext) {
+  let agent: ScAgentId = ctx.params().get_agent_id("agent").value();
+  let total: u64 = Accounts::assets(ctx).get(&ScColor::IOTA).unwrap_or_default();
+  let listed: bool = Accounts::accounts(ctx).get_bytes(&agent).exists();
+
+  ctx.results().get_int64("total").set_value(total as i64);
+  ctx.results().get_int64("listed").set_value(listed as i64);
+}
+
+fn store(ctx: &ScFuncContext) {
+  let blob: ScMutableMap = ScMutableMap::new();
+
+  blob
+    .get_bytes("data")
+    .set_value(&ctx.params().get_bytes("data").value());
+
+  if ctx.params().get_int64("reserved").value() != 0 {
+    blob.get_string("d").set_value("description");
+  }
+
+  ctx.results().get_hash("hash").set_value(&Blob::store(ctx, blob));
+}
+
+fn inspect(ctx: &ScViewContext) {
+  let hash: ScHash = ctx.params().get_hash("hash").value();
+
+  ctx
+    .results()
+    .get_bytes("data")
+    .set_value(&Blob::field(ctx, &hash, b"data"));
+  ctx
+    .results()
+    .get_int64("info")
+    .set_value(i64::from(Blob::info(ctx, &hash).get("data").unwrap_or_default()));
+  ctx
+    .results()
+    .get_int64("size")
+    .set_value(i64::from(Blob::list(ctx).get(&hash).unwrap_or_default()));
+}
+
+fn emit(ctx: &ScFuncContext) {
+  ctx.event(&format!("emit {}", ctx.timestamp()));
+}
+
+fn search(ctx: &ScViewContext) {
+  let log: EventLog = EventLog::new("emitter");
+  let from: i64 = ctx.params().get_int64("from").value();
+  let to: i64 = ctx.params().get_int64("to").value();
+  let count: i64 = ctx.params().get_int64("count").value();
+  let records: Vec<Vec<u8>> = log.search(ctx, EventFilter::new().from(from).to(to).count(count));
+  let output: ScMutableBytesArray = ctx.results().get_bytes_array("records");
+
+  for (index, record) in records.iter().enumerate() {
+    output.get_bytes(index as i32).set_value(record);
+  }
+
+  ctx.results().get_int64("count").set_value(log.count(ctx));
+}
+
+fn claim(ctx: &ScFuncContext) {
+  Root::claim_ownership(ctx);
+}
+
+fn configure(ctx: &ScFuncContext) {
+  let deployer: ScAgentId = ctx.params().get_agent_id("deployer").value();
+
+  Root::set_default_fee(ctx, Fee::new().owner(2));
+  Root::contract(&ScHname::new("receiver")).set_fee(ctx, Fee::new().validator(1));
+
+  if ctx.params().get_int64("revoke").value() != 0 {
+    Root::revoke_deploy_permission(ctx, &deployer);
+  } else {
+    Root::grant_deploy_permission(ctx, &deployer);
+  }
+}
+
+fn install(ctx: &ScFuncContext) {
+  let program: ScHash = ctx.params().get_hash("program").value();
+  let name: String = ctx.params().get_string("name").value();
+
+  Root::deploy(ctx, Deploy::new(&program, &name).description("installed"));
+}
+
+fn init(ctx: &ScFuncContext) {
+  ctx.state().get_agent_id("caller").set_value(&ctx.caller());
+  ctx
+    .state()
+    .get_agent_id("creator")
+    .set_value(&ctx.params().get_agent_id("creator").value());
+}
+
+fn initialized(ctx: &ScViewContext) {
+  ctx
+    .results()
+    .get_agent_id("caller")
+    .set_value(&ctx.state().get_agent_id("caller").value());
+  ctx
+    .results()
+    .get_agent_id("creator")
+    .set_value(&ctx.state().get_agent_id("creator").value());
+}
+
+fn chain_info(ctx: &ScViewContext) {
+  let chain: Chain = Root::chain(ctx);
+  let record: ContractRecord = Root::contract(&ScHname::new("receiver")).record(ctx);
+  let fees: ContractFees = Root::contract(&ScHname::new("receiver")).fees(ctx);
+
+  ctx.results().get_agent_id("owner").set_value(&chain.owner_id());
+  ctx.results().get_string("description").set_value(&chain.description());
+  ctx.results().get_string("name").set_value(record.name());
+  ctx
+    .results()
+    .get_int64("ownerFee")
+    .set_value(fees.owner_fee().unwrap_or_default() as i64);
+  ctx
+    .results()
+    .get_int64("validatorFee")
+    .set_value(fees.validator_fee().unwrap_or_default() as i64);
+}
+
+fn setup() -> MockChain {
+  let chain: MockChain = MockChain::new();
+  let sender: Program = Program::new("sender").func("forward", forward).func("deposit", deposit);
+  let receiver: Program = Program::new("receiver")
+    .func("receive", receive)
+    .view("balance", balance)
+    .view("assets", assets)
+    .view("chainInfo", chain_info);
+
+  let sender: ScHash = chain.upload(&sender).unwrap();
+  let receiver: ScHash = chain.upload(&receiver).unwrap();
+
+  chain.deploy(&sender, "sender").unwrap();
+  chain.deploy(&receiver, "receiver").unwrap();
+  chain
+}
+
+fn balance_of(chain: &MockChain, agent: &ScAgentId) -> i64 {
+  chain.params().get_agent_id("agent").set_value(agent);
+  chain
+    .call_view("receiver", "balance")
+    .unwrap()
+    .get_int64("balance")
+    .value()
+}
+
+#[test]
+fn test_transfer_between_contracts() {
+  let chain: MockChain = setup();
+  let alice: ScAddress = chain.address("alice");
+
+  chain.fund(&alice, &ScColor::IOTA, 1000);
+  chain.params().get_int64("amount").set_value(40);
+  chain
+    .post(&alice, "sender", "forward", &[(ScColor::IOTA, 100)])
+    .unwrap();
+
+  assert_eq!(chain.l1_balance(&alice, &ScColor::IOTA), 900);
+  assert_eq!(balance_of(&chain, &chain.contract_id("sender")), 60);
+  assert_eq!(balance_of(&chain, &chain.contract_id("receiver")), 40);
+  assert_eq!(chain.events("receiver"), ["receive 40"]);
+
+  // A failing transfer rolls back the whole request
+  chain.params().get_int64("amount").set_value(200);
+
+  assert!(chain.post(&alice, "sender", "forward", &[]).is_err());
+  assert_eq!(balance_of(&chain, &chain.contract_id("sender")), 60);
+  assert_eq!(balance_of(&chain, &chain.contract_id("receiver")), 40);
+  assert_eq!(chain.events("receiver"), ["receive 40"]);
+}
+
+#[test]
+fn test_accounts() {
+  let chain: MockChain = setup();
+  let alice: ScAddress = chain.address("alice");
+
+  chain.fund(&alice, &ScColor::IOTA, 100);
+  chain.post(&alice, "sender", "forward", &[(ScColor::IOTA, 50)]).unwrap();
+
+  let params: ScMutableMap = chain.params();
+
+  params.get_agent_id("agent").set_value(&alice.as_agent_id());
+  params.get_int64("amount").set_value(30);
+  chain.post(&alice, "sender", "deposit", &[]).unwrap();
+
+  assert_eq!(balance_of(&chain, &chain.contract_id("sender")), 20);
+  assert_eq!(balance_of(&chain, &alice.as_agent_id()), 30);
+
+  chain.params().get_agent_id("agent").set_value(&alice.as_agent_id());
+
+  let results: ScImmutableMap = chain.call_view("receiver", "assets").unwrap();
+
+  assert_eq!(results.get_int64("total").value(), 50);
+  assert_eq!(results.get_int64("listed").value(), 1);
+
+  // Withdrawing moves the on-chain balance of the caller to L1
+  chain.post(&alice, "accounts", "withdrawToAddress", &[]).unwrap();
+
+  assert_eq!(balance_of(&chain, &alice.as_agent_id()), 0);
+  assert_eq!(chain.l1_balance(&alice, &ScColor::IOTA), 80);
+  assert!(chain.transfers()[0].balances == vec![(ScColor::IOTA, 30)]);
+
+  let params: ScMutableMap = chain.params();
+
+  params.get_agent_id("agent").set_value(&alice.as_agent_id());
+  params.get_int64("amount").set_value(30);
+
+  assert!(chain.post(&alice, "sender", "deposit", &[]).is_err());
+}
+
+#[test]
+fn test_blob() {
+  let chain: MockChain = MockChain::new();
+  let program: Program = Program::new("storage").func("store", store).view("inspect", inspect);
+  let program: ScHash = chain.upload(&program).unwrap();
+  let alice: ScAddress = chain.address("alice");
+
+  chain.deploy(&program, "storage").unwrap();
+  chain.params().get_bytes("data").set_value(b"hello");
+
+  let hash: ScHash = chain
+    .post(&alice, "storage", "store", &[])
+    .unwrap()
+    .get_hash("hash")
+    .value();
+
+  chain.params().get_hash("hash").set_value(&hash);
+
+  let results: ScImmutableMap = chain.call_view("storage", "inspect").unwrap();
+
+  assert_eq!(results.get_bytes("data").value(), b"hello");
+  assert_eq!(results.get_int64("info").value(), 5);
+  assert_eq!(results.get_int64("size").value(), 5);
+
+  let params: ScMutableMap = chain.params();
+
+  params.get_bytes("data").set_value(b"hello");
+  params.get_int64("reserved").set_value(1);
+
+  assert_eq!(
+    chain.post(&alice, "storage", "store", &[]).err(),
+    Some("reserved field: `d`".to_string())
+  );
+}
+
+#[test]
+fn test_eventlog() {
+  let chain: MockChain = MockChain::new();
+  let program: Program = Program::new("emitter").func("emit", emit).view("search", search);
+  let program: ScHash = chain.upload(&program).unwrap();
+  let alice: ScAddress = chain.address("alice");
+
+  chain.deploy(&program, "emitter").unwrap();
+
+  for timestamp in [10, 20, 30, 40].iter() {
+    chain.set_timestamp(*timestamp);
+    chain.post(&alice, "emitter", "emit", &[]).unwrap();
+  }
+
+  let params: ScMutableMap = chain.params();
+
+  params.get_int64("from").set_value(20);
+  params.get_int64("to").set_value(40);
+  params.get_int64("count").set_value(2);
+
+  let results: ScImmutableMap = chain.call_view("emitter", "search").unwrap();
+  let records: ScImmutableBytesArray = results.get_bytes_array("records");
+
+  assert_eq!(results.get_int64("count").value(), 4);
+  assert_eq!(records.length(), 2);
+
+  // Records hold the timestamp followed by the event text
+  assert_eq!(&records.get_bytes(0).value()[..8], &30_i64.to_le_bytes());
+  assert_eq!(&records.get_bytes(0).value()[8..], b"emit 30");
+  assert_eq!(&records.get_bytes(1).value()[8..], b"emit 40");
+}
+
+#[test]
+fn test_root() {
+  let chain: MockChain = setup();
+  let owner: ScAddress = chain.address("owner");
+  let alice: ScAddress = chain.address("alice");
+  let admin: Program = Program::new("admin")
+    .func("claim", claim)
+    .func("configure", configure)
+    .func("install", install);
+  let admin: ScHash = chain.upload(&admin).unwrap();
+  let installed: ScHash = chain.upload(&Program::new("installed")).unwrap();
+
+  chain.deploy(&admin, "admin").unwrap();
+  chain.fund(&alice, &ScColor::IOTA, 10);
+
+  let results: ScImmutableMap = chain.call_view("receiver", "chainInfo").unwrap();
+
+  assert_eq!(results.get_string("description").value(), "Mock Chain");
+  assert_eq!(results.get_string("name").value(), "receiver");
+  assert_eq!(
+    results.get_agent_id("owner").value().to_bytes(),
+    owner.as_agent_id().to_bytes()
+  );
+
+  // Only the chain owner can change fees
+  chain.deploy(&admin, "installer").unwrap();
+  chain
+    .params()
+    .get_agent_id("deployer")
+    .set_value(&chain.contract_id("installer"));
+
+  assert!(chain.post(&alice, "admin", "configure", &[]).is_err());
+
+  // Ownership is transferred once the new owner claims it
+  chain
+    .params()
+    .get_agent_id("$$owner$$")
+    .set_value(&chain.contract_id("admin"));
+  chain.post(&owner, "root", "delegateChainOwnership", &[]).unwrap();
+  chain.post(&alice, "admin", "claim", &[]).unwrap();
+
+  assert!(chain.owner().to_bytes() == chain.contract_id("admin").to_bytes());
+
+  let params: ScMutableMap = chain.params();
+
+  params.get_hash("program").set_value(&installed);
+  params.get_string("name").set_value("first");
+
+  assert_eq!(
+    chain.post(&alice, "installer", "install", &[]).err(),
+    Some("unauthorized: deploy permission required".to_string())
+  );
+
+  chain
+    .params()
+    .get_agent_id("deployer")
+    .set_value(&chain.contract_id("installer"));
+  chain.post(&alice, "admin", "configure", &[]).unwrap();
+
+  let results: ScImmutableMap = chain.call_view("receiver", "chainInfo").unwrap();
+
+  assert_eq!(results.get_int64("ownerFee").value(), 2);
+  assert_eq!(results.get_int64("validatorFee").value(), 1);
+
+  // Requests now pay the owner fee
+  let params: ScMutableMap = chain.params();
+
+  params.get_hash("program").set_value(&installed);
+  params.get_string("name").set_value("first");
+
+  assert_eq!(
+    chain.post(&alice, "installer", "install", &[]).err(),
+    Some("insufficient fee: 0 < 2".to_string())
+  );
+
+  let params: ScMutableMap = chain.params();
+
+  params.get_hash("program").set_value(&installed);
+  params.get_string("name").set_value("first");
+  chain
+    .post(&alice, "installer", "install", &[(ScColor::IOTA, 2)])
+    .unwrap();
+
+  assert_eq!(chain.balance(&chain.owner(), &ScColor::IOTA), 2);
+  assert_eq!(
+    chain.deploy(&installed, "first").err(),
+    Some("contract already exists: first".to_string())
+  );
+
+  let params: ScMutableMap = chain.params();
+
+  params
+    .get_agent_id("deployer")
+    .set_value(&chain.contract_id("installer"));
+  params.get_int64("revoke").set_value(1);
+  chain.post(&alice, "admin", "configure", &[(ScColor::IOTA, 2)]).unwrap();
+
+  let params: ScMutableMap = chain.params();
+
+  params.get_hash("program").set_value(&installed);
+  params.get_string("name").set_value("second");
+
+  assert_eq!(
+    chain.post(&alice, "installer", "install", &[(ScColor::IOTA, 2)]).err(),
+    Some("unauthorized: deploy permission required".to_string())
+  );
+  assert_eq!(chain.l1_balance(&alice, &ScColor::IOTA), 6);
+}
+
+#[test]
+fn test_init() {
+  let chain: MockChain = MockChain::new();
+  let alice: ScAddress = chain.address("alice");
+  let program: Program = Program::new("token")
+    .func("init", init)
+    .view("initialized", initialized);
+  let program: ScHash = chain.upload(&program).unwrap();
+
+  chain.params().get_agent_id("creator").set_value(&alice.as_agent_id());
+  chain.deploy(&program, "token").unwrap();
+
+  // `init` is called by root, so the deployer is only known from the params
+  let results: ScImmutableMap = chain.call_view("token", "initialized").unwrap();
+
+  assert_eq!(
+    results.get_agent_id("caller").value().to_bytes(),
+    chain.contract_id("root").to_bytes()
+  );
+  assert_eq!(
+    results.get_agent_id("creator").value().to_bytes(),
+    alice.as_agent_id().to_bytes()
+  );
+}
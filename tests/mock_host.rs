@@ -0,0 +1,246 @@
+#![cfg(feature = "testing")]
+
+use scarab::testing::MockHost;
+use scarab::wasmlib::*;
+
+fn increment(ctx: &ScFuncContext) {
+  let counter: ScMutableInt64 = ctx.state().get_int64("counter");
+  let amount: i64 = ctx.params().get_int64("amount").value();
+
+  ctx.require(amount > 0, "amount must be positive");
+  counter.set_value(counter.value() + amount);
+  ctx.results().get_int64("counter").set_value(counter.value());
+  ctx.log("incremented");
+  ctx.trace("counter updated");
+  ctx.event("counter.incremented");
+}
+
+fn counter(ctx: &ScViewContext) {
+  let counter: i64 = ctx.state().get_int64("counter").value();
+
+  ctx.results().get_int64("counter").set_value(counter);
+}
+
+fn modify(_: &ScViewContext) {
+  ScFuncContext {}.state().get_int64("counter").set_value(99);
+}
+
+fn collect(ctx: &ScFuncContext) {
+  let names: ScImmutableStringArray = ctx.params().get_string_array("names");
+  let target: ScMutableStringArray = ctx.state().get_string_array("names");
+
+  for index in 0..names.length() {
+    target
+      .get_string(target.length())
+      .set_value(&names.get_string(index).value());
+  }
+
+  ctx.results().get_int64("length").set_value(i64::from(target.length()));
+}
+
+fn nest(ctx: &ScFuncContext) {
+  let inner: ScMutableMap = ctx.state().get_map("outer").get_map("inner");
+
+  inner.get_string("name").set_value("nested");
+  ctx.state().get_map("outer").get_int64("count").set_value(1);
+}
+
+fn abort(ctx: &ScFuncContext) {
+  ctx.log("aborting");
+  ctx.event("counter.aborted");
+  ctx.panic("aborted");
+}
+
+fn pay(ctx: &ScFuncContext) {
+  let address: ScAddress = ScAddress::from_bytes(&[7; 33]);
+  let amount: i64 = ctx.params().get_int64("amount").value();
+
+  ctx.transfer_to_address(&address, ScTransfers::iotas(amount));
+}
+
+fn callee(ctx: &ScFuncContext) {
+  let value: i64 = ctx.params().get_int64("value").value();
+
+  ctx.state().get_int64("value").set_value(value);
+  ctx.results().get_int64("double").set_value(value * 2);
+  ctx
+    .results()
+    .get_int64("incoming")
+    .set_value(ctx.incoming().balance(&ScColor::IOTA));
+}
+
+fn caller(ctx: &ScFuncContext) {
+  let params: ScMutableMap = ScMutableMap::new();
+
+  params.get_int64("value").set_value(21);
+
+  let results: ScImmutableMap = ctx.call(
+    ScHname::new("other"),
+    ScHname::new("callee"),
+    Some(params),
+    Some(ScTransfers::iotas(3)),
+  );
+
+  ctx
+    .results()
+    .get_int64("double")
+    .set_value(results.get_int64("double").value());
+  ctx
+    .results()
+    .get_int64("incoming")
+    .set_value(results.get_int64("incoming").value());
+}
+
+#[test]
+fn test_params_and_results() {
+  let host: MockHost = MockHost::new("counter");
+
+  host.params().get_int64("amount").set_value(5);
+  host.call_func(increment).unwrap();
+
+  assert_eq!(host.results().get_int64("counter").value(), 5);
+
+  // Maps created after the params do not replace them
+  let params: ScMutableMap = host.params();
+  let _other: ScMutableMap = ScMutableMap::new();
+
+  params.get_int64("amount").set_value(2);
+  host.call_func(increment).unwrap();
+
+  assert_eq!(host.results().get_int64("counter").value(), 7);
+
+  // Each call to `params` starts with an empty map
+  host.params();
+
+  assert_eq!(host.call_func(increment), Err("amount must be positive".to_string()));
+}
+
+#[test]
+fn test_state() {
+  let host: MockHost = MockHost::new("counter");
+
+  host.params().get_int64("amount").set_value(5);
+  host.call_func(increment).unwrap();
+  host.params().get_int64("amount").set_value(-1);
+
+  assert!(host.call_func(increment).is_err());
+  assert_eq!(host.state().get_int64("counter").value(), 5);
+
+  host.call_view(counter).unwrap();
+
+  assert_eq!(host.results().get_int64("counter").value(), 5);
+  assert_eq!(
+    host.call_view(modify),
+    Err("attempt to modify state in a view".to_string())
+  );
+  assert_eq!(host.state().get_int64("counter").value(), 5);
+}
+
+#[test]
+fn test_maps() {
+  let host: MockHost = MockHost::new("maps");
+
+  host.call_func(nest).unwrap();
+
+  let outer: ScMutableMap = host.state().get_map("outer");
+
+  assert_eq!(outer.get_map("inner").get_string("name").value(), "nested");
+  assert_eq!(outer.get_int64("count").value(), 1);
+  assert!(!outer.get_map("other").get_string("name").exists());
+
+  outer.clear();
+
+  assert!(!outer.get_int64("count").exists());
+  assert!(!outer.get_map("inner").get_string("name").exists());
+}
+
+#[test]
+fn test_key_namespaces() {
+  let host: MockHost = MockHost::new("keys");
+  let state: ScMutableMap = host.state();
+  let bytes: ScHash = ScHash::from_bytes(&[b'a'; 32]);
+  let string: String = "a".repeat(32);
+
+  state.get_int64(&string).set_value(1);
+  state.get_int64(&bytes).set_value(2);
+
+  assert_ne!(string.get_key_id().0, bytes.get_key_id().0);
+  assert_eq!(state.get_int64(&string).value(), 1);
+  assert_eq!(state.get_int64(&bytes).value(), 2);
+}
+
+#[test]
+fn test_arrays() {
+  let host: MockHost = MockHost::new("arrays");
+  let names: ScMutableStringArray = host.params().get_string_array("names");
+
+  names.get_string(0).set_value("alice");
+  names.get_string(1).set_value("bob");
+  host.call_func(collect).unwrap();
+
+  assert_eq!(host.results().get_int64("length").value(), 2);
+
+  host.params().get_string_array("names").get_string(0).set_value("carol");
+  host.call_func(collect).unwrap();
+
+  let stored: ScMutableStringArray = host.state().get_string_array("names");
+
+  assert_eq!(stored.length(), 3);
+  assert_eq!(stored.get_string(2).value(), "carol");
+
+  stored.clear();
+
+  assert_eq!(stored.length(), 0);
+  assert!(!stored.get_string(0).exists());
+}
+
+#[test]
+fn test_balances() {
+  let host: MockHost = MockHost::new("bank");
+
+  host.set_balance(&ScColor::IOTA, 10);
+  host.params().get_int64("amount").set_value(4);
+  host.call_func(pay).unwrap();
+
+  assert_eq!(host.balance(&ScColor::IOTA), 6);
+  assert_eq!(host.transfers().len(), 1);
+  assert!(host.transfers()[0].address == ScAddress::from_bytes(&[7; 33]));
+  assert!(host.transfers()[0].balances == vec![(ScColor::IOTA, 4)]);
+
+  host.params().get_int64("amount").set_value(7);
+
+  assert!(host.call_func(pay).is_err());
+  assert_eq!(host.balance(&ScColor::IOTA), 6);
+  assert_eq!(host.transfers().len(), 1);
+}
+
+#[test]
+fn test_calls() {
+  let host: MockHost = MockHost::new("bank");
+
+  host.set_balance(&ScColor::IOTA, 10);
+  host.register_func("other", "callee", callee);
+  host.call_func(caller).unwrap();
+
+  assert_eq!(host.results().get_int64("double").value(), 42);
+  assert_eq!(host.results().get_int64("incoming").value(), 3);
+  assert_eq!(host.balance(&ScColor::IOTA), 7);
+
+  // The callee writes to its own state
+  assert!(!host.state().get_int64("value").exists());
+}
+
+#[test]
+fn test_logs() {
+  let host: MockHost = MockHost::new("counter");
+
+  host.params().get_int64("amount").set_value(1);
+  host.call_func(increment).unwrap();
+
+  assert_eq!(host.call_func(abort), Err("aborted".to_string()));
+
+  // Log messages are kept when a request fails, events are rolled back
+  assert_eq!(host.logs(), ["incremented", "aborting"]);
+  assert_eq!(host.traces(), ["counter updated"]);
+  assert_eq!(host.events(), ["counter.incremented"]);
+}
@@ -0,0 +1,171 @@
+#![cfg(feature = "testing")]
+
+use scarab::prelude::*;
+use scarab::testing::MockHost;
+use scarab::types::ParamsError;
+use scarab::wasmlib::*;
+use scarab::Params;
+
+fn positive(value: &i64) -> Result<(), &'static str> {
+  if *value > 0 {
+    Ok(())
+  } else {
+    Err("must be positive")
+  }
+}
+
+fn distinct(value: &Transfer) -> Result<(), String> {
+  if value.from == value.to {
+    Err("`from` and `to` must differ".to_string())
+  } else {
+    Ok(())
+  }
+}
+
+fn ten() -> i64 {
+  10
+}
+
+#[derive(Params)]
+#[scarab(validate = "distinct")]
+struct Transfer {
+  from: ScAgentId,
+  to: ScAgentId,
+  #[scarab(key = "amount", validate = "positive")]
+  value: i64,
+  memo: Option<String>,
+  #[scarab(default = "ten")]
+  fee_limit: i64,
+  #[scarab(default)]
+  urgent: bool,
+  priority: Option<u8>,
+  #[scarab(skip)]
+  attempts: u32,
+}
+
+#[derive(Params)]
+struct Batch(#[scarab(key = "ids")] Vec<i64>, #[scarab(key = "approved")] bool);
+
+fn setup(host: &MockHost) -> ScMutableMap {
+  let params: ScMutableMap = host.params();
+
+  params.get_agent_id("from").set_value(&host.agent_id("alice"));
+  params.get_agent_id("to").set_value(&host.agent_id("bob"));
+  params.get_int64("amount").set_value(5);
+  params
+}
+
+fn transfer(ctx: &ScFuncContext) {
+  let params: Transfer = ctx.get_params();
+
+  ctx.result("amount", params.value);
+}
+
+fn try_transfer(ctx: &ScFuncContext) {
+  match ctx.try_get_params::<Transfer>() {
+    Ok(params) => ctx.result("amount", params.value),
+    Err(error) => ctx.result("missing", error.missing().len() as i64),
+  }
+}
+
+#[test]
+fn test_missing_params() {
+  let host: MockHost = MockHost::new("test");
+  let error: ParamsError = Transfer::from_params(&host.params().immutable()).err().unwrap();
+
+  assert_eq!(error.missing(), ["from", "to", "amount"]);
+  assert_eq!(error.to_string(), "missing required params: from, to, amount");
+}
+
+#[test]
+fn test_optional_params() {
+  let host: MockHost = MockHost::new("test");
+  let params: ScMutableMap = setup(&host);
+  let transfer: Transfer = Transfer::from_params(&params.immutable()).unwrap();
+
+  assert_eq!(transfer.value, 5);
+  assert_eq!(transfer.memo, None);
+  assert_eq!(transfer.fee_limit, 10);
+  assert!(!transfer.urgent);
+  assert_eq!(transfer.priority, None);
+  assert_eq!(transfer.attempts, 0);
+
+  params.get_string("memo").set_value("rent");
+  params.get_int64("feeLimit").set_value(3);
+  params.get_int64("urgent").set_value(1);
+  params.get_int64("priority").set_value(2);
+
+  let transfer: Transfer = Transfer::from_params(&params.immutable()).unwrap();
+
+  assert_eq!(transfer.memo.as_deref(), Some("rent"));
+  assert_eq!(transfer.fee_limit, 3);
+  assert!(transfer.urgent);
+  assert_eq!(transfer.priority, Some(2));
+}
+
+#[test]
+fn test_invalid_params() {
+  let host: MockHost = MockHost::new("test");
+  let params: ScMutableMap = host.params();
+
+  params.get_agent_id("to").set_value(&host.agent_id("bob"));
+  params.get_int64("amount").set_value(-1);
+  params.get_int64("urgent").set_value(2);
+  params.get_int64("priority").set_value(256);
+
+  assert_eq!(
+    Transfer::from_params(&params.immutable()).err().unwrap().to_string(),
+    "missing required params: from; \
+     invalid param `amount`: must be positive; \
+     invalid param `urgent`: `bool` out of range: 2; \
+     invalid param `priority`: `u8` out of range: 256"
+  );
+
+  // The container is only validated once every field is valid
+  let params: ScMutableMap = setup(&host);
+
+  params.get_agent_id("to").set_value(&host.agent_id("alice"));
+
+  assert_eq!(
+    Transfer::from_params(&params.immutable()).err().unwrap().to_string(),
+    "invalid params: `from` and `to` must differ"
+  );
+}
+
+#[test]
+fn test_array_params() {
+  let host: MockHost = MockHost::new("test");
+  let params: ScMutableMap = host.params();
+
+  assert_eq!(
+    Batch::from_params(&params.immutable()).err().unwrap().missing(),
+    ["ids", "approved"]
+  );
+
+  params.set("ids", &[1_i64, 2][..]);
+  params.set("approved", &true);
+
+  let batch: Batch = Batch::from_params(&params.immutable()).unwrap();
+
+  assert_eq!(batch.0, [1, 2]);
+  assert!(batch.1);
+}
+
+#[test]
+fn test_context_params() {
+  let host: MockHost = MockHost::new("test");
+
+  assert_eq!(
+    host.call_func(transfer),
+    Err("missing required params: from, to, amount".to_string())
+  );
+
+  host.call_func(try_transfer).unwrap();
+
+  assert_eq!(host.results().get_int64("missing").value(), 3);
+
+  setup(&host);
+  host.call_func(transfer).unwrap();
+
+  assert_eq!(host.results().get_int64("amount").value(), 5);
+}
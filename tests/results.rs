@@ -0,0 +1,120 @@
+#![cfg(feature = "testing")]
+
+use scarab::prelude::*;
+use scarab::testing::MockHost;
+use scarab::traits::utility::Results;
+use scarab::wasmlib::*;
+use scarab::Results;
+
+fn seven() -> i32 {
+  7
+}
+
+#[derive(Results)]
+struct Info {
+  success: bool,
+  #[scarab(key = "dec")]
+  decimals: u8,
+  total_supply: i64,
+  owner: ScAgentId,
+  memo: Option<String>,
+  #[scarab(default = "seven")]
+  count: i32,
+  #[scarab(skip)]
+  cached: u64,
+}
+
+#[derive(Results)]
+struct Pair(#[scarab(key = "a")] i16, #[scarab(key = "b")] u32);
+
+fn info(memo: Option<String>) -> Info {
+  Info {
+    success: true,
+    decimals: 18,
+    total_supply: 1000,
+    owner: ScAgentId::from_bytes(&[3; 37]),
+    memo,
+    count: 2,
+    cached: 9,
+  }
+}
+
+fn write(ctx: &ScFuncContext) {
+  ctx.set_results(&info(None));
+}
+
+fn write_memo(ctx: &ScFuncContext) {
+  ctx.set_results(&info(Some("rent".to_string())));
+}
+
+fn read(ctx: &ScFuncContext) {
+  let info: Info = Info::from_results(&ctx.call(ScHname::new("other"), ScHname::new("write"), None, None));
+
+  ctx.result("count", info.count);
+}
+
+fn read_pair(ctx: &ScFuncContext) {
+  let _: Pair = Pair::from_results(&ctx.call(ScHname::new("other"), ScHname::new("write"), None, None));
+}
+
+#[test]
+fn test_set_results() {
+  let host: MockHost = MockHost::new("test");
+
+  host.call_func(write).unwrap();
+
+  let results: ScImmutableMap = host.results();
+
+  assert_eq!(results.get_int64("success").value(), 1);
+  assert_eq!(results.get_int64("dec").value(), 18);
+  assert_eq!(results.get_int64("totalSupply").value(), 1000);
+  assert_eq!(results.get_int64("count").value(), 2);
+  assert!(!results.get_string("memo").exists());
+  assert!(!results.get_int64("cached").exists());
+}
+
+#[test]
+fn test_from_results() {
+  let host: MockHost = MockHost::new("test");
+
+  host.call_func(write_memo).unwrap();
+
+  let info: Info = Info::from_results(&host.results());
+
+  assert!(info.success);
+  assert_eq!(info.decimals, 18);
+  assert_eq!(info.total_supply, 1000);
+  assert_eq!(info.owner.to_bytes(), ScAgentId::from_bytes(&[3; 37]).to_bytes());
+  assert_eq!(info.memo.as_deref(), Some("rent"));
+  assert_eq!(info.count, 2);
+  assert_eq!(info.cached, 0);
+
+  // Optional and default fields may be missing from the results
+  let map: ScMutableMap = ScMutableMap::new();
+
+  map.get_int64("success").set_value(0);
+  map.get_int64("dec").set_value(6);
+  map.get_int64("totalSupply").set_value(1);
+  map.get_agent_id("owner").set_value(&info.owner);
+
+  let info: Info = Info::from_results(&map.immutable());
+
+  assert!(!info.success);
+  assert_eq!(info.decimals, 6);
+  assert_eq!(info.memo, None);
+  assert_eq!(info.count, 7);
+}
+
+#[test]
+fn test_missing_results() {
+  let host: MockHost = MockHost::new("test");
+
+  host.register_func("other", "write", write);
+  host.call_func(read).unwrap();
+
+  assert_eq!(host.results().get_int64("count").value(), 2);
+  assert_eq!(
+    host.call_func(read_pair),
+    Err("missing required results: a, b".to_string())
+  );
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "testing")]
+
+use scarab::prelude::*;
+use scarab::storage::IterableMap;
+use scarab::storage::StorageMap;
+use scarab::storage::StorageVec;
+use scarab::testing::MockHost;
+use scarab::wasmlib::*;
+use scarab::State;
+
+#[derive(State)]
+struct Config {
+  fee: i64,
+}
+
+#[derive(State)]
+struct Token {
+  /// The total supply.
+  #[scarab(key = "$supply")]
+  supply: i64,
+  owner: ScAgentId,
+  name: String,
+  balances: StorageMap<ScAgentId, i64>,
+  holders: StorageVec<ScAgentId>,
+  allowances: IterableMap<ScHname, i64>,
+  config: Config,
+  #[scarab(skip)]
+  cache: u8,
+}
+
+fn alice() -> ScAgentId {
+  ScAgentId::from_bytes(&[1; 37])
+}
+
+fn write(ctx: &ScFuncContext) {
+  let token: MutableToken = ctx.get_state_struct::<Token>();
+
+  token.supply().set_value(100);
+  token.name().set_value("Token");
+  token.owner().set_value(&alice());
+  token.balances().insert(&alice(), 40);
+  token.holders().push(alice());
+  token.allowances().insert(&ScHname(5), 7);
+  token.config().fee().set_value(3);
+
+  // The immutable accessor reads the same state
+  let token: ImmutableToken = token.immutable();
+
+  ctx.result("supply", token.supply().value());
+  ctx.result("fee", token.config().fee().value());
+}
+
+fn read(ctx: &ScViewContext) {
+  let token: ImmutableToken = ctx.get_state_struct::<Token>();
+
+  ctx.result("name", token.name().value());
+  ctx.result("owner", token.owner().value());
+  ctx.result("balance", token.balances().get(&alice()).unwrap_or_default());
+  ctx.result("holders", token.holders().len() as i64);
+  ctx.result("allowances", token.allowances().len() as i64);
+}
+
+#[test]
+fn test_state_struct() {
+  let host: MockHost = MockHost::new("test");
+
+  host.call_func(write).unwrap();
+
+  assert_eq!(host.results().get_int64("supply").value(), 100);
+  assert_eq!(host.results().get_int64("fee").value(), 3);
+
+  host.call_view(read).unwrap();
+
+  let results: ScImmutableMap = host.results();
+
+  assert_eq!(results.get_string("name").value(), "Token");
+  assert_eq!(results.get_agent_id("owner").value().to_bytes(), alice().to_bytes());
+  assert_eq!(results.get_int64("balance").value(), 40);
+  assert_eq!(results.get_int64("holders").value(), 1);
+  assert_eq!(results.get_int64("allowances").value(), 1);
+}
+
+#[test]
+fn test_state_keys() {
+  let host: MockHost = MockHost::new("test");
+
+  host.call_func(write).unwrap();
+
+  // Fields are stored at their keys, nested structs in a map of their own
+  assert_eq!(host.state().get_int64("$supply").value(), 100);
+  assert_eq!(host.state().get_string("name").value(), "Token");
+  assert_eq!(host.state().get_map("config").get_int64("fee").value(), 3);
+  assert!(!host.state().get_int64("cache").exists());
+}